@@ -0,0 +1,76 @@
+//! Benchmarks for stepping the engine and counting neighbors.
+//! Run with ```cargo bench```, add ```--features parallel``` to measure the multi-threaded step.
+use conway_rust::conway_engine;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Fixed seed so every run steps the same boards
+const SEED: u64 = 42;
+/// Fraction of cells that start alive
+const DENSITY: f64 = 0.3;
+/// (row, column) sizes of the benchmarked boards
+const SIZES: [usize; 3] = [50, 200, 500];
+
+/// Build an engine with a random board that's the same on every run.
+/// # Params
+/// size: usize, number of rows and columns
+/// # Returns
+/// conway_engine::ConwayEngine, engine that never runs out of steps
+fn seeded_engine(size: usize) -> conway_engine::ConwayEngine {
+    conway_engine::ConwayEngine::new_random(
+        size,
+        size,
+        DENSITY,
+        Some(SEED),
+        768.0,
+        1024.0,
+        0.0,
+        0,
+        conway_engine::MAX_CELLS,
+    )
+    .expect("Benchmark board is valid")
+}
+
+fn bench_take_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("take_step");
+    for size in SIZES.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, size| {
+            // a fresh board for every batch, so the pattern doesn't die out while measuring
+            b.iter_batched_ref(
+                || seeded_engine(*size),
+                |engine| engine.take_step(),
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn bench_get_number_of_neighbors(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_number_of_neighbors");
+    for size in SIZES.iter() {
+        let grid = conway_engine::generate_random_grid(
+            *size,
+            *size,
+            DENSITY,
+            Some(SEED),
+            conway_engine::MAX_CELLS,
+        )
+        .expect("Benchmark board is valid");
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, size| {
+            // count every cell once, the edges and the inside of the board
+            b.iter(|| {
+                let mut total = 0;
+                for row in 0..*size {
+                    for column in 0..*size {
+                        total += grid.get_number_of_neighbors(row, column).unwrap();
+                    }
+                }
+                black_box(total)
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_take_step, bench_get_number_of_neighbors);
+criterion_main!(benches);
@@ -0,0 +1,431 @@
+/// Resolves the CLI arguments into the configuration the simulation runs with.
+/// Every default is filled in here, so ```--print-config``` shows exactly what would run.
+use crate::gui::{CellShape, HudCorner};
+use crate::{parse_arg, parse_color_arg, parse_optional_arg, parse_pixels, resolve_defaults};
+use conway_rust::conway_engine;
+use nannou::color::rgb::Srgb;
+
+/// Effective configuration, built from the CLI arguments with ```from_matches```.
+pub struct Config {
+    /// Files played in order, empty for the default pattern (the headless and GIF runs only use the first one)
+    pub file_locations: Vec<String>,
+    /// (alive, dead) characters the files are read as ASCII art with, None reads them by their extension
+    pub ascii_cells: Option<(char, char)>,
+    /// Bundled pattern loaded instead of the files, empty to use the files
+    pub pattern: String,
+    pub height: f32,
+    pub width: f32,
+    /// Pixels on each side of a cell, the window is sized to fit the grid instead of following height and width
+    pub cell_size: Option<f32>,
+    pub update_rate: f64,
+    pub number_of_steps: usize,
+    /// One line for each default that filled in for a missing --rate or --steps, see ```resolve_defaults```
+    pub notices: Vec<String>,
+    pub alive_color: Srgb<u8>,
+    pub dead_color: Srgb<u8>,
+    pub background: Option<Srgb<u8>>,
+    pub species: bool,
+    pub max_age: usize,
+    pub old_color: Srgb<u8>,
+    /// Color of the cells that died in the last step, None draws them like any dead cell
+    pub dying_color: Option<Srgb<u8>>,
+    pub random_density: Option<f64>,
+    pub seed: Option<u64>,
+    pub random_rows: usize,
+    pub random_columns: usize,
+    pub enable_grid: bool,
+    pub grid_color: Srgb<u8>,
+    pub grid_thickness: f32,
+    pub square_cells: bool,
+    pub cell_shape: CellShape,
+    pub outline_cells: bool,
+    pub show_fps: bool,
+    pub hud: Option<HudCorner>,
+    pub start_paused: bool,
+    pub pause_on_blur: bool,
+    pub boundary: conway_engine::BoundaryCondition,
+    pub infinite: bool,
+    pub neighborhood: conway_engine::Neighborhood,
+    /// Distance the neighborhood reaches from each cell, at least 1, None keeps the file's radius (1 without one)
+    pub radius: Option<usize>,
+    pub backend: conway_engine::Backend,
+    pub rotation: conway_engine::Rotation,
+    pub flip: Option<conway_engine::Flip>,
+    pub detect_cycle: usize,
+    /// Rule given with --rule, None keeps the file's rule (B3/S23 without one)
+    pub rule: Option<String>,
+    /// Location the board is saved to when the simulation ends, empty to skip saving
+    pub save_on_exit: String,
+    pub save_format: conway_engine::SaveFormat,
+    /// Location of the GIF to write instead of opening a window, empty to skip it
+    pub gif_path: String,
+    /// Location of the CSV file the population of every generation is written to, empty to skip it
+    pub log_population: String,
+    /// Localhost port the simulation takes commands on, None to skip listening, see ```control::ControlServer```
+    pub control_port: Option<u16>,
+    pub headless: bool,
+    pub max_cells: usize,
+    /// Short rows in a native file are an error instead of padded with dead cells, see ```conway_engine::LoadOptions```
+    pub strict: bool,
+}
+
+impl Config {
+    /// Resolve the CLI arguments, filling in the defaults for the ones that weren't given.
+    /// An invalid value prints a message and exits with a nonzero code, like ```parse_arg```.
+    /// # Params
+    /// matches: &clap::ArgMatches, the parsed CLI arguments
+    /// # Returns
+    /// Config, the effective configuration
+    pub fn from_matches(matches: &clap::ArgMatches) -> Config {
+        // read in height and width, deafult is 1024 x 768
+        let height: f32 = parse_arg(matches, "height", "768.0");
+        let width: f32 = parse_arg(matches, "width", "1024.0");
+
+        // read in the update rate and number of steps, a note explains each default that fills in for a missing option
+        let (update_rate, number_of_steps, notices) = resolve_defaults(
+            parse_optional_arg(matches, "rate"),
+            parse_optional_arg(matches, "steps"),
+        );
+        if let Err(err) = conway_engine::check_update_rate(update_rate) {
+            eprintln!("Invalid value '{}' for --rate: {}", update_rate, err);
+            std::process::exit(1);
+        }
+
+        // read in the bundled pattern, default is empty (the file is used)
+        let pattern = matches.value_of("pattern").unwrap_or("");
+        if !pattern.is_empty() && conway_rust::patterns::find(pattern).is_none() {
+            eprintln!(
+                "Invalid value '{}' for --pattern: available patterns are {}",
+                pattern,
+                conway_rust::patterns::names()
+            );
+            std::process::exit(1);
+        }
+
+        // read in the grid line style, default is 1 pixel SLATEGREY lines
+        let grid_thickness_value = matches.value_of("grid-thickness").unwrap_or("1");
+        let grid_thickness = match parse_pixels(grid_thickness_value) {
+            Ok(thickness) => thickness,
+            Err(err) => {
+                eprintln!(
+                    "Invalid value '{}' for --grid-thickness: {}",
+                    grid_thickness_value, err
+                );
+                std::process::exit(1);
+            }
+        };
+
+        // read in the cell size, default is none and the window follows --height and --width
+        let cell_size = matches
+            .value_of("cell-size")
+            .map(|value| match parse_pixels(value) {
+                Ok(size) => size,
+                Err(err) => {
+                    eprintln!("Invalid value '{}' for --cell-size: {}", value, err);
+                    std::process::exit(1);
+                }
+            });
+
+        // read in the neighborhood radius, default is the file's radius (1, the 8 or 4 cells around each cell)
+        let radius: Option<usize> = parse_optional_arg(matches, "radius");
+        if radius == Some(0) {
+            eprintln!("Invalid value '0' for --radius: the radius must be at least 1");
+            std::process::exit(1);
+        }
+
+        Config {
+            // read in the game files, default is none (which will generate a default oscillator)
+            file_locations: matches
+                .values_of("file")
+                .map(|values| values.map(str::to_string).collect())
+                .unwrap_or_default(),
+            // the dead character only matters once the alive one switches to ASCII art
+            ascii_cells: parse_optional_arg(matches, "ascii-alive")
+                .map(|alive| (alive, parse_arg(matches, "ascii-dead", "."))),
+            pattern: pattern.to_string(),
+            height,
+            width,
+            cell_size,
+            update_rate,
+            number_of_steps,
+            notices,
+            alive_color: parse_color_arg(matches, "alive", "BLACK"),
+            dead_color: parse_color_arg(matches, "dead", "WHITE"),
+            // default is none, the dead color fills the window
+            background: matches
+                .value_of("background")
+                .map(|value| parse_color_arg(matches, "background", value)),
+            species: matches.is_present("species"),
+            // default is 0, the heat map is disabled
+            max_age: parse_arg(matches, "max-age", "0"),
+            old_color: parse_color_arg(matches, "old", "RED"),
+            // default is none, dead cells vanish right away
+            dying_color: matches
+                .value_of("dying")
+                .map(|value| parse_color_arg(matches, "dying", value)),
+            // a density replaces the game file with a random board
+            random_density: parse_optional_arg(matches, "random"),
+            seed: parse_optional_arg(matches, "seed"),
+            random_rows: parse_arg(matches, "rows", "48"),
+            random_columns: parse_arg(matches, "columns", "64"),
+            enable_grid: matches.is_present("grid"),
+            grid_color: parse_color_arg(matches, "grid-color", "SLATEGREY"),
+            grid_thickness,
+            square_cells: matches.is_present("square-cells"),
+            cell_shape: parse_arg(matches, "cell-shape", "square"),
+            outline_cells: matches.is_present("outline-cells"),
+            show_fps: matches.is_present("show-fps"),
+            // the HUD is hidden without a corner
+            hud: parse_optional_arg(matches, "hud"),
+            start_paused: matches.is_present("start-paused"),
+            pause_on_blur: matches.is_present("pause-on-blur"),
+            // default is dead cells off the grid, --wrap is short for toroidal
+            boundary: if matches.is_present("wrap") {
+                conway_engine::BoundaryCondition::Toroidal
+            } else {
+                parse_arg(matches, "boundary", "dead")
+            },
+            infinite: matches.is_present("infinite"),
+            neighborhood: parse_arg(matches, "neighborhood", "moore"),
+            radius,
+            backend: parse_arg(matches, "backend", "dense"),
+            // default is no rotation or mirror
+            rotation: parse_arg(matches, "rotate", "0"),
+            flip: parse_optional_arg(matches, "flip"),
+            // default is 0, cycle detection is disabled
+            detect_cycle: parse_arg(matches, "detect-cycle", "0"),
+            rule: matches.value_of("rule").map(str::to_string),
+            save_on_exit: matches.value_of("save-on-exit").unwrap_or("").to_string(),
+            save_format: parse_arg(matches, "format", "native"),
+            gif_path: matches.value_of("gif").unwrap_or("").to_string(),
+            log_population: matches.value_of("log-population").unwrap_or("").to_string(),
+            control_port: parse_optional_arg(matches, "control-port"),
+            headless: matches.is_present("headless"),
+            max_cells: parse_optional_arg(matches, "max-cells").unwrap_or(conway_engine::MAX_CELLS),
+            strict: matches.is_present("strict"),
+        }
+    }
+
+    /// Checks the loaders apply to the pattern files, the most cells limit random boards and infinite grids as well.
+    /// # Returns
+    /// conway_engine::LoadOptions, options for ```ConwayEngine::try_new``` and ```ConwayEngine::try_load```
+    pub fn load_options(&self) -> conway_engine::LoadOptions {
+        conway_engine::LoadOptions {
+            max_cells: self.max_cells,
+            strict_columns: self.strict,
+        }
+    }
+
+    /// Where the starting board comes from, in the order they take precedence.
+    /// # Returns
+    /// &'static str, "random", "pattern", "file" or "default"
+    pub fn source(&self) -> &'static str {
+        if self.random_density.is_some() {
+            "random"
+        } else if !self.pattern.is_empty() {
+            "pattern"
+        } else if !self.file_locations.is_empty() {
+            "file"
+        } else {
+            "default"
+        }
+    }
+
+    /// Format the configuration as ```key=value``` lines, the keys match the CLI option names.
+    /// Options that aren't set have an empty value, e.g. ```rule=``` keeps the file's rule.
+    /// # Returns
+    /// String, one line per option, every line ends with a newline
+    pub fn format(&self) -> String {
+        let color =
+            |color: Srgb<u8>| format!("#{:02x}{:02x}{:02x}", color.red, color.green, color.blue);
+        let name = |value: &dyn std::fmt::Debug| format!("{:?}", value).to_ascii_lowercase();
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        let hud = self.hud.map(|corner| {
+            match corner {
+                HudCorner::TopLeft => "top-left",
+                HudCorner::TopRight => "top-right",
+                HudCorner::BottomLeft => "bottom-left",
+                HudCorner::BottomRight => "bottom-right",
+            }
+            .to_string()
+        });
+
+        let lines = [
+            ("source", self.source().to_string()),
+            ("file", self.file_locations.join(",")),
+            (
+                "ascii-alive",
+                optional(self.ascii_cells.map(|cells| cells.0.to_string())),
+            ),
+            (
+                "ascii-dead",
+                optional(self.ascii_cells.map(|cells| cells.1.to_string())),
+            ),
+            ("pattern", self.pattern.clone()),
+            (
+                "random",
+                optional(self.random_density.map(|density| density.to_string())),
+            ),
+            ("seed", optional(self.seed.map(|seed| seed.to_string()))),
+            ("rows", self.random_rows.to_string()),
+            ("columns", self.random_columns.to_string()),
+            ("height", self.height.to_string()),
+            ("width", self.width.to_string()),
+            (
+                "cell-size",
+                optional(self.cell_size.map(|size| size.to_string())),
+            ),
+            ("rate", self.update_rate.to_string()),
+            ("steps", self.number_of_steps.to_string()),
+            ("rule", optional(self.rule.clone())),
+            ("boundary", name(&self.boundary)),
+            ("infinite", self.infinite.to_string()),
+            ("neighborhood", name(&self.neighborhood)),
+            (
+                "radius",
+                optional(self.radius.map(|radius| radius.to_string())),
+            ),
+            ("backend", name(&self.backend)),
+            ("rotate", (self.rotation.quarter_turns() * 90).to_string()),
+            ("flip", optional(self.flip.map(|flip| name(&flip)))),
+            ("detect-cycle", self.detect_cycle.to_string()),
+            ("max-cells", self.max_cells.to_string()),
+            ("strict", self.strict.to_string()),
+            ("alive", color(self.alive_color)),
+            ("dead", color(self.dead_color)),
+            ("background", optional(self.background.map(color))),
+            ("old", color(self.old_color)),
+            ("dying", optional(self.dying_color.map(color))),
+            ("max-age", self.max_age.to_string()),
+            ("species", self.species.to_string()),
+            ("grid", self.enable_grid.to_string()),
+            ("grid-color", color(self.grid_color)),
+            ("grid-thickness", self.grid_thickness.to_string()),
+            ("square-cells", self.square_cells.to_string()),
+            ("cell-shape", name(&self.cell_shape)),
+            ("outline-cells", self.outline_cells.to_string()),
+            ("show-fps", self.show_fps.to_string()),
+            ("hud", optional(hud)),
+            ("start-paused", self.start_paused.to_string()),
+            ("pause-on-blur", self.pause_on_blur.to_string()),
+            ("save-on-exit", self.save_on_exit.clone()),
+            ("format", name(&self.save_format)),
+            ("gif", self.gif_path.clone()),
+            ("log-population", self.log_population.clone()),
+            (
+                "control-port",
+                optional(self.control_port.map(|port| port.to_string())),
+            ),
+            ("headless", self.headless.to_string()),
+        ];
+        lines
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// helper function, parses the arguments like ```main``` does
+    fn config_from(args: &[&str]) -> Config {
+        let yaml = load_yaml!("cli.yml");
+        let matches = clap::App::from_yaml(yaml).get_matches_from(args);
+        Config::from_matches(&matches)
+    }
+
+    #[test]
+    fn test_from_matches() {
+        // nothing given, every default is filled in
+        let config = config_from(&["conway-rust"]);
+        assert_eq!(config.source(), "default");
+        assert!(config.file_locations.is_empty());
+        assert_eq!((config.height, config.width), (768.0, 1024.0));
+        assert_eq!(
+            (config.update_rate, config.number_of_steps),
+            (crate::DEFAULT_UPDATE_RATE, crate::DEFAULT_STEPS)
+        );
+        assert_eq!(config.notices.len(), 2);
+        assert_eq!(config.rule, None);
+        assert_eq!(config.boundary, conway_engine::BoundaryCondition::Dead);
+        assert_eq!(config.max_cells, conway_engine::MAX_CELLS);
+        assert!(!config.strict);
+        assert_eq!(config.load_options(), conway_engine::LoadOptions::default());
+        assert_eq!(config.radius, None);
+        assert_eq!(config.cell_size, None);
+        let printed = config.format();
+        assert!(printed.starts_with("source=default\nfile=\n"));
+        assert!(printed.contains("\nrule=\n"));
+        assert!(printed.contains("\nalive=#000000\ndead=#ffffff\n"));
+        assert!(printed.contains("\ngrid-color=#708090\n"));
+        assert!(printed.ends_with("\nheadless=false\n"));
+
+        // given values win over the defaults
+        let config = config_from(&[
+            "conway-rust",
+            "-f",
+            "a.txt",
+            "-f",
+            "b.rle",
+            "--rate",
+            "0.25",
+            "--steps",
+            "0",
+            "--rule",
+            "B36/S23",
+            "--wrap",
+            "--neighborhood",
+            "vonneumann",
+            "--radius",
+            "2",
+            "--rotate",
+            "90",
+            "--alive",
+            "red",
+            "--hud",
+            "bottom-right",
+            "--dying",
+            "#404040",
+            "--cell-size",
+            "12",
+            "--control-port",
+            "7070",
+            "--strict",
+        ]);
+        assert_eq!(config.source(), "file");
+        assert_eq!(config.file_locations, vec!["a.txt", "b.rle"]);
+        assert_eq!((config.update_rate, config.number_of_steps), (0.25, 0));
+        assert!(config.notices.is_empty());
+        let printed = config.format();
+        for line in [
+            "file=a.txt,b.rle",
+            "rate=0.25",
+            "steps=0",
+            "rule=B36/S23",
+            "boundary=toroidal",
+            "neighborhood=vonneumann",
+            "radius=2",
+            "rotate=90",
+            "alive=#ff0000",
+            "hud=bottom-right",
+            "dying=#404040",
+            "cell-size=12",
+            "control-port=7070",
+            "strict=true",
+        ] {
+            assert!(printed.contains(&format!("{}\n", line)), "{}", line);
+        }
+
+        // a random board wins over the pattern and the files
+        let config = config_from(&["conway-rust", "-p", "glider", "--random", "0.3"]);
+        assert_eq!(config.source(), "random");
+        assert_eq!(config.pattern, "glider");
+
+        // the dead ASCII character defaults to '.' once the alive one is given
+        assert_eq!(config_from(&["conway-rust"]).ascii_cells, None);
+        let config = config_from(&["conway-rust", "-f", "a.txt", "--ascii-alive", "*"]);
+        assert_eq!(config.ascii_cells, Some(('*', '.')));
+    }
+}
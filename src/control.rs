@@ -0,0 +1,145 @@
+/// Accepts line based commands for a running simulation on a localhost TCP socket, see ```--control-port```.
+/// Every reply ends with an ```ok``` line, or is a single ```error: <reason>``` line for a command it doesn't know.
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+
+/// Command a client sends, one per line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    /// Take a single step, paused or not
+    Step,
+    /// Stop stepping on the update rate
+    Pause,
+    /// Start stepping on the update rate again
+    Resume,
+    /// Start over from the initial board
+    Reset,
+    /// Reply with the generation and the board, see ```gui::format_dump```
+    Dump,
+}
+
+impl FromStr for Command {
+    type Err = &'static str;
+
+    /// Parse a command, case insensitive.
+    /// # Params
+    /// line: &str, "step", "pause", "resume", "reset" or "dump"
+    /// # Returns
+    /// Result<Command, &'static str>, the command or an error if it is unknown
+    fn from_str(line: &str) -> Result<Command, &'static str> {
+        match line.trim().to_lowercase().as_str() {
+            "step" => Ok(Command::Step),
+            "pause" => Ok(Command::Pause),
+            "resume" => Ok(Command::Resume),
+            "reset" => Ok(Command::Reset),
+            "dump" => Ok(Command::Dump),
+            _ => Err("Unknown command, expected step, pause, resume, reset or dump"),
+        }
+    }
+}
+
+/// Command waiting for the simulation to apply it, the client is blocked until it is replied to.
+pub struct Request {
+    pub command: Command,
+    reply: Sender<String>,
+}
+
+impl Request {
+    /// Answer the client, the text is sent before the closing ```ok``` line.
+    /// A client that already disconnected is ignored.
+    /// # Params
+    /// text: String, lines to send back, empty for commands without output
+    pub fn reply(self, text: String) {
+        let _ = self.reply.send(text);
+    }
+}
+
+/// Listener running on a background thread, each client is served on its own thread.
+/// The simulation picks the commands up with ```next_request``` wherever it polls for them.
+pub struct ControlServer {
+    // Mutex so the GUI can keep the server in a static
+    requests: Mutex<Receiver<Request>>,
+}
+
+impl ControlServer {
+    /// Listen on localhost, the listener keeps running until the process exits.
+    /// # Params
+    /// port: u16, port to listen on
+    /// # Returns
+    /// io::Result<ControlServer>, the server or the error from binding the port
+    pub fn start(port: u16) -> io::Result<ControlServer> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let sender = sender.clone();
+                // a client that disconnects (or errors) only ends its own thread
+                thread::spawn(move || {
+                    let _ = serve_client(stream, &sender);
+                });
+            }
+        });
+        Ok(ControlServer {
+            requests: Mutex::new(receiver),
+        })
+    }
+
+    /// Take the oldest command that hasn't been applied yet, without waiting for one.
+    /// # Returns
+    /// Option<Request>, the command or None if there's nothing to apply
+    pub fn next_request(&self) -> Option<Request> {
+        self.requests.lock().ok()?.try_recv().ok()
+    }
+}
+
+/// Read commands from a client until it disconnects, answering each one before reading the next.
+/// # Params
+/// - stream: TcpStream, connection to the client
+/// - requests: &Sender<Request>, channel the simulation reads the commands from
+/// # Returns
+/// io::Result<()>, the error from reading or writing the connection, if any
+fn serve_client(stream: TcpStream, requests: &Sender<Request>) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match line.parse::<Command>() {
+            Ok(command) => {
+                let (reply, replied) = mpsc::channel();
+                // the simulation stopped listening, there's nothing left to answer with
+                if requests.send(Request { command, reply }).is_err() {
+                    break;
+                }
+                match replied.recv() {
+                    Ok(text) => format!("{}ok\n", text),
+                    Err(_) => break,
+                }
+            }
+            Err(err) => format!("error: {}\n", err),
+        };
+        writer.write_all(response.as_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!("step".parse::<Command>(), Ok(Command::Step));
+        assert_eq!(" Pause \r".parse::<Command>(), Ok(Command::Pause));
+        assert_eq!("RESUME".parse::<Command>(), Ok(Command::Resume));
+        assert_eq!("reset".parse::<Command>(), Ok(Command::Reset));
+        assert_eq!("dump".parse::<Command>(), Ok(Command::Dump));
+        assert!("jump".parse::<Command>().is_err());
+        assert!("".parse::<Command>().is_err());
+    }
+}
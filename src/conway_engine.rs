@@ -1,21 +1,162 @@
 /// Engine for running Conway's Game of Life
-#[path = "grid.rs"]
-mod grid;
+use crate::{grid, patterns, ruleset, sparse_grid};
+use flate2::read::MultiGzDecoder;
+use grid::GridBackend;
+pub use grid::{Backend, BoundaryCondition, Flip, Neighborhood, Rotation};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, VecDeque};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock, PoisonError};
 use std::time::Duration;
 
+/// How a simulation ended, see ```ConwayEngine::classify_outcome```.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    /// The board stopped changing (an empty board is stable too)
+    Stable,
+    /// The board repeats with the given period
+    Oscillating(usize),
+    /// Neither stable nor a known oscillator when the steps ran out
+    StepLimit,
+}
+
+/// File format the board is saved in, see ```ConwayEngine::save_as```.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SaveFormat {
+    /// Comma delimited text with the header, see ```save_to_file```
+    #[default]
+    Native,
+    /// One bit per cell, see ```Grid::to_bytes```, loaded from files ending in ```.bin```
+    Binary,
+}
+
+impl FromStr for SaveFormat {
+    type Err = &'static str;
+
+    /// Parse a save format name, case insensitive: "native" or "binary".
+    /// # Params
+    /// name: &str, name of the format
+    /// # Returns
+    /// Result<SaveFormat, &'static str>, the format or an error if the name is unknown
+    fn from_str(name: &str) -> Result<SaveFormat, &'static str> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "native" => Ok(SaveFormat::Native),
+            "binary" => Ok(SaveFormat::Binary),
+            _ => Err("Format must be native or binary"),
+        }
+    }
+}
+
+/// Checks applied while a pattern file is loaded, see ```ConwayEngine::try_new```.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoadOptions {
+    /// Most cells (rows * columns) the grid can have, larger patterns are rejected before anything is allocated.
+    /// The limit is for a single grid, see ```MAX_CELLS```.
+    pub max_cells: usize,
+    /// Rows of a native file shorter than the header width are an error instead of padded with dead cells.
+    /// Rows that are too long are always an error.
+    pub strict_columns: bool,
+}
+
+impl Default for LoadOptions {
+    /// MAX_CELLS, and short rows are padded with dead cells.
+    fn default() -> LoadOptions {
+        LoadOptions {
+            max_cells: MAX_CELLS,
+            strict_columns: false,
+        }
+    }
+}
+
 pub struct ConwayEngine {
-    grid: grid::Grid<usize>,
+    grid: Box<dyn GridBackend>,
     height: f32,
     width: f32,
-    update_rate: usize,
+    update_rate: f64,
     number_of_steps: usize,
+    /// Number of steps the run started with, 0 for non-stop, saved in the header so a finished run isn't saved as non-stop
+    step_budget: usize,
     simulation_ended: bool,
     simulation_non_stop: bool,
     name: String,
+    boundary: BoundaryCondition,
+    infinite: bool,
+    /// Most cells an infinite grid can grow to, see ```set_max_cells```
+    max_cells: usize,
+    /// Living cells hold a species id instead of their age, see ```set_species```
+    species: bool,
+    neighborhood: Neighborhood,
+    /// Distance the neighborhood reaches from the cell, 1 for the usual 8 (or 4) neighbors, see ```set_radius```
+    radius: usize,
+    ruleset: ruleset::Ruleset,
+    history: VecDeque<Box<dyn GridBackend>>,
+    /// Hash of each generation in the history, in the same order, so ```detect_period``` only compares grids that hash the same
+    history_hashes: VecDeque<u64>,
+    history_capacity: usize,
+    stable: bool,
+    extinct: bool,
+    period: Option<usize>,
+    previous_population: usize,
+    peak_population: usize,
+    generation: usize,
+    /// Longest period looked for by the hash based cycle detection, 0 disables it
+    cycle_window: usize,
+    /// Hashes of the most recent generations, newest last
+    cycle_hashes: VecDeque<u64>,
+    /// Mutex so a Send callback keeps the engine Sync for the parallel step, it's only reached through ```get_mut```
+    step_callback: Mutex<Option<StepCallback>>,
+    /// Rule applied instead of the ruleset when set, see ```with_rule```
+    rule: Option<Box<dyn ruleset::Rule>>,
+    /// Grid the next generation is written into when there's no history entry to reuse, the previous generation
+    back_buffer: Option<Box<dyn GridBackend>>,
+    /// Step candidates and their next states, kept between steps so their storage is reused
+    candidates: Vec<(usize, usize)>,
+    states: Vec<usize>,
+    /// Cells alive before the last step and dead after it, see ```dying_cells```
+    dying: Vec<(usize, usize)>,
 }
 
+// Shortest time between steps in seconds (1ms), keeps zero or negative rates from spinning.
+const MIN_UPDATE_RATE: f64 = 0.001;
+
+/// Longest time between steps in seconds (1 day), keeps huge rates from overflowing a Duration.
+pub const MAX_UPDATE_RATE: f64 = 86_400.0;
+
+// Largest row or column size an infinite grid can grow to, keeps runaway patterns from using all the memory.
+const MAX_INFINITE_GRID_SIZE: usize = 1024;
+
+/// Default for the most cells a loaded or generated grid can have, keeps a huge header from allocating all the memory.
+/// Change it with ```LoadOptions::max_cells```, and ```ConwayEngine::set_max_cells``` for infinite grids.
+/// The limit is for a single grid, the history kept for ```step_back``` holds up to DEFAULT_HISTORY_CAPACITY more
+/// grids of the same size, see ```ConwayEngine::set_history_capacity```.
+pub const MAX_CELLS: usize = 100_000_000;
+
+// Default number of previous generations kept for stepping back.
+const DEFAULT_HISTORY_CAPACITY: usize = 64;
+// Pixel size of a cell when a bundled pattern or the default pattern sizes its grid to the window
+const PATTERN_CELL_SIZE: f32 = 16.0;
+
+/// Called at the end of every step with the new generation number and grid, see ```set_step_callback```.
+/// With the parallel feature it has to be Send, the engine is shared with the rayon threads while it steps.
+#[cfg(feature = "parallel")]
+pub type StepCallback = Box<dyn FnMut(usize, &grid::Grid<usize>) + Send>;
+/// Called at the end of every step with the new generation number and grid, see ```set_step_callback```.
+#[cfg(not(feature = "parallel"))]
+pub type StepCallback = Box<dyn FnMut(usize, &grid::Grid<usize>)>;
+
+/// File name that reads the pattern from standard input instead of a file.
+pub const STDIN_FILE_NAME: &str = "-";
+
+/// First two bytes of every gzip file, a compressed native file is found by them even without the ```.gz``` extension.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+// Lines read from standard input, it can only be read once but the GUI builds the engine again on reset.
+static STDIN_LINES: OnceLock<Result<Vec<String>, &'static str>> = OnceLock::new();
+
 // Static memory with a built in oscillator.
 static DEFAULT_ARRAY: [&str; 6] = [
     "5, 5",
@@ -29,54 +170,494 @@ static DEFAULT_ARRAY: [&str; 6] = [
 impl ConwayEngine {
     /// Creates a new engine instance based on the input file, and parameters passed in.
     /// If the file is missing data, the parameters passed in will be used instead.
+    /// If the file can't be opened, the default blinker is placed in the center of a grid sized to the window.
+    /// Files ending in ```.rle``` are parsed as Run Length Encoded patterns, ```.cells``` files as plaintext patterns,
+    /// everything else uses the native format.
+    /// A file name of ```-``` reads a native or RLE pattern from standard input, see ```from_lines```.
+    /// # Panics
+    /// If the file can be read but not parsed, see ```try_new``` to handle the error instead.
     pub fn new(
         filename: &String,
         height: f32,
         width: f32,
-        default_update_rate: usize,
+        default_update_rate: f64,
+        default_steps: usize,
+    ) -> ConwayEngine {
+        ConwayEngine::try_new(
+            filename,
+            height,
+            width,
+            default_update_rate,
+            default_steps,
+            LoadOptions::default(),
+        )
+        .expect("Failed to load the engine file")
+    }
+
+    /// Same as ```new```, but a file that can be read and fails to parse is returned as an error.
+    /// # Params
+    /// - filename: &String, location of the pattern file
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - default_update_rate: f64, seconds between steps if the file doesn't set it
+    /// - default_steps: usize, number of steps if the file doesn't set it, 0 is infinite
+    /// - options: LoadOptions, checks applied to the file, ```new``` uses the defaults
+    /// # Returns
+    /// Result<ConwayEngine, String>, the engine or an error if the file is invalid, e.g. a grid without rows or columns
+    pub fn try_new(
+        filename: &String,
+        height: f32,
+        width: f32,
+        default_update_rate: f64,
+        default_steps: usize,
+        options: LoadOptions,
+    ) -> Result<ConwayEngine, String> {
+        // empty standard input falls back to the default oscillator like a missing file
+        if filename == STDIN_FILE_NAME {
+            return ConwayEngine::from_lines(
+                read_stdin_lines()?,
+                "standard input",
+                height,
+                width,
+                default_update_rate,
+                default_steps,
+                options,
+            );
+        }
+
+        // a file that can be opened has to parse, only a missing file falls back to the default oscillator
+        if File::open(filename).is_ok() {
+            return ConwayEngine::try_load(
+                filename,
+                height,
+                width,
+                default_update_rate,
+                default_steps,
+                options,
+            );
+        }
+
+        Ok(ConwayEngine::default_pattern(
+            height,
+            width,
+            default_update_rate,
+            default_steps,
+        ))
+    }
+
+    /// Load an engine from an ASCII art file, see ```parse_ascii```.  The file has to exist, there's no default pattern.
+    /// # Params
+    /// - filename: &String, location of the ASCII file
+    /// - cells: (char, char), characters of the (alive, dead) cells
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - default_update_rate: f64, seconds between steps
+    /// - default_steps: usize, number of steps, 0 is infinite
+    /// - options: LoadOptions, only the cell limit applies to ASCII art
+    /// # Returns
+    /// Result<ConwayEngine, String>, the engine or an error if the file can't be read or parsed
+    pub fn try_load_ascii(
+        filename: &String,
+        cells: (char, char),
+        height: f32,
+        width: f32,
+        default_update_rate: f64,
+        default_steps: usize,
+        options: LoadOptions,
+    ) -> Result<ConwayEngine, String> {
+        // standard input is read without its blank and '#' lines, which are rows of an ASCII pattern
+        if filename == STDIN_FILE_NAME {
+            return Err("ASCII patterns can't be read from standard input".to_string());
+        }
+        let contents = std::fs::read_to_string(filename).map_err(|_err| "Failed to read file")?;
+        let grid = parse_ascii(&contents, cells.0, cells.1, options.max_cells)?;
+        Ok(ConwayEngine::from_grid(
+            grid,
+            filename,
+            height,
+            width,
+            default_update_rate,
+            default_steps,
+        ))
+    }
+
+    /// Creates an engine with the default blinker in the center of a grid sized to the window.
+    /// # Params
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - default_update_rate: f64, seconds between each step
+    /// - default_steps: usize, number of steps to take, 0 is non-stop
+    /// # Returns
+    /// ConwayEngine, engine ready to step
+    fn default_pattern(
+        height: f32,
+        width: f32,
+        default_update_rate: f64,
         default_steps: usize,
     ) -> ConwayEngine {
-        let mut name = "No file found, using default pattern";
-
-        // read the file, or sub in the default oscillator
-        let mut file_data = match read_engine_file(filename) {
-            Ok(data) => {
-                // file parsed, set the filename as the name of the engine
-                name = filename;
-                data
+        let mut file_data = generate_default_file_array();
+        let (row_size, column_size, update_rate, number_of_steps, _ruleset, _radius) =
+            parse_header(file_data.remove(0), default_update_rate, default_steps)
+                .expect("Default pattern header is valid");
+        let grid = generate_grid(row_size, column_size, file_data, LoadOptions::default())
+            .expect("Default pattern grid is valid");
+
+        // the default pattern follows the window shape instead of staying a small square
+        let (row_size, column_size) = window_grid_size(height, width, grid.size());
+        ConwayEngine::from_grid(
+            grid.resize_centered(row_size, column_size),
+            "No file found, using default pattern",
+            height,
+            width,
+            update_rate,
+            number_of_steps,
+        )
+    }
+
+    /// Load an engine from a file, without falling back to the default pattern.
+    /// Files ending in ```.rle``` are parsed as Run Length Encoded patterns, ```.cells``` files as plaintext patterns,
+    /// ```.bin``` files as binary grids (see ```Grid::from_bytes```), everything else uses the native format.
+    /// Native files can place bundled patterns with ```PLACE <name> AT <row>,<column>``` lines, see ```place_pattern```.
+    /// A file name of ```-``` reads a native or RLE pattern from standard input.
+    /// # Params
+    /// - filename: &String, location of the pattern file
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - default_update_rate: f64, seconds between steps if the file doesn't set it
+    /// - default_steps: usize, number of steps if the file doesn't set it, 0 is infinite
+    /// - options: LoadOptions, checks applied to the file
+    /// # Returns
+    /// Result<ConwayEngine, String>, the engine or an error if the file can't be read or parsed
+    pub fn try_load(
+        filename: &String,
+        height: f32,
+        width: f32,
+        default_update_rate: f64,
+        default_steps: usize,
+        options: LoadOptions,
+    ) -> Result<ConwayEngine, String> {
+        if filename == STDIN_FILE_NAME {
+            let file_data = read_stdin_lines()?;
+            if file_data.is_empty() {
+                return Err("Standard input is empty".to_string());
+            }
+            return ConwayEngine::from_lines(
+                file_data,
+                "standard input",
+                height,
+                width,
+                default_update_rate,
+                default_steps,
+                options,
+            );
+        }
+
+        // RLE files only describe the pattern, the update rate and number of steps come from the defaults
+        if has_extension(filename, "rle") {
+            let contents =
+                std::fs::read_to_string(filename).map_err(|_err| "Failed to read file")?;
+            let (grid, ruleset) = parse_rle(&contents, options.max_cells)?;
+            let mut engine = ConwayEngine::from_grid(
+                grid,
+                filename,
+                height,
+                width,
+                default_update_rate,
+                default_steps,
+            );
+            engine.set_ruleset(ruleset);
+            return Ok(engine);
+        }
+
+        // binary files only hold the cells too
+        if has_extension(filename, "bin") {
+            let data = std::fs::read(filename).map_err(|_err| "Failed to read file")?;
+            let grid = grid::Grid::from_bytes(&data, options.max_cells)?;
+            return Ok(ConwayEngine::from_grid(
+                grid,
+                filename,
+                height,
+                width,
+                default_update_rate,
+                default_steps,
+            ));
+        }
+
+        // plaintext files only describe the pattern as well
+        if has_extension(filename, "cells") {
+            let contents =
+                std::fs::read_to_string(filename).map_err(|_err| "Failed to read file")?;
+            let grid = parse_plaintext(&contents, options.max_cells)?;
+            return Ok(ConwayEngine::from_grid(
+                grid,
+                filename,
+                height,
+                width,
+                default_update_rate,
+                default_steps,
+            ));
+        }
+
+        let file_data = read_engine_file(filename)?;
+        if file_data.is_empty() {
+            return Err("File is empty".to_string());
+        }
+        ConwayEngine::from_native_lines(
+            file_data,
+            filename,
+            height,
+            width,
+            default_update_rate,
+            default_steps,
+            options,
+        )
+    }
+
+    /// Creates an engine from lines of a pattern without a file extension to pick the format, e.g. standard input.
+    /// A first line starting with ```x``` is an RLE header (native headers start with the row size),
+    /// everything else uses the native format. No lines gives the default blinker, like a missing file.
+    /// # Params
+    /// - file_data: Vec<String>, lines from ```read_engine_lines```, without blank lines or comments
+    /// - name: &str, name of the engine shown in the title
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - default_update_rate: f64, seconds between steps if the pattern doesn't set it
+    /// - default_steps: usize, number of steps if the pattern doesn't set it, 0 is infinite
+    /// - options: LoadOptions, checks applied to the pattern
+    /// # Returns
+    /// Result<ConwayEngine, String>, the engine or an error if the lines can't be parsed
+    fn from_lines(
+        file_data: Vec<String>,
+        name: &str,
+        height: f32,
+        width: f32,
+        default_update_rate: f64,
+        default_steps: usize,
+        options: LoadOptions,
+    ) -> Result<ConwayEngine, String> {
+        let first_line = match file_data.first() {
+            Some(line) => line,
+            None => {
+                return Ok(ConwayEngine::default_pattern(
+                    height,
+                    width,
+                    default_update_rate,
+                    default_steps,
+                ))
             }
-            Err(_err) => generate_default_file_array(),
         };
+        if first_line.trim_start().starts_with('x') {
+            let (grid, ruleset) = parse_rle(&file_data.join("\n"), options.max_cells)?;
+            let mut engine = ConwayEngine::from_grid(
+                grid,
+                name,
+                height,
+                width,
+                default_update_rate,
+                default_steps,
+            );
+            engine.set_ruleset(ruleset);
+            return Ok(engine);
+        }
+        ConwayEngine::from_native_lines(
+            file_data,
+            name,
+            height,
+            width,
+            default_update_rate,
+            default_steps,
+            options,
+        )
+    }
+
+    /// Creates an engine from the lines of a native file, the header first.
+    /// # Params
+    /// - file_data: Vec<String>, lines of the file without blank lines or comments, must not be empty
+    /// - name: &str, name of the engine shown in the title
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - default_update_rate: f64, seconds between steps if the header doesn't set it
+    /// - default_steps: usize, number of steps if the header doesn't set it, 0 is infinite
+    /// - options: LoadOptions, checks applied to the header and cells
+    /// # Returns
+    /// Result<ConwayEngine, String>, the engine or an error if the header, cells or placements can't be parsed
+    fn from_native_lines(
+        mut file_data: Vec<String>,
+        name: &str,
+        height: f32,
+        width: f32,
+        default_update_rate: f64,
+        default_steps: usize,
+        options: LoadOptions,
+    ) -> Result<ConwayEngine, String> {
         // parse the header
-        let header_data = parse_header(file_data.remove(0), default_update_rate, default_steps)
-            .expect("Failed to parse engine file header");
+        let header_data = parse_header(file_data.remove(0), default_update_rate, default_steps)?;
         // store elements to variables
         let row_size = header_data.0;
         let column_size = header_data.1;
         let update_rate = header_data.2;
         let number_of_steps = header_data.3;
-        // generate the grid
-        let grid = generate_grid(
-            row_size,
-            column_size,
-            file_data[0..file_data.len()].to_vec(),
-        )
-        .expect("Failed to generate the grid");
+        // generate the grid, the placements are stamped on top of the cell rows
+        let (placements, cell_lines): (Vec<String>, Vec<String>) =
+            file_data.into_iter().partition(|line| is_placement(line));
+        let mut grid = generate_grid(row_size, column_size, cell_lines, options)?;
+        for placement in placements.iter() {
+            place_pattern(&mut grid, placement)
+                .map_err(|err| format!("{}: '{}'", err, placement.trim()))?;
+        }
+
+        let mut engine =
+            ConwayEngine::from_grid(grid, name, height, width, update_rate, number_of_steps);
+        engine.set_ruleset(header_data.4);
+        engine.set_radius(header_data.5);
+        Ok(engine)
+    }
+
+    /// Creates a new engine instance from a bundled pattern, see the ```patterns``` module.
+    /// The grid is sized to the window with PATTERN_CELL_SIZE pixel cells (larger if the pattern doesn't fit),
+    /// and the pattern is placed in its center.
+    /// # Params
+    /// - name: &str, name of the pattern, ignoring case
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - update_rate: f64, seconds between each step
+    /// - number_of_steps: usize, number of steps to take, 0 is non-stop
+    /// # Returns
+    /// Result<ConwayEngine, &'static str>, engine ready to step or an error if there is no pattern with that name
+    pub fn from_pattern(
+        name: &str,
+        height: f32,
+        width: f32,
+        update_rate: f64,
+        number_of_steps: usize,
+    ) -> Result<ConwayEngine, &'static str> {
+        let pattern = match patterns::find(name) {
+            Some(pattern) => pattern,
+            None => return Err("Unknown pattern"),
+        };
+        let (pattern_grid, ruleset) = parse_rle(pattern, MAX_CELLS)?;
+        let (pattern_rows, pattern_columns) = pattern_grid.size();
+        // leave at least one dead cell around the pattern
+        let (row_size, column_size) =
+            window_grid_size(height, width, (pattern_rows + 2, pattern_columns + 2));
+        let grid = pattern_grid.resize_centered(row_size, column_size);
+
+        let mut engine = ConwayEngine::from_grid(
+            grid,
+            &name.trim().to_ascii_lowercase(),
+            height,
+            width,
+            update_rate,
+            number_of_steps,
+        );
+        engine.set_ruleset(ruleset);
+        Ok(engine)
+    }
 
-        ConwayEngine {
+    /// Creates a new engine instance with a randomly filled grid instead of reading a file.
+    /// # Params
+    /// - row_size: usize, number of rows in the grid
+    /// - column_size: usize, number of columns in the grid
+    /// - density: f64, probability (0.0 - 1.0) of each cell starting alive
+    /// - seed: Option<u64>, seed for the random number generator, the same seed always gives the same board
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - update_rate: f64, seconds between each step
+    /// - number_of_steps: usize, number of steps to take, 0 is non-stop
+    /// - max_cells: usize, largest rows * columns allowed, see ```MAX_CELLS```
+    /// # Returns
+    /// Result<ConwayEngine, &'static str>, engine ready to step or an error if the density or size is out of range
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_random(
+        row_size: usize,
+        column_size: usize,
+        density: f64,
+        seed: Option<u64>,
+        height: f32,
+        width: f32,
+        update_rate: f64,
+        number_of_steps: usize,
+        max_cells: usize,
+    ) -> Result<ConwayEngine, &'static str> {
+        let grid = generate_random_grid(row_size, column_size, density, seed, max_cells)?;
+        Ok(ConwayEngine::from_grid(
             grid,
+            "random board",
+            height,
+            width,
+            update_rate,
+            number_of_steps,
+        ))
+    }
+
+    /// Creates a new engine instance around an already generated grid.
+    /// # Params
+    /// - grid: grid::Grid<usize>, initial state of the simulation
+    /// - name: &str, name of the engine shown in the title
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - update_rate: f64, seconds between each step, fractions of a second are allowed
+    /// - number_of_steps: usize, number of steps to take, 0 is non-stop
+    /// # Returns
+    /// ConwayEngine, engine ready to step
+    pub fn from_grid(
+        grid: grid::Grid<usize>,
+        name: &str,
+        height: f32,
+        width: f32,
+        update_rate: f64,
+        number_of_steps: usize,
+    ) -> ConwayEngine {
+        let mut engine = ConwayEngine {
+            grid: Box::new(grid),
             height,
             width,
             update_rate,
             number_of_steps,
+            step_budget: number_of_steps,
             simulation_ended: false,
-            simulation_non_stop: if number_of_steps == 0 { true } else { false },
+            simulation_non_stop: number_of_steps == 0,
             name: name.to_string(),
-        }
+            boundary: BoundaryCondition::default(),
+            infinite: false,
+            max_cells: MAX_CELLS,
+            species: false,
+            neighborhood: Neighborhood::default(),
+            radius: 1,
+            ruleset: ruleset::Ruleset::default(),
+            history: VecDeque::with_capacity(DEFAULT_HISTORY_CAPACITY),
+            history_hashes: VecDeque::with_capacity(DEFAULT_HISTORY_CAPACITY),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
+            stable: false,
+            extinct: false,
+            period: None,
+            previous_population: 0,
+            peak_population: 0,
+            generation: 0,
+            cycle_window: 0,
+            cycle_hashes: VecDeque::new(),
+            step_callback: Mutex::new(None),
+            rule: None,
+            back_buffer: None,
+            candidates: Vec::new(),
+            states: Vec::new(),
+            dying: Vec::new(),
+        };
+        engine.previous_population = engine.population();
+        engine.peak_population = engine.previous_population;
+
+        engine
     }
 
     /// Take a step in the simulation.
-    /// This is where the rules of the game are applied to the application.
+    /// This is where the rules of the game are applied to the application, using the engine's ruleset.
+    /// Living cells hold their age, the number of consecutive generations they have been alive.
+    /// New cells are born with an age of 1 and surviving cells age by 1 each step.
+    /// # NOTE
+    /// The grid is double buffered, every cell of the next generation is computed from the current grid only
+    /// and written into a separate grid that replaces the current one once it is complete.
+    /// Updating the current grid in place would let cells that were already stepped change the neighbor counts
+    /// of the cells after them, ```test_double_buffered_step``` guards against that.
     pub fn take_step(&mut self) {
         // If the simulation is marked as ended, skip this fucntion
         if self.simulation_ended {
@@ -94,143 +675,940 @@ impl ConwayEngine {
             }
         }
 
-        // Generate new grid to fill in next steps
-        let mut next_grid = self.grid.clone();
-        let (row_size, column_size) = self.grid.size();
-        for row_index in 0..row_size {
-            for column_index in 0..column_size {
-                let number_of_neighbors = self
-                    .grid
-                    .get_number_of_neighbors(row_index, column_index)
-                    .expect("Failed to get the number of neighbors");
-                let cell_status = self
-                    .grid
-                    .get(row_index, column_index)
-                    .expect("Failed to get cell");
-                if (number_of_neighbors < 2 || number_of_neighbors > 3) && *cell_status == 1 {
-                    next_grid
-                        .set(row_index, column_index, 0)
-                        .expect("Failed to kill cell");
-                } else if number_of_neighbors == 3 && *cell_status == 0 {
-                    next_grid
-                        .set(row_index, column_index, 1)
-                        .expect("Failed to create cell");
-                }
+        self.previous_population = self.population();
+        // picked before the generation moves on, B0 rules alternate between two rulesets
+        let b0_ruleset = self.step_ruleset();
+        self.generation += 1;
+
+        // Generate new grid to fill in next steps, the scratch buffers are taken out so self can still be borrowed
+        let mut next_grid = self.take_back_buffer();
+        let mut candidates = std::mem::take(&mut self.candidates);
+        let mut states = std::mem::take(&mut self.states);
+        // a bigger radius lets cells far from the living ones change, the backend's candidates don't reach them
+        if self.radius > 1 {
+            grid::all_cells(self.grid.size(), &mut candidates);
+        } else {
+            self.grid.step_candidates(self.boundary, &mut candidates);
+        }
+        // borrowed instead of cloned, so rules without B0 don't allocate every step
+        let ruleset = b0_ruleset.as_ref().unwrap_or(&self.ruleset);
+        #[cfg(feature = "parallel")]
+        self.next_states_parallel(ruleset, &candidates, &mut states);
+        #[cfg(not(feature = "parallel"))]
+        self.next_states_serial(ruleset, &candidates, &mut states);
+        self.build_next_grid(&mut *next_grid, &candidates, &states);
+        self.candidates = candidates;
+        self.states = states;
+
+        // remember the cells that just died, the GUI can fade them out
+        self.dying.clear();
+        let dying = &mut self.dying;
+        self.grid.all_live(&mut |row_index, column_index, _cell| {
+            if next_grid.get(row_index, column_index) == Ok(0) {
+                dying.push((row_index, column_index));
             }
+            true
+        });
+
+        // a still life (or an empty board) won't change anymore, end the simulation
+        // NOTE: ages always change, only the living cells are compared
+        self.stable = next_grid.same_live_cells(&*self.grid);
+        if self.stable {
+            self.simulation_ended = true;
         }
-        // swap grids
-        self.grid = next_grid;
-    }
 
-    /// Based on update_rate, return a duration.
-    /// Whole numbers of update_rate is seconds.
-    /// Decimal of update_rate is milliseconds.
-    /// # Equation
-    /// Duration::new(update_rate, 0)
-    /// # Returns
-    /// Duration, self.update_rate as a duration
-    pub fn get_update_rate_duration(&self) -> Duration {
-        Duration::new(self.update_rate as u64, 0)
-    }
+        // swap grids, the current generation is remembered so it can be stepped back to
+        let previous_grid = std::mem::replace(&mut self.grid, next_grid);
+        if self.history_capacity > 0 {
+            self.history_hashes.push_back(previous_grid.hash());
+            self.history.push_back(previous_grid);
+        } else {
+            self.back_buffer = Some(previous_grid);
+        }
 
-    pub fn get_number_of_steps(&self) -> usize {
-        self.number_of_steps
+        // nothing is left to step, end the simulation
+        self.extinct = self.grid.is_empty();
+        if self.extinct {
+            self.simulation_ended = true;
+        }
+        self.peak_population = self.peak_population.max(self.population());
+
+        // look for oscillators in non-stop runs, the history limits how long a period can be found
+        self.period = if self.simulation_non_stop {
+            self.detect_period(self.history.len())
+        } else {
+            None
+        };
+
+        if self.infinite {
+            self.expand_if_needed();
+        }
+
+        if self.cycle_window > 0 {
+            self.detect_cycle();
+        }
+
+        // only copy the grid for the callback when someone is listening
+        let step_callback = self
+            .step_callback
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(callback) = step_callback.as_mut() {
+            callback(self.generation, &to_dense(&*self.grid));
+        }
     }
 
-    /// Return the self.simulation_ended boolean.
-    pub fn is_simulation_ended(&self) -> bool {
-        return self.simulation_ended;
+    /// Take up to the given number of steps, stopping early once the simulation ends
+    /// (out of steps, stabilized or a cycle was detected).
+    /// # Params
+    /// steps: usize, max number of steps to take
+    /// # Returns
+    /// usize, number of steps actually taken
+    pub fn step_many(&mut self, steps: usize) -> usize {
+        let mut taken = 0;
+        while taken < steps && !self.simulation_ended {
+            self.take_step();
+            taken += 1;
+        }
+        taken
     }
 
-    /// Return the self.simulation_non_stop boolean.
-    pub fn is_simulation_non_stop(&self) -> bool {
-        return self.simulation_non_stop;
+    /// Compare the hash of the current grid against the recent generations, ending the simulation on a match.
+    /// A hash match is confirmed against the history buffer when it still holds that generation,
+    /// older matches are trusted since a 64 bit hash collision is unlikely.
+    fn detect_cycle(&mut self) {
+        let hash = self.grid.hash();
+        let period = self
+            .cycle_hashes
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, old_hash)| (index + 1, *old_hash))
+            .find(|&(period, old_hash)| {
+                old_hash == hash
+                    && (period > self.history.len()
+                        || self.history[self.history.len() - period].same_live_cells(&*self.grid))
+            })
+            .map(|(period, _)| period);
+        if let Some(period) = period {
+            self.period = Some(period);
+            self.simulation_ended = true;
+        }
+
+        self.cycle_hashes.push_back(hash);
+        while self.cycle_hashes.len() > self.cycle_window {
+            self.cycle_hashes.pop_front();
+        }
     }
 
-    /// Get the name of the engine, there are two cases:
-    /// 1. File was not parsed, return "No file found, using default pattern"
-    /// 2. The filename used in the engine
+    /// Ruleset for the step from the current generation.
+    /// Rules with B0 use ```Ruleset::b0_rulesets``` so the grid is stored inverted on odd generations,
+    /// otherwise the background would flash between all dead and all alive every generation.
+    /// The inverted generations are drawn as stored, like other Life programs do.
     /// # Returns
-    /// &String, name of the current engine session
-    pub fn get_name(&self) -> &String {
-        return &self.name;
+    /// Option<ruleset::Ruleset>, the ruleset for even or odd generations with B0, None when the engine's ruleset applies
+    fn step_ruleset(&self) -> Option<ruleset::Ruleset> {
+        self.ruleset
+            .b0_rulesets(self.neighborhood.max_neighbors_in_radius(self.radius))
+            .map(|(even, odd)| {
+                if self.generation.is_multiple_of(2) {
+                    even
+                } else {
+                    odd
+                }
+            })
     }
 
-    /// Get the title of the engine.  This will provide a title description of the engine
-    /// in its current state.
+    /// Apply the ruleset (or the custom rule from ```with_rule```) to a single cell of the current grid.
+    /// # Params
+    /// - row_index: usize, row of the cell
+    /// - column_index: usize, column of the cell
+    /// - ruleset: &ruleset::Ruleset, ruleset for this step, see ```step_ruleset```
     /// # Returns
-    /// String, string representing the engine's current state
-    pub fn get_title_string(&self) -> String {
-        // format the end text string
-        let end_text = if self.is_simulation_non_stop() {
-            " -- non-stop"
-        } else if self.is_simulation_ended() {
-            " -- simulation ended"
+    /// usize, state of the cell in the next generation: its age (or species) if it is alive, 0 if it is dead
+    fn next_cell_state(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        ruleset: &ruleset::Ruleset,
+    ) -> usize {
+        let number_of_neighbors = if self.radius > 1 {
+            self.grid.count_neighbors_in_radius(
+                row_index,
+                column_index,
+                self.radius,
+                self.neighborhood,
+                self.boundary,
+            )
         } else {
-            ""
-        };
-
-        format!(
-            "Conway-rust v{}: {}{}",
-            crate_version!(),
-            self.get_name(),
-            end_text
-        )
+            self.grid
+                .count_neighbors(row_index, column_index, self.neighborhood, self.boundary)
+        }
+        .expect("Failed to get the number of neighbors");
+        let cell_status = self
+            .grid
+            .get(row_index, column_index)
+            .expect("Failed to get cell");
+        if let Some(rule) = &self.rule {
+            return rule.next_state(cell_status, number_of_neighbors);
+        }
+        if !self.species {
+            return ruleset::Rule::next_state(ruleset, cell_status, number_of_neighbors);
+        }
+        // species keep their id instead of aging, newborns take after their neighbors
+        if cell_status > 0 {
+            if ruleset.is_survival(number_of_neighbors) {
+                cell_status
+            } else {
+                0
+            }
+        } else if ruleset.is_birth(number_of_neighbors) {
+            self.birth_species(row_index, column_index)
+        } else {
+            0
+        }
     }
 
-    /// Calculate the spacing between rows and columns.
-    /// The maths: (self.width / self.grid.column_size, self.height / self.grid.row_size)
+    /// Pick the species of a cell being born from its living neighbors on the grid.
+    /// # Params
+    /// - row_index: usize, row of the cell
+    /// - column_index: usize, column of the cell
     /// # Returns
-    /// (f32, f32), (X spacing, Y spacing)
-    pub fn get_grid_spacing(&self) -> (f32, f32) {
-        (
-            self.width / self.grid.size().1 as f32,
-            self.height / self.grid.size().0 as f32,
+    /// usize, majority species of the neighbors, 1 if every living neighbor is off the grid (```BoundaryCondition::Alive```)
+    fn birth_species(&self, row_index: usize, column_index: usize) -> usize {
+        let wrap_edges = self.boundary == BoundaryCondition::Toroidal;
+        let neighbors = grid::neighbor_coordinates(
+            row_index,
+            column_index,
+            self.grid.size(),
+            self.neighborhood,
+            wrap_edges,
+        );
+        majority_species(
+            neighbors.map(|(row, column)| self.grid.get(row, column).expect("Failed to get cell")),
         )
+        .unwrap_or(1)
     }
 
-    /// Get the row and column count for the grid
-    /// # Returns
-    /// (usize, usize), (row_size, column_size)
-    pub fn get_grid_dimensions(&self) -> (usize, usize) {
-        self.grid.size()
+    /// Compute the next state of each step candidate one cell at a time.
+    /// Only the cells the backend reports as step candidates are computed, the rest stay dead.
+    /// # Params
+    /// - ruleset: &ruleset::Ruleset, ruleset for this step
+    /// - candidates: &[(usize, usize)], (row, column) of each cell to compute
+    /// - states: &mut Vec<usize>, cleared and filled with the next state of each candidate, in the same order
+    #[cfg_attr(feature = "parallel", allow(dead_code))]
+    fn next_states_serial(
+        &self,
+        ruleset: &ruleset::Ruleset,
+        candidates: &[(usize, usize)],
+        states: &mut Vec<usize>,
+    ) {
+        states.clear();
+        states.extend(candidates.iter().map(|(row_index, column_index)| {
+            self.next_cell_state(*row_index, *column_index, ruleset)
+        }));
     }
 
-    /// Get the state of a cell.
-    /// Returns either the cell contents or 0 on an error.
+    /// Compute the next state of each step candidate with the candidates spread over the rayon thread pool.
+    /// The current grid is only read, so the cells don't depend on each other.
     /// # Params
-    /// row_index: usize, row index in the engine grid.
-    /// column_index: usize, column index in the engine grid.
+    /// - ruleset: &ruleset::Ruleset, ruleset for this step
+    /// - candidates: &[(usize, usize)], (row, column) of each cell to compute
+    /// - states: &mut Vec<usize>, filled with the next states, identical to ```next_states_serial```
+    #[cfg(feature = "parallel")]
+    fn next_states_parallel(
+        &self,
+        ruleset: &ruleset::Ruleset,
+        candidates: &[(usize, usize)],
+        states: &mut Vec<usize>,
+    ) {
+        use rayon::prelude::*;
+
+        candidates
+            .par_iter()
+            .map(|(row_index, column_index)| {
+                self.next_cell_state(*row_index, *column_index, ruleset)
+            })
+            .collect_into_vec(states);
+    }
+
+    /// Grid to write the next generation into, a grid the step no longer needs is reused before a new one is made.
+    /// With a full history the oldest generation is recycled, without a history it's the previous generation.
     /// # Returns
-    /// usize, Cell state or 0
-    pub fn get_cell(&self, row_index: usize, column_index: usize) -> usize {
-        match self.grid.get(row_index, column_index) {
-            Ok(data) => *data,
-            Err(_err) => 0,
+    /// Box<dyn GridBackend>, grid of the current backend, its cells are overwritten by ```build_next_grid```
+    fn take_back_buffer(&mut self) -> Box<dyn GridBackend> {
+        if self.history_capacity > 0 && self.history.len() >= self.history_capacity {
+            if let Some(oldest_grid) = self.history.pop_front() {
+                self.history_hashes.pop_front();
+                return oldest_grid;
+            }
+        }
+        match self.back_buffer.take() {
+            Some(back_buffer) => back_buffer,
+            None => {
+                let (row_size, column_size) = self.grid.size();
+                self.grid.new_empty(row_size, column_size)
+            }
         }
     }
 
-    /// Replace the existing grid with a new grid.
-    /// This is for changing the grid with each new step.  The rules of the game make the grid change
-    /// all at once.  In order to accomplish changing earlier cells, a new grid is created representing
-    /// the new state.  The old state needs to be overwritten and removed.
+    /// Write the computed cell states into a grid, after resetting it to the current size with every cell dead.
+    /// Starting empty (instead of from a copy of the current grid) means cells that weren't candidates are dead.
     /// # Params
-    /// - new_grid: grid::Grid<usize>, the modified replacement grid
+    /// - next_grid: &mut dyn GridBackend, grid receiving the next generation
+    /// - candidates: &[(usize, usize)], (row, column) of each computed cell
+    /// - states: &[usize], next state of each candidate, in the same order
+    fn build_next_grid(
+        &self,
+        next_grid: &mut dyn GridBackend,
+        candidates: &[(usize, usize)],
+        states: &[usize],
+    ) {
+        let (row_size, column_size) = self.grid.size();
+        next_grid.reset(row_size, column_size);
+        for ((row_index, column_index), state) in candidates.iter().zip(states.iter()) {
+            if *state > 0 {
+                next_grid
+                    .set(*row_index, *column_index, *state)
+                    .expect("Failed to update cell");
+            }
+        }
+    }
+
+    /// Grow the grid when living cells reach the border, so patterns can keep moving without falling off the edge.
+    /// A dimension with living cells on either of its edges is doubled, the old contents stay in the center.
+    /// Dimensions are capped at MAX_INFINITE_GRID_SIZE and the grid at ```set_max_cells``` cells, once a cap is reached
+    /// the edges act bounded again.
+    pub fn expand_if_needed(&mut self) {
+        let (row_size, column_size) = self.grid.size();
+        let mut touches_row_edge = false;
+        let mut touches_column_edge = false;
+        for (row_index, column_index, _) in self.grid.iter_live() {
+            touches_row_edge |= row_index == 0 || row_index == row_size - 1;
+            touches_column_edge |= column_index == 0 || column_index == column_size - 1;
+        }
+
+        let new_row_size = if touches_row_edge {
+            (row_size * 2).min(MAX_INFINITE_GRID_SIZE).max(row_size)
+        } else {
+            row_size
+        };
+        let new_column_size = if touches_column_edge {
+            (column_size * 2)
+                .min(MAX_INFINITE_GRID_SIZE)
+                .max(column_size)
+        } else {
+            column_size
+        };
+        // a grid that would go over the most cells allowed keeps its size, its edges act bounded from then on
+        if check_grid_size(new_row_size, new_column_size, self.max_cells).is_err() {
+            return;
+        }
+        if (new_row_size, new_column_size) != (row_size, column_size) {
+            self.grid = self.grid.resize_centered(new_row_size, new_column_size);
+            // the cells moved to the center of the bigger grid
+            self.dying.clear();
+        }
+    }
+
+    /// Step back one generation, restoring the grid from the history buffer.
+    /// The step taken is given back to the step counter, so a simulation that had ended will run again.
     /// # Returns
-    /// - Result<(), &'static str>, result, empty OK if sucessful, err if the grids don't match in size.
-    fn replace_grid(&mut self, new_grid: grid::Grid<usize>) -> Result<(), &'static str> {
-        if self.grid.size().0 != new_grid.size().0 || self.grid.size().1 != new_grid.size().1 {
-            return Err("Grids do not match in size");
+    /// Result<(), &'static str>, empty OK if successful, err if there is no history to step back to.
+    pub fn step_back(&mut self) -> Result<(), &'static str> {
+        let previous_grid = match self.history.pop_back() {
+            Some(grid) => grid,
+            None => return Err("No previous generation to step back to"),
+        };
+        self.history_hashes.pop_back();
+        self.back_buffer = Some(std::mem::replace(&mut self.grid, previous_grid));
+        self.dying.clear();
+        self.generation = self.generation.saturating_sub(1);
+        self.stable = false;
+        self.extinct = false;
+        self.period = None;
+        self.previous_population = match self.history.back() {
+            Some(grid) => count_population(&**grid),
+            None => self.population(),
+        };
+
+        if !self.simulation_non_stop {
+            self.number_of_steps += 1;
         }
-        // update the grid
-        self.grid = new_grid;
+        // a step is now available again
+        self.simulation_ended = false;
 
         Ok(())
     }
-}
+
+    /// Rotate and mirror the board, meant to be applied right after loading a pattern.
+    /// The rotation is applied first, rotating by 90 or 270 degrees swaps the row and column sizes.
+    /// The history is cleared since older generations don't match the new orientation.
+    /// # Params
+    /// - rotation: Rotation, clockwise rotation of the board
+    /// - flip: Option<Flip>, mirror applied after the rotation, None to leave the board unmirrored
+    pub fn transform(&mut self, rotation: Rotation, flip: Option<Flip>) {
+        if rotation == Rotation::None && flip.is_none() {
+            return;
+        }
+
+        let mut grid = to_dense(&*self.grid);
+        for _ in 0..rotation.quarter_turns() {
+            grid = grid.rotate_90();
+        }
+        grid = match flip {
+            Some(Flip::Horizontal) => grid.flip_horizontal(),
+            Some(Flip::Vertical) => grid.flip_vertical(),
+            None => grid,
+        };
+
+        // copy back into the current backend
+        let (row_size, column_size) = grid.size();
+        let mut transformed = self.grid.new_empty(row_size, column_size);
+        for (row_index, column_index, cell) in grid.iter_live() {
+            transformed
+                .set(row_index, column_index, *cell)
+                .expect("Failed to copy cell");
+        }
+        self.grid = transformed;
+        self.history.clear();
+        self.history_hashes.clear();
+        self.dying.clear();
+        self.set_cycle_detection(self.cycle_window);
+    }
+
+    /// Stop the simulation once the board repeats a generation from the last max_period generations.
+    /// Only a hash of each generation is kept, so long periods can be found without storing the full grids.
+    /// The detected period is reported by the title and the summary.
+    /// # Params
+    /// max_period: usize, longest period to look for, 0 disables the cycle detection
+    pub fn set_cycle_detection(&mut self, max_period: usize) {
+        self.cycle_window = max_period;
+        self.cycle_hashes.clear();
+        if max_period > 0 {
+            self.cycle_hashes.push_back(self.grid.hash());
+        }
+    }
+
+    /// Set how many previous generations are kept for ```step_back```.
+    /// The oldest generations are dropped if the history is larger than the new capacity.
+    /// A capacity of 0 disables the history.
+    /// # Params
+    /// history_capacity: usize, max number of generations to keep
+    pub fn set_history_capacity(&mut self, history_capacity: usize) {
+        while self.history.len() > history_capacity {
+            self.history.pop_front();
+            self.history_hashes.pop_front();
+        }
+        self.history_capacity = history_capacity;
+    }
+
+    /// Based on update_rate, return a duration.
+    /// Whole numbers of update_rate is seconds, the decimal part is the fraction of a second (0.25 is 250ms).
+    /// Rates below MIN_UPDATE_RATE (including zero and negative rates) are raised to MIN_UPDATE_RATE,
+    /// rates above MAX_UPDATE_RATE (including infinity) are lowered to MAX_UPDATE_RATE and NaN is treated as zero.
+    /// # Equation
+    /// Duration::from_secs_f64(update_rate)
+    /// # Returns
+    /// Duration, self.update_rate as a duration
+    pub fn get_update_rate_duration(&self) -> Duration {
+        if self.update_rate.is_nan() {
+            return Duration::from_secs_f64(MIN_UPDATE_RATE);
+        }
+        Duration::from_secs_f64(self.update_rate.clamp(MIN_UPDATE_RATE, MAX_UPDATE_RATE))
+    }
+
+    pub fn get_number_of_steps(&self) -> usize {
+        self.number_of_steps
+    }
+
+    /// Return the self.simulation_ended boolean.
+    pub fn is_simulation_ended(&self) -> bool {
+        self.simulation_ended
+    }
+
+    /// Return the self.simulation_non_stop boolean.
+    pub fn is_simulation_non_stop(&self) -> bool {
+        self.simulation_non_stop
+    }
+
+    /// Check if the simulation has stabilized, the last ```take_step``` produced a grid identical to the one before it.
+    /// # Returns
+    /// bool, true if the pattern is frozen
+    pub fn is_stable(&self) -> bool {
+        self.stable
+    }
+
+    /// Check if every cell died in the last ```take_step```.
+    /// # Returns
+    /// bool, true if the grid is empty
+    pub fn is_extinct(&self) -> bool {
+        self.extinct
+    }
+
+    /// Get the number of steps taken since the board was loaded, stepping back counts down.
+    /// # Returns
+    /// usize, current generation, 0 is the initial board
+    pub fn get_generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Iterate over the living cells in row-major order.
+    /// # Returns
+    /// impl Iterator<Item = (usize, usize, usize)>, (row, column, age) for each living cell
+    pub fn iter_live(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.grid.iter_live()
+    }
+
+    /// Count the number of living cells in the grid.
+    /// # Returns
+    /// usize, number of cells with a value greater than 0
+    pub fn population(&self) -> usize {
+        count_population(&*self.grid)
+    }
+
+    /// Cells that were alive before the last ```take_step``` and died in it, in row-major order.
+    /// Empty before the first step, and after a step back, a transform or the grid growing since the cells moved.
+    /// Cells toggled back to life since the step are still listed, check the cell before drawing it as dying.
+    /// # Returns
+    /// &[(usize, usize)], (row, column) of each cell that just died
+    pub fn dying_cells(&self) -> &[(usize, usize)] {
+        &self.dying
+    }
+
+    /// Copy the living part of the grid into a grid just big enough to hold it, for compact exports.
+    /// The cells keep their values (ages or species), see ```Grid::live_bounding_box```.
+    /// # Returns
+    /// grid::Grid<usize>, grid cropped to the living cells, 0x0 if every cell is dead
+    pub fn trim_to_live(&self) -> grid::Grid<usize> {
+        let dense = to_dense(&*self.grid);
+        let (min_row, min_column, max_row, max_column) = match dense.live_bounding_box() {
+            Some(bounds) => bounds,
+            None => return grid::Grid::new(0, 0, 0),
+        };
+        let mut trimmed = grid::Grid::new(max_row - min_row + 1, max_column - min_column + 1, 0);
+        for (row_index, column_index, cell) in dense.iter_live() {
+            trimmed
+                .set(row_index - min_row, column_index - min_column, *cell)
+                .expect("Living cells are inside the bounding box");
+        }
+        trimmed
+    }
+
+    /// Fraction of the grid that is alive.
+    /// # Returns
+    /// f32, population divided by the total number of cells, 0.0 for an empty grid
+    pub fn density(&self) -> f32 {
+        let (row_size, column_size) = self.grid.size();
+        let number_of_cells = row_size * column_size;
+        if number_of_cells == 0 {
+            return 0.0;
+        }
+        self.population() as f32 / number_of_cells as f32
+    }
+
+    /// Count the living cells in the outermost ring of the grid, the first and last rows and columns.
+    /// Compared with ```population``` this shows how much of the activity is at the edges,
+    /// e.g. at the seams of a toroidal board or where an infinite grid is about to grow.
+    /// # Returns
+    /// usize, number of living cells on the border of the grid
+    pub fn edge_population(&self) -> usize {
+        let (row_size, column_size) = self.grid.size();
+        self.grid
+            .iter_live()
+            .filter(|&(row, column, _)| {
+                row == 0 || column == 0 || row + 1 == row_size || column + 1 == column_size
+            })
+            .count()
+    }
+
+    /// Cells that changed value in the last step, so a view can redraw only those cells.
+    /// The previous generation comes from the history buffer.
+    /// # Returns
+    /// Option<Vec<(usize, usize, usize)>>, (row, column, new value) of each changed cell in row-major order,
+    /// None if the previous generation isn't known (no step yet, no history kept or the grid was transformed)
+    /// or the grid grew during the step, in both cases the whole board needs to be redrawn
+    pub fn changed_cells(&self) -> Option<Vec<(usize, usize, usize)>> {
+        self.history
+            .back()
+            .and_then(|previous| self.grid.diff(&**previous).ok())
+    }
+
+    /// Largest population seen so far, including the initial board.
+    /// # Returns
+    /// usize, highest number of living cells after any step
+    pub fn get_peak_population(&self) -> usize {
+        self.peak_population
+    }
+
+    /// Describe how the simulation went, meant to be printed once it ends.
+    /// # Returns
+    /// String, generations run, final and peak population, and if the board stabilized, oscillated, or was still changing
+    pub fn summary(&self) -> String {
+        let outcome = match self.classify_outcome() {
+            Outcome::Stable if self.extinct => "died out".to_string(),
+            Outcome::Stable => "stabilized".to_string(),
+            Outcome::Oscillating(period) => format!("oscillating with period {}", period),
+            Outcome::StepLimit => "still changing".to_string(),
+        };
+        format!(
+            "Ran {} generations, final population {}, peak population {}, {}",
+            self.generation,
+            self.population(),
+            self.peak_population,
+            outcome
+        )
+    }
+
+    /// Classify the simulation with the stability and period detectors.
+    /// Periods are found by the cycle detection or, failing that, the history buffer.
+    /// # Returns
+    /// Outcome, stable, oscillating with its period, or still changing (the step limit was reached)
+    pub fn classify_outcome(&self) -> Outcome {
+        if self.stable || self.extinct {
+            return Outcome::Stable;
+        }
+        match self
+            .period
+            .or_else(|| self.detect_period(self.history.len()))
+        {
+            Some(period) => Outcome::Oscillating(period),
+            None => Outcome::StepLimit,
+        }
+    }
+
+    /// Change in population caused by the last ```take_step```.
+    /// # Returns
+    /// isize, current population minus the population before the last step
+    pub fn population_delta(&self) -> isize {
+        self.population() as isize - self.previous_population as isize
+    }
+
+    /// Find the period of an oscillating pattern by comparing the current grid against the history buffer.
+    /// Only periods up to the history capacity can be found.
+    /// # Params
+    /// max_period: usize, longest period to look for
+    /// # Returns
+    /// Option<usize>, smallest period p where the grid p generations ago matches the current grid, None if not found
+    pub fn detect_period(&self, max_period: usize) -> Option<usize> {
+        let max_period = max_period.min(self.history.len());
+        if max_period == 0 {
+            return None;
+        }
+        // one pass over the grid for its hash, the grids are only compared when the hashes match
+        let hash = self.grid.hash();
+        (1..=max_period).find(|&period| {
+            let index = self.history.len() - period;
+            self.history_hashes[index] == hash && self.history[index].same_live_cells(&*self.grid)
+        })
+    }
+
+    /// Set if the grid edges wrap around (toroidal topology) when counting neighbors.
+    /// By default the edges are bounded and cells off the grid are treated as dead.
+    /// # Params
+    /// wrap_edges: bool, true to wrap the edges, false to keep them bounded
+    pub fn set_wrap_edges(&mut self, wrap_edges: bool) {
+        self.boundary = if wrap_edges {
+            BoundaryCondition::Toroidal
+        } else {
+            BoundaryCondition::Dead
+        };
+    }
+
+    /// Set how the cells on the edges of the grid see the neighbors that fall off the grid.
+    /// By default the neighbors off the grid are dead.
+    /// # Params
+    /// boundary: BoundaryCondition, dead, alive, or toroidal (wrapped) edges
+    pub fn set_boundary(&mut self, boundary: BoundaryCondition) {
+        self.boundary = boundary;
+    }
+
+    /// Get how the cells on the edges of the grid see the neighbors that fall off the grid.
+    /// # Returns
+    /// BoundaryCondition, dead, alive, or toroidal (wrapped) edges
+    pub fn get_boundary(&self) -> BoundaryCondition {
+        self.boundary
+    }
+
+    /// Set if the grid grows when living cells reach its edges, see ```expand_if_needed```.
+    /// # Params
+    /// infinite: bool, true to grow the grid, false to keep its size fixed
+    pub fn set_infinite(&mut self, infinite: bool) {
+        self.infinite = infinite;
+    }
+
+    /// Set the most cells an infinite grid can grow to, default is MAX_CELLS.
+    /// Like MAX_INFINITE_GRID_SIZE, a grid that can't double anymore keeps its size.
+    /// # Params
+    /// max_cells: usize, largest rows * columns the grid can grow to
+    pub fn set_max_cells(&mut self, max_cells: usize) {
+        self.max_cells = max_cells;
+    }
+
+    /// Turn the multi-species mode on or off.  Default is off, living cells hold their age.
+    /// With species on, living cells hold a species id (1, 2, 3...) that they keep while they survive,
+    /// the ruleset still treats any nonzero cell as alive, and a newborn cell takes the most common species
+    /// among its living neighbors (see ```majority_species```).
+    /// # Params
+    /// species: bool, true to keep species ids instead of ages
+    pub fn set_species(&mut self, species: bool) {
+        self.species = species;
+    }
+
+    /// Replace the ruleset with a custom rule, e.g. ```ConwayEngine::from_grid(...).with_rule(Box::new(rule))```.
+    /// Every cell is passed to ```Rule::next_state``` with its living neighbor count, the neighborhood and boundary still apply.
+    /// The rule's values are used as they are, so the species mode and the B0 inversion don't apply,
+    /// and ```set_ruleset``` only changes the ruleset shown in the title and saved to files.
+    /// The sparse backend only passes the cells near living cells, a dead cell without living neighbors must stay dead.
+    /// # Params
+    /// rule: Box<dyn ruleset::Rule>, transition function applied in ```take_step```
+    /// # Returns
+    /// ConwayEngine, the engine using the rule
+    pub fn with_rule(mut self, rule: Box<dyn ruleset::Rule>) -> ConwayEngine {
+        self.rule = Some(rule);
+        self
+    }
+
+    /// Watch each generation as it is made, instead of polling the engine.
+    /// The callback runs at the end of every ```take_step``` that actually steps, not when the simulation has ended,
+    /// and gets a dense copy of the grid whatever the backend is.
+    /// # Params
+    /// callback: StepCallback, called with the new generation number and grid
+    pub fn set_step_callback(&mut self, callback: StepCallback) {
+        *self
+            .step_callback
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = Some(callback);
+    }
+
+    /// Set which surrounding cells count as neighbors in ```take_step```.  Default is the Moore neighborhood (8 cells).
+    /// # Params
+    /// neighborhood: Neighborhood, neighborhood used for every neighbor count
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+    }
+
+    /// Set how far the neighborhood reaches, for Larger than Life rules like "B34..45/S33..57".
+    /// Default is 1, the 8 (or 4) cells around each cell.  A radius of 2 counts the 24 cells of the 5x5 square
+    /// around a cell with the Moore neighborhood, or the 12 cells of the diamond with von Neumann.
+    /// Every cell is computed on each step with a radius above 1, the sparse backend is no faster than the dense one.
+    /// # Params
+    /// radius: usize, distance from the cell that still counts as a neighbor, 0 is treated as 1
+    pub fn set_radius(&mut self, radius: usize) {
+        self.radius = radius.max(1);
+    }
+
+    /// Get how far the neighborhood reaches, see ```set_radius```.
+    /// # Returns
+    /// usize, distance from the cell that still counts as a neighbor
+    pub fn get_radius(&self) -> usize {
+        self.radius
+    }
+
+    /// Set the ruleset applied in ```take_step```.  Default is Conway's Game of Life (B3/S23).
+    /// # Params
+    /// ruleset: ruleset::Ruleset, the birth and survival rules to apply
+    pub fn set_ruleset(&mut self, ruleset: ruleset::Ruleset) {
+        self.ruleset = ruleset;
+    }
+
+    /// Set how the grid is stored, the current grid and the history are converted to the new backend.
+    /// The dense backend is the default, the sparse backend only stores and steps the living cells.
+    /// # Params
+    /// backend: Backend, storage to switch to
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.grid = convert_grid(&*self.grid, backend);
+        for grid in self.history.iter_mut() {
+            *grid = convert_grid(&**grid, backend);
+        }
+        // the back buffer is the old backend, a new one is made on the next step
+        self.back_buffer = None;
+    }
+
+    /// Get the name of the engine, there are two cases:
+    /// 1. File was not parsed, return "No file found, using default pattern"
+    /// 2. The filename used in the engine
+    /// # Returns
+    /// &String, name of the current engine session
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    /// Get the title of the engine.  This will provide a title description of the engine
+    /// in its current state, including the current generation.
+    /// # Returns
+    /// String, string representing the engine's current state
+    pub fn get_title_string(&self) -> String {
+        // format the end text string
+        let end_text = if self.is_extinct() {
+            " -- extinct".to_string()
+        } else if self.is_stable() {
+            " -- stabilized".to_string()
+        } else if let Some(period) = self.period.filter(|&period| period > 1) {
+            format!(" -- oscillating (period {})", period)
+        } else if self.is_simulation_non_stop() {
+            " -- non-stop".to_string()
+        } else if self.is_simulation_ended() {
+            " -- simulation ended".to_string()
+        } else {
+            "".to_string()
+        };
+
+        format!(
+            "Conway-rust v{}: {} -- gen {}{}",
+            env!("CARGO_PKG_VERSION"),
+            self.get_name(),
+            self.get_generation(),
+            end_text
+        )
+    }
+
+    /// Change the window size the grid is drawn in, e.g. after the window is resized.
+    /// The grid keeps its rows and columns, only the spacing changes.
+    /// # Params
+    /// - width: f32, width of the window in pixels
+    /// - height: f32, height of the window in pixels
+    pub fn set_dimensions(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Calculate the spacing between rows and columns.
+    /// The maths: (self.width / self.grid.column_size, self.height / self.grid.row_size)
+    /// An empty dimension counts as 1 so the spacing never divides by zero.
+    /// # Returns
+    /// (f32, f32), (X spacing, Y spacing)
+    pub fn get_grid_spacing(&self) -> (f32, f32) {
+        let (row_size, column_size) = self.grid.size();
+        (
+            self.width / column_size.max(1) as f32,
+            self.height / row_size.max(1) as f32,
+        )
+    }
+
+    /// Calculate a uniform spacing that keeps the cells square, and the margins that center the grid in the window.
+    /// The spacing is the smaller of the two from ```get_grid_spacing```, so the whole grid still fits.
+    /// # Returns
+    /// (f32, (f32, f32)), (spacing, (X offset, Y offset)), the offsets are the margins on the left and top of the grid
+    pub fn get_square_spacing_and_offset(&self) -> (f32, (f32, f32)) {
+        let (x_spacing, y_spacing) = self.get_grid_spacing();
+        let spacing = x_spacing.min(y_spacing);
+        let (row_size, column_size) = self.grid.size();
+        (
+            spacing,
+            (
+                (self.width - spacing * column_size as f32) / 2.0,
+                (self.height - spacing * row_size as f32) / 2.0,
+            ),
+        )
+    }
+
+    /// Get the row and column count for the grid
+    /// # Returns
+    /// (usize, usize), (row_size, column_size)
+    pub fn get_grid_dimensions(&self) -> (usize, usize) {
+        self.grid.size()
+    }
+
+    /// Get the state of a cell.
+    /// Returns either the cell contents or 0 on an error.
+    /// # Params
+    /// row_index: usize, row index in the engine grid.
+    /// column_index: usize, column index in the engine grid.
+    /// # Returns
+    /// usize, Cell state or 0
+    pub fn get_cell(&self, row_index: usize, column_index: usize) -> usize {
+        self.grid.get(row_index, column_index).unwrap_or(0)
+    }
+
+    /// Render the grid as text, one line per row with ```#``` for living cells and ```.``` for dead cells.
+    /// # Returns
+    /// String, the rendered grid, every row ends with a newline
+    pub fn to_ascii(&self) -> String {
+        let (row_size, column_size) = self.grid.size();
+        let mut ascii = String::with_capacity(row_size * (column_size + 1));
+        for row_index in 0..row_size {
+            for column_index in 0..column_size {
+                if self.get_cell(row_index, column_index) > 0 {
+                    ascii.push('#');
+                } else {
+                    ascii.push('.');
+                }
+            }
+            ascii.push('\n');
+        }
+        ascii
+    }
+
+    /// Write the current grid to a file in the native comma delimited format.
+    /// The header holds the row size, column size, update rate, the number of steps the run started with (0 for non-stop),
+    /// the ruleset and the radius.  The steps left aren't saved, a finished run has none left and 0 would load as non-stop.
+    /// # Params
+    /// path: &str, location of the file to write, an existing file is overwritten
+    /// # Returns
+    /// io::Result<()>, empty OK if successful, the io error otherwise
+    pub fn save_to_file(&self, path: &str) -> io::Result<()> {
+        let (row_size, column_size) = self.grid.size();
+        let mut file = File::create(path)?;
+        writeln!(
+            file,
+            "{}, {}, {}, {}, {}, R{}",
+            row_size, column_size, self.update_rate, self.step_budget, self.ruleset, self.radius
+        )?;
+        for row_index in 0..row_size {
+            let row: Vec<String> = (0..column_size)
+                .map(|column_index| self.get_cell(row_index, column_index).to_string())
+                .collect();
+            writeln!(file, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Write the current grid to a file in the given format.
+    /// The binary format only keeps the cells, the update rate, steps and ruleset come from the defaults when it's loaded.
+    /// # Params
+    /// - path: &str, location of the file to write, an existing file is overwritten
+    /// - format: SaveFormat, native text or binary
+    /// # Returns
+    /// io::Result<()>, empty OK if successful, the io error otherwise
+    pub fn save_as(&self, path: &str, format: SaveFormat) -> io::Result<()> {
+        match format {
+            SaveFormat::Native => self.save_to_file(path),
+            SaveFormat::Binary => std::fs::write(path, to_dense(&*self.grid).to_bytes()),
+        }
+    }
+
+    /// Flip the state of a cell, living cells die and dead cells come to life.
+    /// Editing a stabilized board lets the simulation continue if it has steps left.
+    /// # Params
+    /// row_index: usize, row index in the engine grid.
+    /// column_index: usize, column index in the engine grid.
+    /// # Returns
+    /// Result<(), &'static str>, empty OK if successful, err if the cell is out of bounds
+    pub fn toggle_cell(
+        &mut self,
+        row_index: usize,
+        column_index: usize,
+    ) -> Result<(), &'static str> {
+        let cell_status = self.grid.get(row_index, column_index)?;
+        let new_status = if cell_status > 0 { 0 } else { 1 };
+        self.grid.set(row_index, column_index, new_status)?;
+
+        if self.stable || self.extinct {
+            self.stable = false;
+            self.extinct = false;
+            self.simulation_ended = !self.simulation_non_stop && self.number_of_steps == 0;
+        }
+        self.period = None;
+
+        Ok(())
+    }
+}
 
 /// Reads an input file and returns a collection of strings representing lines in the file.
 /// Each line is denoted by a newline character.
+/// Comment lines (starting with '#' or '!') and blank lines are skipped anywhere in the file, so the first line returned is the header.
+/// Files ending in ```.gz``` or starting with the gzip magic bytes are decompressed first.
 /// This function is static, no need to reference the struct.
 /// # Params
 /// filename: &String, the input file to read in
@@ -241,80 +1619,581 @@ fn read_engine_file(filename: &String) -> Result<Vec<String>, &'static str> {
         Ok(file) => file,
         Err(_err) => return Err("Failed to open file"),
     };
+    let mut reader = BufReader::new(file);
+    let gzipped = has_extension(filename, "gz")
+        || reader
+            .fill_buf()
+            .is_ok_and(|start| start.starts_with(&GZIP_MAGIC));
+    if !gzipped {
+        return read_engine_lines(reader);
+    }
+
+    // the whole file is decompressed up front, so corrupt data is reported as such instead of as a bad line
+    let mut contents: Vec<u8> = Vec::new();
+    if MultiGzDecoder::new(reader)
+        .read_to_end(&mut contents)
+        .is_err()
+    {
+        return Err("Failed to decompress the gzip file, it is corrupt or not gzip");
+    }
+    read_engine_lines(contents.as_slice())
+}
+
+/// Read the pattern lines from standard input.
+/// The lines are kept after the first call, later calls return the same lines.
+/// # Returns
+/// Result<Vec<String>, &'static str>, lines without blank lines or comments, an error if reading fails
+fn read_stdin_lines() -> Result<Vec<String>, &'static str> {
+    STDIN_LINES
+        .get_or_init(|| read_engine_lines(io::stdin().lock()))
+        .clone()
+}
 
-    let line_iter = BufReader::new(file).lines();
+/// Read the lines of a pattern from any reader, a file or standard input.
+/// # Params
+/// reader: R, source of the pattern
+/// # Returns
+/// Result<Vec<String>, &'static str>, lines without blank lines or comments, an error if a line can't be read
+fn read_engine_lines<R: BufRead>(reader: R) -> Result<Vec<String>, &'static str> {
     let mut file_data: Vec<String> = Vec::new();
-    for line in line_iter {
+    for line in reader.lines() {
         let line = match line {
             Ok(data) => data,
             Err(_err) => return Err("Failed to get line in file"),
         };
-        // skip comments
-        if !line.starts_with("#") {
-            file_data.push(line);
+        // skip comments and blank lines
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
+            continue;
         }
+        file_data.push(line);
     }
     Ok(file_data)
 }
 
-/// Convert the static [str] array to a Vec<String>.
-/// The result is a default oscillator.
+/// Check if a line of a native file places a bundled pattern instead of listing cells.
+/// # Params
+/// line: &str, line from the body of the file
 /// # Returns
-/// Vec<String>, vector representing a default osciallator.
-fn generate_default_file_array() -> Vec<String> {
-    let mut default_vec: Vec<String> = Vec::new();
-    for line in DEFAULT_ARRAY.iter() {
-        default_vec.push(line.to_string());
-    }
-    default_vec
+/// bool, true if the line starts with PLACE, ignoring case
+fn is_placement(line: &str) -> bool {
+    line.split_whitespace()
+        .next()
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case("PLACE"))
 }
 
-/// Given a header array of strings, parse out:
-/// - Row size
-/// - Column size
-/// - update rate (optional)
-/// - number of steps (optional)
+/// Stamp a bundled pattern onto the grid from a ```PLACE <name> AT <row>,<column>``` line.
+/// The row and column are the top left corner of the pattern, the whole pattern rectangle replaces the cells under it.
 /// # Params
-/// header_line: String, the raw header line from the file.
-/// default_update_rate: usize, default update rate if not provided in the file.
-/// default_steps: uszie, default number of steps if not provided in the file.
+/// - grid: &mut grid::Grid<usize>, grid to stamp the pattern onto
+/// - placement: &str, the placement line, e.g. "PLACE glider AT 3,4"
 /// # Returns
-/// (usize, usize, usize, usize), tuple containing: row size, column size, update rate, number of steps
+/// Result<(), &'static str>, empty OK if successful, err if the line is malformed, the pattern is unknown or it doesn't fit
+fn place_pattern(grid: &mut grid::Grid<usize>, placement: &str) -> Result<(), &'static str> {
+    let tokens: Vec<&str> = placement.split_whitespace().collect();
+    if tokens.len() < 4 || !tokens[2].eq_ignore_ascii_case("AT") {
+        return Err("Placement must be in the form PLACE <name> AT <row>,<column>");
+    }
+    // the coordinates can have spaces around the comma
+    let coordinates = tokens[3..].concat();
+    let coordinates: Vec<&str> = coordinates.split(',').collect();
+    if coordinates.len() != 2 {
+        return Err("Placement must be in the form PLACE <name> AT <row>,<column>");
+    }
+    let (row, column) = match (
+        coordinates[0].parse::<usize>(),
+        coordinates[1].parse::<usize>(),
+    ) {
+        (Ok(row), Ok(column)) => (row, column),
+        _ => return Err("Placement row and column must be positive integers"),
+    };
+
+    let pattern = match patterns::find(tokens[1]) {
+        Some(pattern) => pattern,
+        None => return Err("Unknown pattern"),
+    };
+    let (pattern_grid, _ruleset) = parse_rle(pattern, MAX_CELLS)?;
+    grid.stamp(row, column, &pattern_grid)
+}
+
+/// Count the living cells in a grid.
+/// # Params
+/// grid: &dyn GridBackend, grid to count
+/// # Returns
+/// usize, number of cells with a value greater than 0
+fn count_population(grid: &dyn GridBackend) -> usize {
+    grid.population()
+}
+
+/// Find the most common species, dead cells (0) are skipped.
+/// # Params
+/// species: impl Iterator<Item = usize>, species of each neighbor
+/// # Returns
+/// Option<usize>, the most common species with ties going to the lowest id, None if there are no living cells
+fn majority_species(species: impl Iterator<Item = usize>) -> Option<usize> {
+    let mut tally: BTreeMap<usize, usize> = BTreeMap::new();
+    for id in species.filter(|&id| id > 0) {
+        *tally.entry(id).or_insert(0) += 1;
+    }
+    // ascending ids, a later species only wins with a strictly higher count
+    let mut majority: Option<(usize, usize)> = None;
+    for (id, count) in tally {
+        if majority.is_none_or(|(_, best_count)| count > best_count) {
+            majority = Some((id, count));
+        }
+    }
+    majority.map(|(id, _)| id)
+}
+
+/// Size of a grid that fills the window with PATTERN_CELL_SIZE pixel cells.
+/// # Params
+/// - height: f32, height of the window in pixels
+/// - width: f32, width of the window in pixels
+/// - min_size: (usize, usize), smallest (row, column) size, used if the window is too small
+/// # Returns
+/// (usize, usize), (row, column) size of the grid
+fn window_grid_size(height: f32, width: f32, min_size: (usize, usize)) -> (usize, usize) {
+    (
+        ((height / PATTERN_CELL_SIZE) as usize).max(min_size.0),
+        ((width / PATTERN_CELL_SIZE) as usize).max(min_size.1),
+    )
+}
+
+/// Copy a grid into the given backend.
+/// # Params
+/// - grid: &dyn GridBackend, grid to copy
+/// - backend: Backend, storage of the copy
+/// # Returns
+/// Box<dyn GridBackend>, grid with the same size and cells
+fn convert_grid(grid: &dyn GridBackend, backend: Backend) -> Box<dyn GridBackend> {
+    match backend {
+        Backend::Dense => Box::new(to_dense(grid)),
+        Backend::Sparse => Box::new(sparse_grid::SparseGrid::from_backend(grid)),
+    }
+}
+
+/// Copy any grid into a dense grid.
+/// # Params
+/// grid: &dyn GridBackend, grid to copy
+/// # Returns
+/// grid::Grid<usize>, dense grid with the same size and cells
+fn to_dense(grid: &dyn GridBackend) -> grid::Grid<usize> {
+    let (row_size, column_size) = grid.size();
+    let mut dense = grid::Grid::new(row_size, column_size, 0);
+    for (row_index, column_index, cell) in grid.iter_live() {
+        dense
+            .set(row_index, column_index, cell)
+            .expect("Failed to copy cell");
+    }
+    dense
+}
+
+/// Check the extension of a file name, ignoring case.
+/// # Params
+/// - filename: &str, file name to check
+/// - extension: &str, extension without the leading '.'
+/// # Returns
+/// bool, true if the file name has the extension
+fn has_extension(filename: &str, extension: &str) -> bool {
+    match Path::new(filename).extension() {
+        Some(file_extension) => file_extension.eq_ignore_ascii_case(extension),
+        None => false,
+    }
+}
+
+/// Parse a Run Length Encoded (RLE) pattern.
+/// The first non-comment line is the header ```x = columns, y = rows, rule = B3/S23```, the rule is optional.
+/// The pattern follows, ```b``` is a dead cell, ```o``` (or any other letter) is a living cell, ```$``` ends a row
+/// and ```!``` ends the pattern.  Each of these can be preceded by a run count.
+/// Lines starting with '#' are comments.
+/// # Params
+/// - contents: &str, contents of the RLE file
+/// - max_cells: usize, largest rows * columns the header can declare, see ```MAX_CELLS```
+/// # Returns
+/// Result<(grid::Grid<usize>, ruleset::Ruleset), &'static str>, the pattern and its ruleset, or an error
+pub fn parse_rle(
+    contents: &str,
+    max_cells: usize,
+) -> Result<(grid::Grid<usize>, ruleset::Ruleset), &'static str> {
+    let mut lines = contents
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    // parse the header
+    let header = match lines.next() {
+        Some(header) => header,
+        None => return Err("RLE file is missing the header line"),
+    };
+    let mut row_size = None;
+    let mut column_size = None;
+    let mut ruleset = ruleset::Ruleset::default();
+    for field in header.split(',') {
+        let key_value: Vec<&str> = field.splitn(2, '=').map(|data| data.trim()).collect();
+        if key_value.len() != 2 {
+            return Err("RLE header fields must be in the form key = value");
+        }
+        match key_value[0] {
+            "x" => match key_value[1].parse::<usize>() {
+                Ok(data) => column_size = Some(data),
+                Err(_err) => return Err("RLE header x is not a positive integer"),
+            },
+            "y" => match key_value[1].parse::<usize>() {
+                Ok(data) => row_size = Some(data),
+                Err(_err) => return Err("RLE header y is not a positive integer"),
+            },
+            "rule" => ruleset = parse_rle_rule(key_value[1])?,
+            // unknown fields are skipped
+            _ => (),
+        }
+    }
+    let (row_size, column_size) = match (row_size, column_size) {
+        (Some(0), _) | (_, Some(0)) => return Err("RLE header x and y must be greater than zero"),
+        (Some(row_size), Some(column_size)) => {
+            check_grid_size(row_size, column_size, max_cells)?;
+            (row_size, column_size)
+        }
+        _ => return Err("RLE header must have both x and y"),
+    };
+    let mut grid = grid::Grid::new(row_size, column_size, 0);
+
+    // parse the pattern
+    let mut row_index = 0;
+    let mut column_index = 0;
+    let mut run_count: Option<usize> = None;
+    'pattern: for line in lines {
+        for tag in line.chars() {
+            // a missing run count means a single cell
+            let count = run_count.unwrap_or(1);
+            match tag {
+                '0'..='9' => {
+                    let digit = tag.to_digit(10).unwrap() as usize;
+                    run_count = match run_count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|run| run.checked_add(digit))
+                    {
+                        Some(run) => Some(run),
+                        None => return Err("RLE run count is too large"),
+                    };
+                    continue;
+                }
+                'b' => column_index = advance_rle_index(column_index, count, column_size)?,
+                '$' => {
+                    row_index = advance_rle_index(row_index, count, row_size)?;
+                    column_index = 0;
+                }
+                '!' => break 'pattern,
+                tag if tag.is_whitespace() => continue,
+                tag if tag.is_ascii_alphabetic() => {
+                    let end = advance_rle_index(column_index, count, column_size)?;
+                    if row_index >= row_size {
+                        return Err("RLE pattern is larger than the header size");
+                    }
+                    for column in column_index..end {
+                        grid.set(row_index, column, 1)?;
+                    }
+                    column_index = end;
+                }
+                _ => return Err("RLE pattern has an unexpected character"),
+            }
+            run_count = None;
+        }
+    }
+
+    Ok((grid, ruleset))
+}
+
+/// Parse a plaintext (```.cells```) pattern.
+/// Each line is a row, ```.``` is a dead cell and ```O``` is a living cell.  Lines starting with '!' are comments.
+/// The grid is as wide as the widest row, shorter rows are padded with dead cells.
+/// # Params
+/// - contents: &str, contents of the plaintext file
+/// - max_cells: usize, largest rows * columns allowed, see ```MAX_CELLS```
+/// # Returns
+/// Result<grid::Grid<usize>, &'static str>, the pattern or an error
+pub fn parse_plaintext(
+    contents: &str,
+    max_cells: usize,
+) -> Result<grid::Grid<usize>, &'static str> {
+    let rows: Vec<&str> = contents
+        .lines()
+        .map(|line| line.trim_end())
+        .filter(|line| !line.starts_with('!'))
+        .collect();
+    if rows.is_empty() {
+        return Err("Plaintext pattern has no rows");
+    }
+
+    let column_size = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    if column_size == 0 {
+        return Err("Plaintext pattern has no columns");
+    }
+    check_grid_size(rows.len(), column_size, max_cells)?;
+    let mut grid = grid::Grid::new(rows.len(), column_size, 0);
+    for (row_index, row) in rows.iter().enumerate() {
+        for (column_index, cell) in row.chars().enumerate() {
+            match cell {
+                '.' => (),
+                'O' | '*' => grid.set(row_index, column_index, 1)?,
+                _ => return Err("Plaintext pattern has an unexpected character"),
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
+/// Parse an ASCII art pattern with the given characters, e.g. ```*``` for living cells and a space for dead ones.
+/// Each line is a row, the alive character is a living cell and any other character is a dead cell.
+/// The grid is as wide as the widest row, shorter rows are padded with dead cells and blank lines at the end are dropped.
+/// # Params
+/// - contents: &str, contents of the ASCII file
+/// - alive: char, character of the living cells
+/// - dead: char, character of the dead cells, it has to differ from alive
+/// - max_cells: usize, largest rows * columns allowed, see ```MAX_CELLS```
+/// # Returns
+/// Result<grid::Grid<usize>, &'static str>, the pattern or an error
+pub fn parse_ascii(
+    contents: &str,
+    alive: char,
+    dead: char,
+    max_cells: usize,
+) -> Result<grid::Grid<usize>, &'static str> {
+    if alive == dead {
+        return Err("ASCII alive and dead characters must be different");
+    }
+    // only the line endings are stripped, spaces can be cells
+    let mut rows: Vec<&str> = contents
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    while rows.last().is_some_and(|row| row.is_empty()) {
+        rows.pop();
+    }
+    if rows.is_empty() {
+        return Err("ASCII pattern has no rows");
+    }
+
+    let column_size = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    check_grid_size(rows.len(), column_size, max_cells)?;
+    let mut grid = grid::Grid::new(rows.len(), column_size, 0);
+    for (row_index, row) in rows.iter().enumerate() {
+        for (column_index, cell) in row.chars().enumerate() {
+            if cell == alive {
+                grid.set(row_index, column_index, 1)?;
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
+/// Parse the rule from an RLE header.  Both B/S notation (B3/S23) and the older S/B notation (23/3) are accepted.
+/// # Params
+/// rule: &str, rule string from the header
+/// # Returns
+/// Result<ruleset::Ruleset, &'static str>, the parsed ruleset or an error
+/// Move an RLE row or column index forward by a run count.
+/// # Params
+/// - index: usize, current row or column
+/// - count: usize, run count of the tag
+/// - size: usize, number of rows or columns from the header
+/// # Returns
+/// Result<usize, &'static str>, the new index or an error if the run goes past the header size
+fn advance_rle_index(index: usize, count: usize, size: usize) -> Result<usize, &'static str> {
+    match index.checked_add(count) {
+        Some(end) if end <= size => Ok(end),
+        _ => Err("RLE pattern is larger than the header size"),
+    }
+}
+
+fn parse_rle_rule(rule: &str) -> Result<ruleset::Ruleset, &'static str> {
+    if rule.contains(['B', 'b']) {
+        return rule.parse::<ruleset::Ruleset>();
+    }
+    let parts: Vec<&str> = rule.split('/').collect();
+    if parts.len() != 2 {
+        return Err("RLE rule must be in the form B3/S23 or 23/3");
+    }
+    format!("B{}/S{}", parts[1], parts[0]).parse::<ruleset::Ruleset>()
+}
+
+/// Check an update rate is a finite number of seconds no longer than ```MAX_UPDATE_RATE```.
+/// Zero and negative rates are allowed, they step as fast as possible (see ```ConwayEngine::get_update_rate_duration```).
+/// # Params
+/// update_rate: f64, seconds between steps
+/// # Returns
+/// Result<f64, &'static str>, the update rate or an error if it's infinite, NaN or too long
+pub fn check_update_rate(update_rate: f64) -> Result<f64, &'static str> {
+    if !update_rate.is_finite() || update_rate > MAX_UPDATE_RATE {
+        return Err("Update rate must be a finite number of seconds, at most one day");
+    }
+    Ok(update_rate)
+}
+
+/// Check a grid size against the most cells allowed before it's allocated.
+/// # Params
+/// - row_size: usize, number of rows in the grid
+/// - column_size: usize, number of columns in the grid
+/// - max_cells: usize, largest rows * columns allowed, see ```MAX_CELLS```
+/// # Returns
+/// Result<(), &'static str>, an error if rows * columns is over the limit or doesn't fit in a usize
+pub(crate) fn check_grid_size(
+    row_size: usize,
+    column_size: usize,
+    max_cells: usize,
+) -> Result<(), &'static str> {
+    match row_size.checked_mul(column_size) {
+        Some(number_of_cells) if number_of_cells <= max_cells => Ok(()),
+        _ => Err("Grid has more cells than the maximum allowed"),
+    }
+}
+
+/// Fill a grid with living cells at random.
+/// # Params
+/// - row_size: usize, number of rows in the grid
+/// - column_size: usize, number of columns in the grid
+/// - density: f64, probability (0.0 - 1.0) of each cell starting alive
+/// - seed: Option<u64>, seed for the random number generator, None seeds from the operating system
+/// - max_cells: usize, largest rows * columns allowed, see ```MAX_CELLS```
+/// # Returns
+/// Result<grid::Grid<usize>, &'static str>, the random grid or an error if the density or size is invalid
+pub fn generate_random_grid(
+    row_size: usize,
+    column_size: usize,
+    density: f64,
+    seed: Option<u64>,
+    max_cells: usize,
+) -> Result<grid::Grid<usize>, &'static str> {
+    if !(0.0..=1.0).contains(&density) {
+        return Err("Random density must be between 0.0 and 1.0");
+    }
+    if row_size == 0 || column_size == 0 {
+        return Err("Random grid must have at least one row and one column");
+    }
+    check_grid_size(row_size, column_size, max_cells)?;
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut grid = grid::Grid::new(row_size, column_size, 0);
+    for row_index in 0..row_size {
+        for column_index in 0..column_size {
+            if rng.gen_bool(density) {
+                grid.set(row_index, column_index, 1)?;
+            }
+        }
+    }
+    Ok(grid)
+}
+
+/// Convert the static [str] array to a Vec<String>.
+/// The result is a default oscillator.
+/// # Returns
+/// Vec<String>, vector representing a default osciallator.
+fn generate_default_file_array() -> Vec<String> {
+    let mut default_vec: Vec<String> = Vec::new();
+    for line in DEFAULT_ARRAY.iter() {
+        default_vec.push(line.to_string());
+    }
+    default_vec
+}
+
+/// Given a header array of strings, parse out:
+/// - Row size
+/// - Column size
+/// - update rate (optional)
+/// - number of steps (optional)
+/// - ruleset (optional)
+/// - radius (optional)
+/// # Params
+/// header_line: String, the raw header line from the file.
+/// default_update_rate: f64, default update rate if not provided in the file.
+/// default_steps: uszie, default number of steps if not provided in the file.
+/// # Returns
+/// (usize, usize, f64, usize, ruleset::Ruleset, usize), tuple containing: row size, column size, update rate, number of steps,
+/// ruleset, radius
+/// # NOTE
+/// The canonical header is ```rows, columns, rate, steps, rule, R<radius>```, only the row and column size are required.
+/// A missing rule is B3/S23 and a missing radius is 1, so files written before they were saved still load the same.
+/// Range rules hold commas of their own, so the rule is every field between the steps and the radius.
 fn parse_header(
     header_line: String,
-    default_update_rate: usize,
+    default_update_rate: f64,
     default_steps: usize,
-) -> Result<(usize, usize, usize, usize), &'static str> {
-    let header_data: Vec<&str> = header_line.split(',').collect();
+) -> Result<(usize, usize, f64, usize, ruleset::Ruleset, usize), &'static str> {
+    // files saved on Windows can keep a carriage return at the end of the line
+    let header_data: Vec<&str> = header_line
+        .trim_end_matches(['\r', '\n'])
+        .split(',')
+        .collect();
     if header_data.len() < 2 {
         return Err("Parse error, header is too small.  Row and column size are needed.");
     }
-    let row_size = header_data[0]
-        .trim()
-        .parse::<usize>()
-        .expect("Failed to parse row size");
-    let column_size = header_data[1]
-        .trim()
-        .parse::<usize>()
-        .expect("Failed to parse column size");
+    let row_size = match header_data[0].trim().parse::<usize>() {
+        Ok(row_size) => row_size,
+        Err(_err) => return Err("Header row size must be a positive integer"),
+    };
+    let column_size = match header_data[1].trim().parse::<usize>() {
+        Ok(column_size) => column_size,
+        Err(_err) => return Err("Header column size must be a positive integer"),
+    };
+    if row_size == 0 || column_size == 0 {
+        return Err("Header row and column size must be greater than zero");
+    }
+    // the size is checked against the limit by generate_grid, before the grid is allocated
 
     // handle the update rate and number of steps, if no value present use the defaults provided
     let update_rate = match header_data.get(2) {
-        Some(data) => data
-            .trim()
-            .parse::<usize>()
-            .expect("Header update rate not formatted correctly"),
+        Some(data) => match data.trim().parse::<f64>() {
+            Ok(update_rate) => check_update_rate(update_rate)?,
+            Err(_err) => return Err("Header update rate must be a number"),
+        },
         None => default_update_rate,
     };
     let number_of_steps = match header_data.get(3) {
-        Some(data) => data
-            .trim()
-            .parse::<usize>()
-            .expect("Header # steps not formatted correctly"),
+        Some(data) => match data.trim().parse::<usize>() {
+            Ok(number_of_steps) => number_of_steps,
+            Err(_err) => return Err("Header number of steps must be a positive integer"),
+        },
         None => default_steps,
     };
+    // the radius is tagged with an R, like Larger than Life rules write it, so it can't be mistaken for a rule count
+    let mut rule_data = header_data.get(4..).unwrap_or_default();
+    let mut radius = 1;
+    if let Some((last, rest)) = rule_data.split_last() {
+        if let Some(data) = last.trim().strip_prefix(['R', 'r']) {
+            radius = match data.parse::<usize>() {
+                Ok(radius) if radius > 0 => radius,
+                _ => return Err("Header radius must be R followed by a positive integer"),
+            };
+            rule_data = rest;
+        }
+    }
+    let ruleset = if rule_data.is_empty() {
+        ruleset::Ruleset::default()
+    } else {
+        rule_data.join(",").parse::<ruleset::Ruleset>()?
+    };
 
-    Ok((row_size, column_size, update_rate, number_of_steps))
+    Ok((
+        row_size,
+        column_size,
+        update_rate,
+        number_of_steps,
+        ruleset,
+        radius,
+    ))
 }
 
 /// Generate the grid for the engine
@@ -322,286 +2201,2516 @@ fn parse_header(
 /// - row_size: usize, size of rows
 /// - column_size: usize, size of columns
 /// - grid_lines: Vec<String>, vector of strings to be parsed for a row of grid data
+/// - options: LoadOptions, most cells allowed and whether a row with fewer columns than column_size is an error
+///   instead of padded with 0
 /// # Returns
 /// - Result<Grid<T>, str>, either a successfully created grid is returned or an error
 fn generate_grid(
     row_size: usize,
     column_size: usize,
     grid_lines: Vec<String>,
+    options: LoadOptions,
 ) -> Result<grid::Grid<usize>, &'static str> {
+    check_grid_size(row_size, column_size, options.max_cells)?;
     // setup the grid
     let mut grid = grid::Grid::new(row_size, column_size, 0);
     // parse the data, add to grid cells
-    let mut row_counter = 0;
-    for line in grid_lines {
-        // check row bounds
-        if row_counter > grid.size().0 {
+    for (row_counter, line) in grid_lines.iter().enumerate() {
+        // check row bounds, rows are 0 indexed so row_size itself is already outside the grid
+        if row_counter >= grid.size().0 {
             return Err("Row exceeds the grid");
         }
-        let line_data: Vec<&str> = line.split(',').collect();
+        // surrounding whitespace and a Windows carriage return aren't part of the cells
+        let line_data: Vec<&str> = line.trim().split(',').collect();
         // check column bounds
         if line_data.len() > grid.size().1 {
             return Err("Column is too large to fit in the grid");
         }
-        for column_counter in 0..line_data.len() {
-            let data = match line_data[column_counter].trim().parse::<usize>() {
-                Ok(data) => data,
-                Err(_err) => return Err("failed to parse cell data"),
-            };
-            match grid.set(row_counter, column_counter, data) {
-                Ok(()) => (),
-                Err(_err) => return Err("Failed to set grid cell"),
-            };
+        if options.strict_columns && line_data.len() < grid.size().1 {
+            return Err("Row has fewer columns than the header declares");
+        }
+        for (column_counter, cell) in line_data.iter().enumerate() {
+            let data = match cell.trim().parse::<usize>() {
+                Ok(data) => data,
+                Err(_err) => return Err("failed to parse cell data"),
+            };
+            match grid.set(row_counter, column_counter, data) {
+                Ok(()) => (),
+                Err(_err) => return Err("Failed to set grid cell"),
+            };
+        }
+    }
+
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// helper function, checks every cell of the engine is alive or dead like the given grid
+    fn assert_same_cells(engine: &ConwayEngine, grid: &dyn GridBackend) {
+        assert_eq!(engine.get_grid_dimensions(), grid.size());
+        for row in 0..grid.size().0 {
+            for column in 0..grid.size().1 {
+                assert_eq!(
+                    engine.get_cell(row, column) > 0,
+                    grid.get(row, column).unwrap() > 0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let engine = ConwayEngine::new(&"test-files/test.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        for row in 0..engine.get_grid_dimensions().0 {
+            let mut row_s: String = "".to_string();
+            for column in 0..engine.get_grid_dimensions().1 {
+                row_s += &engine.get_cell(row, column).to_string();
+            }
+            println!("{}", row_s);
+        }
+        assert_eq!(engine.get_cell(0, 0), 1);
+        assert_eq!(engine.get_cell(1, 1), 1);
+        assert_eq!(engine.get_cell(2, 2), 1);
+        assert_eq!(engine.get_cell(3, 3), 1);
+        assert_eq!(engine.get_cell(4, 4), 1);
+
+        // a window of 5x5 pattern cells keeps the default grid at 5x5
+        let engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        assert_eq!(engine.get_grid_dimensions(), (5, 5));
+        assert_eq!(engine.get_cell(1, 2), 1);
+        assert_eq!(engine.get_cell(2, 2), 1);
+        assert_eq!(engine.get_cell(3, 2), 1);
+    }
+
+    #[test]
+    fn test_default_pattern_centered() {
+        // 768 / 16 = 48 rows, 2014 / 16 = 125 columns, blinker offset by (21, 60)
+        let engine = ConwayEngine::new(&"".to_string(), 768.0, 2014.0, 0.0, 0);
+        assert_eq!(engine.get_name(), "No file found, using default pattern");
+        assert_eq!(engine.get_grid_dimensions(), (48, 125));
+        assert_eq!(engine.population(), 3);
+        assert_eq!(engine.get_cell(22, 62), 1);
+        assert_eq!(engine.get_cell(23, 62), 1);
+        assert_eq!(engine.get_cell(24, 62), 1);
+
+        // tall window, 64 rows by 48 columns
+        let engine = ConwayEngine::new(&"".to_string(), 1024.0, 768.0, 0.0, 0);
+        assert_eq!(engine.get_grid_dimensions(), (64, 48));
+        assert_eq!(engine.get_cell(30, 23), 1);
+        assert_eq!(engine.get_cell(31, 23), 1);
+        assert_eq!(engine.get_cell(32, 23), 1);
+
+        // tiny window, never smaller than the default pattern
+        let engine = ConwayEngine::new(&"".to_string(), 10.0, 10.0, 0.0, 0);
+        assert_eq!(engine.get_grid_dimensions(), (5, 5));
+    }
+
+    #[test]
+    fn test_read_engine_file() {
+        let result = read_engine_file(&"test-files/test.txt".to_string());
+        assert!(result.is_ok());
+        let test_file_one: Vec<String> = vec![
+            "5, 5, 1, 20".to_string(),
+            "1,0,0,0,0".to_string(),
+            "0,1,0,0,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,0,1,0".to_string(),
+            "0,0,0,0,1".to_string(),
+        ];
+        assert_eq!(result.unwrap(), test_file_one);
+
+        // blank lines and comments between the rows are skipped
+        let result = read_engine_file(&"test-files/spaced_test.txt".to_string());
+        assert_eq!(result.unwrap(), test_file_one);
+        let engine = ConwayEngine::new(
+            &"test-files/spaced_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        let expected_engine =
+            ConwayEngine::new(&"test-files/test.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert_same_cells(&engine, &*expected_engine.grid);
+        assert_eq!(engine.get_number_of_steps(), 20);
+
+        let result = read_engine_file(&"test-files/bad_test.txt".to_string());
+        assert!(result.is_ok());
+        let test_file_bad: Vec<String> = vec![
+            "5, 5".to_string(),
+            "1,0,0,0,0".to_string(),
+            "0,1,0,a,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,0,1,c".to_string(),
+        ];
+        assert_eq!(result.unwrap(), test_file_bad);
+
+        let result = read_engine_file(&"test-files/test2.txt".to_string());
+        assert!(result.is_ok());
+        let test_file_two: Vec<String> = vec![
+            "5, 5, 1, 20".to_string(),
+            "0,0,0,0,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,0,0,0".to_string(),
+        ];
+        assert_eq!(result.unwrap(), test_file_two);
+
+        let result = read_engine_file(&"random_file_name.hello_world".to_string());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_gzip_file() {
+        // a gzipped copy loads to the same lines and grid as the plain file
+        let plain = read_engine_file(&"test-files/test.txt".to_string()).unwrap();
+        assert_eq!(
+            read_engine_file(&"test-files/test.txt.gz".to_string()).unwrap(),
+            plain
+        );
+        let engine = ConwayEngine::try_load(
+            &"test-files/test.txt.gz".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+            LoadOptions::default(),
+        )
+        .unwrap();
+        let expected_engine =
+            ConwayEngine::new(&"test-files/test.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert!(engine.grid.equals(&*expected_engine.grid));
+        assert_eq!(engine.get_number_of_steps(), 20);
+
+        // the magic bytes are enough without the extension
+        let path = std::env::temp_dir().join("conway_rust_test_gzip_no_extension.txt");
+        let path = path.to_str().unwrap().to_string();
+        std::fs::copy("test-files/test.txt.gz", &path).unwrap();
+        assert_eq!(read_engine_file(&path).unwrap(), plain);
+
+        // a truncated stream is reported as corrupt
+        let data = std::fs::read("test-files/test.txt.gz").unwrap();
+        std::fs::write(&path, &data[..data.len() / 2]).unwrap();
+        assert_eq!(
+            read_engine_file(&path),
+            Err("Failed to decompress the gzip file, it is corrupt or not gzip")
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_engine_lines() {
+        // same lines as test.txt, with a comment and blank line
+        let contents =
+            "# diagonal\n5, 5, 1, 20\n1,0,0,0,0\n\n0,1,0,0,0\n0,0,1,0,0\n0,0,0,1,0\n0,0,0,0,1\n";
+        let file_data = read_engine_lines(io::Cursor::new(contents)).unwrap();
+        assert_eq!(
+            file_data,
+            read_engine_file(&"test-files/test.txt".to_string()).unwrap()
+        );
+        let engine = ConwayEngine::from_lines(
+            file_data,
+            "-",
+            768.0,
+            1024.0,
+            0.0,
+            0,
+            LoadOptions::default(),
+        )
+        .unwrap();
+        let expected_engine =
+            ConwayEngine::new(&"test-files/test.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert_same_cells(&engine, &*expected_engine.grid);
+        assert_eq!(engine.get_number_of_steps(), 20);
+
+        // RLE is found from the header
+        let contents = std::fs::read_to_string("test-files/glider.rle").unwrap();
+        let file_data = read_engine_lines(io::Cursor::new(contents)).unwrap();
+        let engine = ConwayEngine::from_lines(
+            file_data,
+            "-",
+            768.0,
+            1024.0,
+            0.0,
+            0,
+            LoadOptions::default(),
+        )
+        .unwrap();
+        let expected_engine =
+            ConwayEngine::new(&"test-files/glider.rle".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert_same_cells(&engine, &*expected_engine.grid);
+
+        // nothing to read falls back to the default pattern
+        let file_data = read_engine_lines(io::Cursor::new("\n# only a comment\n")).unwrap();
+        assert!(file_data.is_empty());
+        let engine =
+            ConwayEngine::from_lines(file_data, "-", 80.0, 80.0, 0.0, 0, LoadOptions::default())
+                .unwrap();
+        let expected_engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        assert_same_cells(&engine, &*expected_engine.grid);
+
+        // invalid lines are still errors
+        let file_data = read_engine_lines(io::Cursor::new("5, 5\n1,0,a,0,0")).unwrap();
+        assert!(ConwayEngine::from_lines(
+            file_data,
+            "-",
+            768.0,
+            1024.0,
+            0.0,
+            0,
+            LoadOptions::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let data = parse_header("5, 5, 5, 5".to_string(), 0.0, 0);
+        assert!(data.is_ok());
+        let data = data.unwrap();
+        assert_eq!(data, (5, 5, 5.0, 5, ruleset::Ruleset::default(), 1));
+
+        // the fifth field is the ruleset
+        let data = parse_header("1, 2, 3, 4, B36/S23".to_string(), 0.0, 0);
+        assert!(data.is_ok());
+        let data = data.unwrap();
+        assert_eq!(
+            data,
+            (
+                1,
+                2,
+                3.0,
+                4,
+                "B36/S23".parse::<ruleset::Ruleset>().unwrap(),
+                1
+            )
+        );
+
+        // range rules keep their commas, the radius is the last field
+        let data = parse_header("1, 2, 3, 4, B34..45/S8,33..57, R2".to_string(), 0.0, 0);
+        assert_eq!(
+            data,
+            Ok((
+                1,
+                2,
+                3.0,
+                4,
+                "B34..45/S8,33..57".parse::<ruleset::Ruleset>().unwrap(),
+                2
+            ))
+        );
+        let data = parse_header("1, 2, 3, 4, r3".to_string(), 0.0, 0);
+        assert_eq!(data, Ok((1, 2, 3.0, 4, ruleset::Ruleset::default(), 3)));
+        assert_eq!(
+            parse_header("1, 2, 3, 4, B3/S23, R0".to_string(), 0.0, 0),
+            Err("Header radius must be R followed by a positive integer")
+        );
+        assert!(parse_header("1, 2, 3, 4, B3/S23, Rx".to_string(), 0.0, 0).is_err());
+
+        let data = parse_header("5, 5, 20, 1".to_string(), 0.0, 0);
+        assert!(data.is_ok());
+        let data = data.unwrap();
+        assert_eq!(data, (5, 5, 20.0, 1, ruleset::Ruleset::default(), 1));
+
+        let data = parse_header("5, 5, 5".to_string(), 0.0, 0);
+        assert!(data.is_ok());
+        let data = data.unwrap();
+        assert_eq!(data, (5, 5, 5.0, 0, ruleset::Ruleset::default(), 1));
+
+        let data = parse_header("5, 5".to_string(), 0.0, 0);
+        assert!(data.is_ok());
+        let data = data.unwrap();
+        assert_eq!(data, (5, 5, 0.0, 0, ruleset::Ruleset::default(), 1));
+
+        let data = parse_header("5".to_string(), 0.0, 0);
+        assert!(data.is_err());
+
+        // typos are reported instead of crashing
+        assert_eq!(
+            parse_header("x, 5, 1, 20".to_string(), 0.0, 0),
+            Err("Header row size must be a positive integer")
+        );
+        assert_eq!(
+            parse_header("5, x, 1, 20".to_string(), 0.0, 0),
+            Err("Header column size must be a positive integer")
+        );
+        assert_eq!(
+            parse_header("5, 5, fast, 20".to_string(), 0.0, 0),
+            Err("Header update rate must be a number")
+        );
+        assert_eq!(
+            parse_header("5, 5, 1, -20".to_string(), 0.0, 0),
+            Err("Header number of steps must be a positive integer")
+        );
+        assert!(parse_header("5, 5, 1, 20, 6".to_string(), 0.0, 0).is_err());
+    }
+
+    #[test]
+    fn test_scene() {
+        let engine = ConwayEngine::new(
+            &"test-files/scene_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert_eq!(engine.get_grid_dimensions(), (12, 12));
+        assert_eq!(engine.get_number_of_steps(), 10);
+        let live: Vec<(usize, usize)> = engine
+            .grid
+            .iter_live()
+            .map(|(row, column, _)| (row, column))
+            .collect();
+        assert_eq!(
+            live,
+            vec![
+                (1, 2),
+                (2, 3),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (6, 8),
+                (7, 9),
+                (8, 7),
+                (8, 8),
+                (8, 9)
+            ]
+        );
+
+        // placements go on top of the cell rows
+        let mut grid =
+            generate_grid(5, 5, vec!["1,1".to_string()], LoadOptions::default()).unwrap();
+        assert!(place_pattern(&mut grid, "PLACE blinker AT 2,0").is_ok());
+        assert_eq!(grid.iter_live().count(), 5);
+
+        // errors
+        let mut grid = grid::Grid::new(10, 10, 0);
+        assert_eq!(
+            place_pattern(&mut grid, "PLACE spaceship AT 1,1"),
+            Err("Unknown pattern")
+        );
+        assert!(place_pattern(&mut grid, "PLACE glider AT 8,1").is_err());
+        assert!(place_pattern(&mut grid, "PLACE glider AT 1,8").is_err());
+        assert!(place_pattern(&mut grid, "PLACE glider 1,1").is_err());
+        assert!(place_pattern(&mut grid, "PLACE glider AT 1").is_err());
+        assert!(place_pattern(&mut grid, "PLACE glider AT x,1").is_err());
+        assert_eq!(grid.iter_live().count(), 0);
+    }
+
+    #[test]
+    fn test_scene_errors() {
+        let path = std::env::temp_dir().join("conway_rust_test_scene_errors.txt");
+        let path = path.to_str().unwrap().to_string();
+
+        // the error names the offending line
+        std::fs::write(&path, "5, 5\nPLACE glider AT 0,0\nPLACE glider AT 3,3\n").unwrap();
+        assert_eq!(
+            ConwayEngine::try_load(&path, 768.0, 1024.0, 0.0, 0, LoadOptions::default()).err(),
+            Some("Stamped pattern overflows the grid rows: 'PLACE glider AT 3,3'".to_string())
+        );
+        std::fs::write(&path, "5, 5\nPLACE ship AT 0,0\n").unwrap();
+        assert_eq!(
+            ConwayEngine::try_load(&path, 768.0, 1024.0, 0.0, 0, LoadOptions::default()).err(),
+            Some("Unknown pattern: 'PLACE ship AT 0,0'".to_string())
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_crlf_file() {
+        // CRLF line endings and stray spaces parse the same as the LF version
+        let lf_data = read_engine_file(&"test-files/test.txt".to_string()).unwrap();
+        let crlf_data = read_engine_file(&"test-files/crlf_test.txt".to_string()).unwrap();
+        assert_eq!(
+            parse_header(crlf_data[0].clone(), 0.0, 0),
+            parse_header(lf_data[0].clone(), 0.0, 0)
+        );
+        assert_eq!(
+            parse_header("5, 5, 1, 20, B3/S23\r".to_string(), 0.0, 0),
+            parse_header("5, 5, 1, 20".to_string(), 0.0, 0)
+        );
+        assert!(
+            generate_grid(5, 5, crlf_data[1..].to_vec(), LoadOptions::default())
+                .unwrap()
+                .equals(
+                    &generate_grid(5, 5, lf_data[1..].to_vec(), LoadOptions::default()).unwrap()
+                )
+        );
+        assert!(generate_grid(1, 3, vec!["1,0,1\r".to_string()], LoadOptions::default()).is_ok());
+
+        let lf = ConwayEngine::new(&"test-files/test.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        let crlf = ConwayEngine::new(
+            &"test-files/crlf_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert!(crlf.grid.equals(&*lf.grid));
+        assert_eq!(
+            crlf.get_update_rate_duration(),
+            lf.get_update_rate_duration()
+        );
+        assert_eq!(crlf.get_number_of_steps(), lf.get_number_of_steps());
+    }
+
+    #[test]
+    fn test_empty_generated_grid() {
+        let test_grid: Vec<String> = Vec::new();
+        let grid = generate_grid(5, 5, test_grid, LoadOptions::default());
+        assert!(grid.is_ok());
+        let grid = grid.unwrap();
+        let test_grid_cells: Vec<usize> = vec![
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        for row_index in 0..grid.size().0 {
+            for column_index in 0..grid.size().1 {
+                assert_eq!(
+                    grid.get(row_index, column_index).unwrap(),
+                    &test_grid_cells[row_index * grid.size().1 + column_index]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_grid() {
+        let test_grid: Vec<String> = vec![
+            "1,0,0,0,0".to_string(),
+            "0,1,0,0,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,0,1,0".to_string(),
+            "0,0,0,0,1".to_string(),
+        ];
+        let test_grid_cells: Vec<usize> = vec![
+            1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1,
+        ];
+        let grid = generate_grid(5, 5, test_grid, LoadOptions::default());
+        assert!(grid.is_ok());
+        let grid = grid.unwrap();
+        for row_index in 0..grid.size().0 {
+            for column_index in 0..grid.size().1 {
+                assert_eq!(
+                    grid.get(row_index, column_index).unwrap(),
+                    &test_grid_cells[row_index * grid.size().1 + column_index]
+                );
+            }
+        }
+
+        let test_grid: Vec<String> = vec![
+            "1,0,0,0,0".to_string(),
+            "0,1,0,0,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,0,1,0".to_string(),
+            "0,0,0,0,1".to_string(),
+        ];
+        let grid = generate_grid(4, 5, test_grid, LoadOptions::default());
+        assert!(grid.is_err());
+
+        // one row more than the header declares is rejected instead of partially loaded
+        let test_grid: Vec<String> = vec![
+            "1,0,0,0,0".to_string(),
+            "0,1,0,0,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,0,1,0".to_string(),
+            "0,0,0,0,1".to_string(),
+            "1,1,1,1,1".to_string(),
+        ];
+        let grid = generate_grid(5, 5, test_grid.clone(), LoadOptions::default());
+        assert_eq!(grid.err(), Some("Row exceeds the grid"));
+        // a narrow extra row is rejected as well
+        let mut narrow_test_grid = test_grid[0..5].to_vec();
+        narrow_test_grid.push("1".to_string());
+        let grid = generate_grid(5, 5, narrow_test_grid, LoadOptions::default());
+        assert_eq!(grid.err(), Some("Row exceeds the grid"));
+        // the same rows fit once the header is correct
+        let grid = generate_grid(6, 5, test_grid, LoadOptions::default());
+        assert!(grid.is_ok());
+        assert_eq!(*grid.unwrap().get(5, 4).unwrap(), 1);
+
+        let test_grid: Vec<String> = vec![
+            "1,0,0,0,0".to_string(),
+            "0,1,0,0,0".to_string(),
+            "0,0,1,0,0".to_string(),
+            "0,0,0,1,0".to_string(),
+            "0,0,0,0,1".to_string(),
+        ];
+        let grid = generate_grid(5, 4, test_grid, LoadOptions::default());
+        assert!(grid.is_err());
+
+        let test_grid: Vec<String> = vec![
+            "1,0,0,0,0".to_string(),
+            "0,1,0,0,0".to_string(),
+            "0,0,a,0,0".to_string(),
+            "0,0,0,1,0".to_string(),
+            "0,0,0,0,1".to_string(),
+        ];
+        let grid = generate_grid(5, 5, test_grid, LoadOptions::default());
+        assert!(grid.is_err());
+    }
+
+    #[test]
+    fn test_strict_columns() {
+        // the second row is one column short of the header width
+        let short_row: Vec<String> = vec!["1,0,1".to_string(), "0,1".to_string()];
+        let strict = LoadOptions {
+            strict_columns: true,
+            ..LoadOptions::default()
+        };
+
+        // lenient, the missing cell is dead
+        let grid = generate_grid(2, 3, short_row.clone(), LoadOptions::default()).unwrap();
+        assert_eq!(grid.get(1, 1), Ok(&1));
+        assert_eq!(grid.get(1, 2), Ok(&0));
+        assert_eq!(grid.count_live(), 3);
+
+        // strict, the short row is an error
+        assert_eq!(
+            generate_grid(2, 3, short_row, strict).err(),
+            Some("Row has fewer columns than the header declares")
+        );
+        // full rows and missing rows are still fine, only the columns are checked
+        assert!(generate_grid(3, 3, vec!["1,0,1".to_string()], strict).is_ok());
+        // a row that's too wide is an error either way
+        assert!(generate_grid(1, 3, vec!["1,0,1,0".to_string()], strict).is_err());
+
+        // the loaders pad short rows unless the options ask for strict columns
+        let path = std::env::temp_dir().join("conway_rust_test_strict_columns.txt");
+        let path = path.to_str().unwrap().to_string();
+        std::fs::write(&path, "2, 3\n1,0,1\n0,1\n").unwrap();
+        let engine =
+            ConwayEngine::try_load(&path, 768.0, 1024.0, 0.0, 0, LoadOptions::default()).unwrap();
+        assert_eq!(engine.population(), 3);
+        assert_eq!(
+            ConwayEngine::try_new(&path, 768.0, 1024.0, 0.0, 0, strict).err(),
+            Some("Row has fewer columns than the header declares".to_string())
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_max_cells() {
+        let error = Some("Grid has more cells than the maximum allowed");
+        let defaults = LoadOptions::default();
+        // rows * columns overflows a usize
+        assert_eq!(
+            generate_grid(usize::MAX, 2, Vec::new(), defaults).err(),
+            error
+        );
+        // no overflow, but 400 million cells is over the limit
+        assert_eq!(
+            generate_grid(20000, 20000, Vec::new(), defaults).err(),
+            error
+        );
+        assert_eq!(parse_rle("x = 20000, y = 20000\n!", MAX_CELLS).err(), error);
+        assert_eq!(
+            generate_random_grid(20000, 20000, 0.5, Some(1), MAX_CELLS).err(),
+            error
+        );
+
+        // the header only reads the size, the file is rejected before its grid is allocated
+        let path = std::env::temp_dir().join("conway_rust_test_max_cells.txt");
+        let path = path.to_str().unwrap().to_string();
+        std::fs::write(&path, "20000, 20000\n").unwrap();
+        assert!(parse_header("20000, 20000".to_string(), 0.0, 0).is_ok());
+        assert_eq!(
+            ConwayEngine::try_load(&path, 768.0, 1024.0, 0.0, 0, defaults).err(),
+            error.map(str::to_string)
+        );
+
+        // a smaller limit applies to native files, RLE patterns and random boards
+        let options = LoadOptions {
+            max_cells: 24,
+            ..defaults
+        };
+        std::fs::write(&path, "5, 5\n1,1\n").unwrap();
+        assert!(ConwayEngine::try_load(&path, 768.0, 1024.0, 0.0, 0, defaults).is_ok());
+        assert_eq!(
+            ConwayEngine::try_load(&path, 768.0, 1024.0, 0.0, 0, options).err(),
+            error.map(str::to_string)
+        );
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(parse_rle("x = 5, y = 5\no!", 24).err(), error);
+        assert!(
+            ConwayEngine::new_random(5, 5, 0.5, Some(1), 768.0, 1024.0, 0.0, 0, 24)
+                .err()
+                .is_some()
+        );
+        // the limit itself is allowed
+        assert!(generate_grid(4, 6, Vec::new(), options).is_ok());
+        assert!(parse_rle("x = 6, y = 4\no!", 24).is_ok());
+        assert!(generate_random_grid(4, 6, 0.5, Some(1), 24).is_ok());
+    }
+
+    #[test]
+    fn test_zero_sized_grid() {
+        assert_eq!(
+            parse_header("0, 0".to_string(), 0.0, 0),
+            Err("Header row and column size must be greater than zero")
+        );
+        assert!(parse_header("5, 0, 1, 20".to_string(), 0.0, 0).is_err());
+        assert!(parse_rle("x = 0, y = 3\n!", MAX_CELLS).is_err());
+        assert!(parse_plaintext("!Name: empty\n\n", MAX_CELLS).is_err());
+
+        // a "0, 0" header is a clean error instead of an empty grid
+        let result = ConwayEngine::try_new(
+            &"test-files/zero_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+            LoadOptions::default(),
+        );
+        assert_eq!(
+            result.err().as_deref(),
+            Some("Header row and column size must be greater than zero")
+        );
+
+        // the spacing of an empty grid is still a number
+        let engine =
+            ConwayEngine::from_grid(grid::Grid::new(0, 0, 0), "empty", 768.0, 1024.0, 0.0, 0);
+        assert_eq!(engine.get_grid_spacing(), (1024.0, 768.0));
+    }
+
+    #[test]
+    fn test_get_grid_spacing() {
+        let engine = ConwayEngine::new(&"test-files/test2.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        let (x_width, y_width) = engine.get_grid_spacing();
+        assert_eq!(x_width, 204.8);
+        assert_eq!(y_width, 153.6);
+
+        let engine = ConwayEngine::new(&"test-files/test3.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        let (x_width, y_width) = engine.get_grid_spacing();
+        assert_eq!(x_width, 64.0);
+        assert_eq!(y_width, 51.2);
+    }
+
+    #[test]
+    fn test_b0_rule() {
+        // an empty board with B0 would fill solid every other generation, the background stays dead instead
+        let mut engine =
+            ConwayEngine::new_random(6, 6, 0.0, None, 768.0, 1024.0, 0.0, 0, MAX_CELLS).unwrap();
+        engine.set_ruleset("B012/S".parse().unwrap());
+        engine.take_step();
+        assert_eq!(engine.population(), 0);
+
+        // on a torus every cell sees the same background, so the board can be stepped without the trick:
+        // the stored grid matches the real board on even generations and its inverse on odd ones
+        let ruleset: ruleset::Ruleset = "B01/S23".parse().unwrap();
+        let real = generate_random_grid(12, 12, 0.4, Some(5), MAX_CELLS).unwrap();
+        for backend in [Backend::Dense, Backend::Sparse].iter() {
+            let mut engine = ConwayEngine::from_grid(real.clone(), "b0", 768.0, 1024.0, 0.0, 0);
+            engine.set_boundary(BoundaryCondition::Toroidal);
+            engine.set_ruleset(ruleset.clone());
+            engine.set_backend(*backend);
+            let mut expected = real.clone();
+            for generation in 1..=6 {
+                let mut next = grid::Grid::new(12, 12, 0);
+                for (row, column, cell) in expected.iter_cells() {
+                    let neighbors = expected
+                        .get_number_of_neighbors_wrapped(row, column)
+                        .unwrap();
+                    let alive = if *cell > 0 {
+                        ruleset.is_survival(neighbors)
+                    } else {
+                        ruleset.is_birth(neighbors)
+                    };
+                    next.set(row, column, alive as usize).unwrap();
+                }
+                expected = next;
+                engine.take_step();
+                for (row, column, cell) in expected.iter_cells() {
+                    let stored_alive = engine.get_cell(row, column) > 0;
+                    assert_eq!(
+                        stored_alive,
+                        (*cell > 0) != (generation % 2 == 1),
+                        "{:?} generation {} ({}, {})",
+                        backend,
+                        generation,
+                        row,
+                        column
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_dimensions() {
+        let mut engine =
+            ConwayEngine::new(&"test-files/test2.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        engine.set_dimensions(500.0, 1000.0);
+        assert_eq!(engine.get_grid_spacing(), (100.0, 200.0));
+        assert_eq!(
+            engine.get_square_spacing_and_offset(),
+            (100.0, (0.0, 250.0))
+        );
+        // the grid itself is untouched
+        assert_eq!(engine.get_grid_dimensions(), (5, 5));
+
+        engine.set_dimensions(1024.0, 768.0);
+        assert_eq!(engine.get_grid_spacing(), (204.8, 153.6));
+    }
+
+    #[test]
+    fn test_get_square_spacing_and_offset() {
+        // 5x5 grid in a 1024x768 window, the height limits the cells to 153.6 pixels
+        let engine = ConwayEngine::new(&"test-files/test2.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        let (spacing, (x_offset, y_offset)) = engine.get_square_spacing_and_offset();
+        assert_eq!(spacing, 153.6);
+        assert_eq!(x_offset, (1024.0 - 5.0 * 153.6) / 2.0);
+        assert_eq!(y_offset, 0.0);
+
+        // tall window, the width limits the cells and the margins go to the top and bottom
+        let engine = ConwayEngine::new(&"test-files/test2.txt".to_string(), 1000.0, 500.0, 0.0, 0);
+        let (spacing, (x_offset, y_offset)) = engine.get_square_spacing_and_offset();
+        assert_eq!(spacing, 100.0);
+        assert_eq!(x_offset, 0.0);
+        assert_eq!(y_offset, 250.0);
+
+        // square window, nothing changes
+        let engine = ConwayEngine::new(&"test-files/test2.txt".to_string(), 500.0, 500.0, 0.0, 0);
+        assert_eq!(engine.get_square_spacing_and_offset(), (100.0, (0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_get_title_string() {
+        let mut engine =
+            ConwayEngine::new(&"test-files/test2.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert_eq!(
+            engine.get_title_string(),
+            format!(
+                "Conway-rust v{}: test-files/test2.txt -- gen 0",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        // the generation counts up with every step
+        engine.take_step();
+        engine.take_step();
+        assert!(engine.get_title_string().contains(" -- gen 2"));
+
+        let engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert_eq!(
+            engine.get_title_string(),
+            format!(
+                "Conway-rust v{}: test-files/glider_test.txt -- gen 0 -- non-stop",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            1,
+        );
+        engine.take_step();
+        assert_eq!(
+            engine.get_title_string(),
+            format!(
+                "Conway-rust v{}: test-files/glider_test.txt -- gen 1 -- simulation ended",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        let engine =
+            ConwayEngine::new(&"test-files/no-file.txt".to_string(), 768.0, 1024.0, 0.0, 1);
+        assert_eq!(
+            engine.get_title_string(),
+            format!(
+                "Conway-rust v{}: No file found, using default pattern -- gen 0",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        let engine =
+            ConwayEngine::new(&"test-files/no-file.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert_eq!(
+            engine.get_title_string(),
+            format!(
+                "Conway-rust v{}: No file found, using default pattern -- gen 0 -- non-stop",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        let mut engine =
+            ConwayEngine::new(&"test-files/no-file.txt".to_string(), 768.0, 1024.0, 0.0, 1);
+        engine.take_step();
+        assert_eq!(
+            engine.get_title_string(),
+            format!(
+                "Conway-rust v{}: No file found, using default pattern -- gen 1 -- simulation ended",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    fn test_wrapped_glider() {
+        // 15 x 16 grid, glider moves one row and one column every 4 steps
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        engine.set_wrap_edges(true);
+        let initial_grid = engine.grid.clone_backend();
+        let (row_size, column_size) = engine.get_grid_dimensions();
+
+        // glider leaves through the bottom and right edges, and re-enters through the top and left edges
+        let mut crossed_top = false;
+        let mut crossed_left = false;
+        for _ in 0..(4 * row_size * column_size) {
+            engine.take_step();
+            let mut population = 0;
+            for row in 0..row_size {
+                for column in 0..column_size {
+                    if engine.get_cell(row, column) > 0 {
+                        population += 1;
+                        crossed_top |= row == 0;
+                        crossed_left |= column == 0;
+                    }
+                }
+            }
+            // glider never loses a cell to an edge
+            assert_eq!(population, 5);
+        }
+        assert!(crossed_top);
+        assert!(crossed_left);
+
+        // glider is back where it started
+        assert_same_cells(&engine, &*initial_grid);
+
+        // bounded edges, the glider collapses into a block in the corner
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        for _ in 0..(4 * row_size) {
+            engine.take_step();
+        }
+        assert_eq!(engine.population(), 4);
+    }
+
+    #[test]
+    fn test_ruleset() {
+        // default pattern is a vertical blinker in the middle of a 5x5 grid
+        let mut engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        engine.take_step();
+        // Conway, blinker flips to horizontal, the center survives and ages
+        assert_eq!(engine.get_cell(1, 2), 0);
+        assert_eq!(engine.get_cell(2, 1), 1);
+        assert_eq!(engine.get_cell(2, 2), 2);
+        assert_eq!(engine.get_cell(2, 3), 1);
+        assert_eq!(engine.get_cell(3, 2), 0);
+
+        // no survival, the center of the blinker dies with the ends
+        let mut engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        engine.set_ruleset("B3/S".parse::<ruleset::Ruleset>().unwrap());
+        engine.take_step();
+        assert_eq!(engine.get_cell(1, 2), 0);
+        assert_eq!(engine.get_cell(2, 1), 1);
+        assert_eq!(engine.get_cell(2, 2), 0);
+        assert_eq!(engine.get_cell(2, 3), 1);
+        assert_eq!(engine.get_cell(3, 2), 0);
+    }
+
+    #[test]
+    fn test_changed_cells() {
+        // 5x5 window, vertical blinker in the middle column
+        let mut engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        assert_eq!(engine.changed_cells(), None);
+
+        // the ends die, the sides are born and the center ages
+        engine.take_step();
+        assert_eq!(
+            engine.changed_cells(),
+            Some(vec![(1, 2, 0), (2, 1, 1), (2, 2, 2), (2, 3, 1), (3, 2, 0)])
+        );
+        engine.take_step();
+        assert_eq!(
+            engine.changed_cells(),
+            Some(vec![(1, 2, 1), (2, 1, 0), (2, 2, 3), (2, 3, 0), (3, 2, 1)])
+        );
+
+        // the sparse backend reports the same cells
+        engine.set_backend(Backend::Sparse);
+        engine.take_step();
+        assert_eq!(
+            engine.changed_cells(),
+            Some(vec![(1, 2, 0), (2, 1, 1), (2, 2, 4), (2, 3, 1), (3, 2, 0)])
+        );
+
+        // without history the previous generation is unknown
+        engine.set_history_capacity(0);
+        engine.take_step();
+        assert_eq!(engine.changed_cells(), None);
+    }
+
+    #[test]
+    fn test_double_buffered_step() {
+        // vertical blinker, stepping in place would see the new horizontal cells while counting later neighbors
+        let mut grid = grid::Grid::new(5, 5, 0);
+        for row_index in 1..4 {
+            grid.set(row_index, 2, 1).unwrap();
+        }
+        let mut expected = grid::Grid::new(5, 5, 0);
+        expected.set(2, 1, 1).unwrap();
+        expected.set(2, 2, 2).unwrap();
+        expected.set(2, 3, 1).unwrap();
+
+        for backend in [Backend::Dense, Backend::Sparse].iter() {
+            let mut engine =
+                ConwayEngine::from_grid(grid.clone(), "blinker", 768.0, 1024.0, 0.0, 0);
+            engine.set_backend(*backend);
+            engine.take_step();
+            // every cell matches, dead ones included
+            for row_index in 0..5 {
+                for column_index in 0..5 {
+                    assert_eq!(
+                        engine.get_cell(row_index, column_index),
+                        *expected.get(row_index, column_index).unwrap(),
+                        "{:?} ({}, {})",
+                        backend,
+                        row_index,
+                        column_index
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_many() {
+        // the glider stops at its step limit of 3
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            3,
+        );
+        assert_eq!(engine.step_many(2), 2);
+        assert_eq!(engine.get_generation(), 2);
+        assert_eq!(engine.step_many(10), 1);
+        assert_eq!(engine.get_generation(), 3);
+        assert!(engine.is_simulation_ended());
+        assert_eq!(engine.step_many(10), 0);
+
+        // a still life ends after the first step
+        let mut engine = ConwayEngine::new(
+            &"test-files/block_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert_eq!(engine.step_many(10), 1);
+        assert!(engine.is_stable());
+
+        // non-stop runs take every step
+        let mut engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        assert_eq!(engine.step_many(0), 0);
+        assert_eq!(engine.step_many(7), 7);
+        assert_eq!(engine.get_generation(), 7);
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            3,
+        );
+        // nothing to step back to
+        assert!(engine.step_back().is_err());
+
+        let initial_grid = engine.grid.clone_backend();
+        engine.take_step();
+        let first_grid = engine.grid.clone_backend();
+        engine.take_step();
+        engine.take_step();
+        assert!(engine.is_simulation_ended());
+        assert_eq!(engine.get_number_of_steps(), 0);
+        assert_eq!(engine.get_generation(), 3);
+
+        // an ended simulation doesn't count more generations
+        engine.take_step();
+        assert_eq!(engine.get_generation(), 3);
+
+        // stepping back gives the step back to the simulation
+        assert!(engine.step_back().is_ok());
+        assert!(!engine.is_simulation_ended());
+        assert_eq!(engine.get_number_of_steps(), 1);
+        assert!(engine.step_back().is_ok());
+        assert_eq!(engine.get_number_of_steps(), 2);
+        assert_eq!(engine.get_generation(), 1);
+        assert!(engine.grid.equals(&*first_grid));
+
+        // stepping forward again reproduces the same generations
+        engine.take_step();
+        engine.take_step();
+        assert!(engine.is_simulation_ended());
+        for _ in 0..3 {
+            assert!(engine.step_back().is_ok());
+        }
+        assert_eq!(engine.get_number_of_steps(), 3);
+        assert!(engine.grid.equals(&*initial_grid));
+        assert!(engine.step_back().is_err());
+    }
+
+    #[test]
+    fn test_history_capacity() {
+        let mut engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, 0.0, 0);
+        engine.set_history_capacity(2);
+        for _ in 0..5 {
+            engine.take_step();
+        }
+        assert!(engine.step_back().is_ok());
+        assert!(engine.step_back().is_ok());
+        assert!(engine.step_back().is_err());
+
+        // no history kept
+        engine.set_history_capacity(0);
+        engine.take_step();
+        assert!(engine.step_back().is_err());
+    }
+
+    #[test]
+    fn test_is_stable() {
+        // block is a still life
+        let mut engine = ConwayEngine::new(
+            &"test-files/block_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert!(!engine.is_stable());
+        engine.take_step();
+        assert!(engine.is_stable());
+        assert!(engine.is_simulation_ended());
+        assert_eq!(
+            engine.get_title_string(),
+            format!(
+                "Conway-rust v{}: test-files/block_test.txt -- gen 1 -- stabilized",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        // blinker never settles
+        let mut engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, 0.0, 0);
+        for _ in 0..10 {
+            engine.take_step();
+            assert!(!engine.is_stable());
+        }
+        assert!(!engine.is_simulation_ended());
+    }
+
+    #[test]
+    fn test_detect_period() {
+        let mut engine = ConwayEngine::new(
+            &"test-files/blinker_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert_eq!(engine.detect_period(10), None);
+        engine.take_step();
+        assert_eq!(engine.detect_period(10), None);
+        engine.take_step();
+        assert_eq!(engine.detect_period(10), Some(2));
+        // period is longer than what is being looked for
+        assert_eq!(engine.detect_period(1), None);
+        assert_eq!(
+            engine.get_title_string(),
+            format!(
+                "Conway-rust v{}: test-files/blinker_test.txt -- gen 2 -- oscillating (period 2)",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+        // the hashes follow the history when it's stepped back, trimmed or refilled
+        engine.step_back().unwrap();
+        engine.take_step();
+        assert_eq!(engine.detect_period(10), Some(2));
+        engine.set_history_capacity(1);
+        assert_eq!(engine.detect_period(10), None);
+        engine.set_history_capacity(4);
+        for _ in 0..4 {
+            engine.take_step();
+        }
+        assert_eq!(engine.detect_period(10), Some(2));
+        engine.step_back().unwrap();
+        engine.step_back().unwrap();
+        assert_eq!(engine.detect_period(10), Some(2));
+
+        let mut engine = ConwayEngine::new(
+            &"test-files/pulsar_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        for _ in 0..6 {
+            engine.take_step();
+        }
+        assert_eq!(engine.detect_period(10), Some(3));
+        assert!(engine
+            .get_title_string()
+            .ends_with(" -- oscillating (period 3)"));
+
+        // glider moves, never matches an earlier generation
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        for _ in 0..8 {
+            engine.take_step();
+        }
+        assert_eq!(engine.detect_period(8), None);
+    }
+
+    #[test]
+    fn test_edge_population() {
+        // 2 corners, 1 cell on each side and 2 cells inside the ring
+        let mut grid = grid::Grid::new(6, 7, 0);
+        for (row, column) in [
+            (0, 0),
+            (5, 6),
+            (0, 3),
+            (5, 2),
+            (2, 0),
+            (3, 6),
+            (1, 1),
+            (3, 4),
+        ] {
+            grid.set(row, column, 1).unwrap();
+        }
+        for backend in [Backend::Dense, Backend::Sparse] {
+            let mut engine = ConwayEngine::from_grid(grid.clone(), "border", 768.0, 1024.0, 0.0, 0);
+            engine.set_backend(backend);
+            assert_eq!(engine.edge_population(), 6, "{:?}", backend);
+            assert_eq!(engine.population(), 8, "{:?}", backend);
+        }
+
+        // every cell of a single row is on the edge
+        let mut grid = grid::Grid::new(1, 4, 0);
+        grid.set(0, 1, 1).unwrap();
+        grid.set(0, 2, 1).unwrap();
+        let engine = ConwayEngine::from_grid(grid, "row", 768.0, 1024.0, 0.0, 0);
+        assert_eq!(engine.edge_population(), 2);
+
+        // the default blinker stays in the middle
+        let engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        assert_eq!(engine.edge_population(), 0);
+    }
+
+    #[test]
+    fn test_population() {
+        // default oscillator, 3 cells in a 5x5 grid
+        let mut engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        assert_eq!(engine.population(), 3);
+        assert_eq!(engine.density(), 3.0 / 25.0);
+        assert_eq!(engine.population_delta(), 0);
+        engine.take_step();
+        assert_eq!(engine.population(), 3);
+        assert_eq!(engine.population_delta(), 0);
+
+        // glider, 5 cells in a 15x16 grid
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert_eq!(engine.population(), 5);
+        assert_eq!(engine.density(), 5.0 / 240.0);
+        engine.take_step();
+        assert_eq!(engine.population(), 5);
+        assert_eq!(engine.population_delta(), 0);
+
+        // glider hits the corner and turns into a block
+        for _ in 0..60 {
+            engine.take_step();
+        }
+        assert_eq!(engine.population(), 4);
+
+        // single cell dies
+        let mut engine =
+            ConwayEngine::new(&"test-files/test.txt".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert_eq!(engine.population(), 5);
+        engine.take_step();
+        assert_eq!(engine.population(), 3);
+        assert_eq!(engine.population_delta(), -2);
+        assert!(engine.step_back().is_ok());
+        assert_eq!(engine.population(), 5);
+        assert_eq!(engine.population_delta(), 0);
+    }
+
+    #[test]
+    fn test_toggle_cell() {
+        let mut engine = ConwayEngine::new(
+            &"test-files/block_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert!(engine.toggle_cell(0, 0).is_ok());
+        assert_eq!(engine.get_cell(0, 0), 1);
+        assert!(engine.toggle_cell(0, 0).is_ok());
+        assert_eq!(engine.get_cell(0, 0), 0);
+        assert!(engine.toggle_cell(6, 0).is_err());
+        assert!(engine.toggle_cell(0, 6).is_err());
+
+        // editing a stabilized board starts it back up
+        engine.take_step();
+        assert!(engine.is_simulation_ended());
+        assert!(engine.toggle_cell(2, 2).is_ok());
+        assert!(!engine.is_stable());
+        assert!(!engine.is_simulation_ended());
+    }
+
+    #[test]
+    fn test_parse_rle() {
+        // glider
+        let result = parse_rle(
+            "#N Glider\n#C comment line\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!",
+            MAX_CELLS,
+        );
+        assert!(result.is_ok());
+        let (grid, ruleset) = result.unwrap();
+        assert_eq!(grid.size(), (3, 3));
+        assert_eq!(ruleset, ruleset::Ruleset::default());
+        let expected_cells: Vec<usize> = vec![0, 1, 0, 0, 0, 1, 1, 1, 1];
+        for row_index in 0..3 {
+            for column_index in 0..3 {
+                assert_eq!(
+                    *grid.get(row_index, column_index).unwrap(),
+                    expected_cells[row_index * 3 + column_index]
+                );
+            }
+        }
+
+        // pulsar, split over several lines with blank rows encoded as a run of '$'
+        let result = parse_rle(
+            "x = 13, y = 13, rule = 23/3\n\
+             2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$\n\
+             2b3o3b3o2b$o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+            MAX_CELLS,
+        );
+        assert!(result.is_ok());
+        let (grid, ruleset) = result.unwrap();
+        assert_eq!(ruleset, ruleset::Ruleset::default());
+        // must match the native pulsar test file, which has a border of 2 dead cells
+        let engine = ConwayEngine::new(
+            &"test-files/pulsar_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        let mut population = 0;
+        for row_index in 0..13 {
+            for column_index in 0..13 {
+                let cell = *grid.get(row_index, column_index).unwrap();
+                assert_eq!(cell, engine.get_cell(row_index + 2, column_index + 2));
+                population += cell;
+            }
+        }
+        assert_eq!(population, 48);
+
+        // HighLife rule is carried through
+        let (_grid, ruleset) = parse_rle("x = 1, y = 1, rule = B36/S23\no!", MAX_CELLS).unwrap();
+        assert_eq!(ruleset, "B36/S23".parse::<ruleset::Ruleset>().unwrap());
+
+        // errors
+        assert!(parse_rle("", MAX_CELLS).is_err());
+        assert!(parse_rle("x = 3\nbob$2bo$3o!", MAX_CELLS).is_err());
+        assert!(parse_rle("x = a, y = 3\nbob$2bo$3o!", MAX_CELLS).is_err());
+        assert!(parse_rle("x = 3, y = 3, rule = B9/S23\nbob$2bo$3o!", MAX_CELLS).is_err());
+        assert!(parse_rle("x = 2, y = 3\nbob$2bo$3o!", MAX_CELLS).is_err());
+        assert!(parse_rle("x = 3, y = 2\nbob$2bo$3o!", MAX_CELLS).is_err());
+        assert!(parse_rle("x = 3, y = 3\nbob$2bo$3o?", MAX_CELLS).is_err());
+
+        // run counts that overflow or run past the header size are errors, not panics
+        assert_eq!(
+            parse_rle("x = 3, y = 3\n99999999999999999999999o!", MAX_CELLS).err(),
+            Some("RLE run count is too large")
+        );
+        let huge_run = format!("x = 3, y = 3\n{}b!", usize::MAX);
+        assert_eq!(
+            parse_rle(&huge_run, MAX_CELLS).err(),
+            Some("RLE pattern is larger than the header size")
+        );
+        let huge_rows = format!("x = 3, y = 3\no{}$!", usize::MAX);
+        assert_eq!(
+            parse_rle(&huge_rows, MAX_CELLS).err(),
+            Some("RLE pattern is larger than the header size")
+        );
+        assert!(parse_rle("x = 3, y = 3\n4b!", MAX_CELLS).is_err());
+        assert!(parse_rle("x = 3, y = 3\n3$o!", MAX_CELLS).is_err());
+        // a trailing run of '$' up to the last row is fine
+        assert!(parse_rle("x = 3, y = 3\n3b3$!", MAX_CELLS).is_ok());
+    }
+
+    #[test]
+    fn test_new_rle() {
+        let mut engine =
+            ConwayEngine::new(&"test-files/glider.rle".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert_eq!(engine.get_grid_dimensions(), (10, 10));
+        assert_eq!(engine.get_name(), "test-files/glider.rle");
+        assert_eq!(engine.population(), 5);
+        assert_eq!(engine.get_cell(0, 1), 1);
+        assert_eq!(engine.get_cell(1, 2), 1);
+        assert_eq!(engine.get_cell(2, 0), 1);
+        assert_eq!(engine.get_cell(2, 1), 1);
+        assert_eq!(engine.get_cell(2, 2), 1);
+
+        // the rule in the header is used, the middle cell with 6 neighbors is only born under HighLife
+        let mut highlife = ConwayEngine::new(
+            &"test-files/highlife.rle".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert_eq!(highlife.ruleset, "B36/S23".parse().unwrap());
+        highlife.take_step();
+        assert_ne!(highlife.get_cell(1, 1), 0);
+        let mut life = ConwayEngine::new(
+            &"test-files/highlife.rle".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        life.set_ruleset(ruleset::Ruleset::default());
+        life.take_step();
+        assert_eq!(life.get_cell(1, 1), 0);
+
+        // glider moves one cell down and right after 4 steps
+        for _ in 0..4 {
+            engine.take_step();
+        }
+        assert_eq!(engine.population(), 5);
+        assert_eq!(engine.get_cell(1, 2), 1);
+        assert_eq!(engine.get_cell(3, 3), 1);
+    }
+
+    #[test]
+    fn test_parse_ascii() {
+        // '*' and spaces, ragged rows are padded with dead cells
+        let grid = parse_ascii(" *\n  *\r\n***\n\n", '*', ' ', MAX_CELLS).unwrap();
+        assert_eq!(grid.size(), (3, 3));
+        let expected_cells: Vec<usize> = vec![0, 1, 0, 0, 0, 1, 1, 1, 1];
+        for row_index in 0..3 {
+            for column_index in 0..3 {
+                assert_eq!(
+                    *grid.get(row_index, column_index).unwrap(),
+                    expected_cells[row_index * 3 + column_index]
+                );
+            }
+        }
+
+        // '#' and '.', every other character is dead and blank lines inside the pattern are dead rows
+        let grid = parse_ascii("#.#x\n\n.##", '#', '.', MAX_CELLS).unwrap();
+        assert_eq!(grid.size(), (3, 4));
+        assert_eq!(grid.count_live(), 4);
+        assert_eq!(*grid.get(0, 3).unwrap(), 0);
+        assert_eq!(*grid.get(1, 0).unwrap(), 0);
+        assert_eq!(*grid.get(2, 2).unwrap(), 1);
+
+        assert_eq!(
+            parse_ascii("\n\n", '#', '.', MAX_CELLS).err(),
+            Some("ASCII pattern has no rows")
+        );
+        assert!(parse_ascii("##", '#', '#', MAX_CELLS).is_err());
+
+        let engine = ConwayEngine::try_load_ascii(
+            &"test-files/glider_ascii.txt".to_string(),
+            ('*', ' '),
+            768.0,
+            1024.0,
+            0.5,
+            3,
+            LoadOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(engine.get_grid_dimensions(), (5, 5));
+        assert_eq!(engine.population(), 5);
+        assert!(ConwayEngine::try_load_ascii(
+            &"test-files/missing.txt".to_string(),
+            ('*', ' '),
+            768.0,
+            1024.0,
+            0.5,
+            3,
+            LoadOptions::default(),
+        )
+        .is_err());
+
+        // the cell limit is checked before the grid is allocated
+        assert_eq!(
+            parse_ascii("*\n  *", '*', ' ', 5).err(),
+            Some("Grid has more cells than the maximum allowed")
+        );
+        assert!(parse_ascii("*\n  *", '*', ' ', 6).is_ok());
+    }
+
+    #[test]
+    fn test_parse_plaintext() {
+        // ragged rows are padded with dead cells
+        let result = parse_plaintext("!Name: Glider\n!\n.O\n..O\nOOO\n", MAX_CELLS);
+        assert!(result.is_ok());
+        let grid = result.unwrap();
+        assert_eq!(grid.size(), (3, 3));
+        let expected_cells: Vec<usize> = vec![0, 1, 0, 0, 0, 1, 1, 1, 1];
+        for row_index in 0..3 {
+            for column_index in 0..3 {
+                assert_eq!(
+                    *grid.get(row_index, column_index).unwrap(),
+                    expected_cells[row_index * 3 + column_index]
+                );
+            }
+        }
+
+        // empty lines are rows of dead cells
+        let grid = parse_plaintext("O\n\n.O", MAX_CELLS).unwrap();
+        assert_eq!(grid.size(), (3, 2));
+        assert_eq!(*grid.get(2, 1).unwrap(), 1);
+
+        assert!(parse_plaintext("", MAX_CELLS).is_err());
+        assert!(parse_plaintext("!only a comment", MAX_CELLS).is_err());
+        assert!(parse_plaintext(".O.\n.x.", MAX_CELLS).is_err());
+
+        // the cell limit is checked before the grid is allocated
+        assert_eq!(
+            parse_plaintext(".O.\n..O", 5).err(),
+            Some("Grid has more cells than the maximum allowed")
+        );
+        assert!(parse_plaintext(".O.\n..O", 6).is_ok());
+    }
+
+    #[test]
+    fn test_new_plaintext() {
+        let engine = ConwayEngine::new(
+            &"test-files/glider.cells".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        assert_eq!(engine.get_grid_dimensions(), (8, 8));
+        assert_eq!(engine.get_name(), "test-files/glider.cells");
+        assert_eq!(engine.population(), 5);
+        assert_eq!(engine.get_cell(0, 1), 1);
+        assert_eq!(engine.get_cell(1, 2), 1);
+        assert_eq!(engine.get_cell(2, 0), 1);
+        assert_eq!(engine.get_cell(2, 1), 1);
+        assert_eq!(engine.get_cell(2, 2), 1);
+    }
+
+    #[test]
+    fn test_save_to_file() {
+        let path = std::env::temp_dir().join("conway_rust_test_save_to_file.txt");
+        let path = path.to_str().unwrap().to_string();
+
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            2.0,
+            30,
+        );
+        engine.take_step();
+        assert!(engine.toggle_cell(14, 15).is_ok());
+        assert!(engine.save_to_file(&path).is_ok());
+
+        let file_data = read_engine_file(&path).unwrap();
+        assert_eq!(file_data[0], "15, 16, 2, 30, B3/S23, R1");
+        assert_eq!(file_data.len(), 16);
+
+        // reloading gives back the identical grid
+        let reloaded = ConwayEngine::new(&path, 768.0, 1024.0, 0.0, 0);
+        assert!(reloaded.grid.equals(&*engine.grid));
+        assert_eq!(reloaded.get_number_of_steps(), 30);
+        assert_eq!(reloaded.get_cell(14, 15), 1);
+
+        // the ruleset round-trips
+        engine.set_ruleset("B36/S23".parse::<ruleset::Ruleset>().unwrap());
+        assert!(engine.save_to_file(&path).is_ok());
+        assert_eq!(
+            read_engine_file(&path).unwrap()[0],
+            "15, 16, 2, 30, B36/S23, R1"
+        );
+        let reloaded = ConwayEngine::new(&path, 768.0, 1024.0, 0.0, 0);
+        assert_eq!(reloaded.ruleset, engine.ruleset);
+
+        // a range rule with several entries and its radius round-trip as well
+        engine.set_ruleset("B34..45/S8,33..57".parse::<ruleset::Ruleset>().unwrap());
+        engine.set_radius(2);
+        assert!(engine.save_to_file(&path).is_ok());
+        assert_eq!(
+            read_engine_file(&path).unwrap()[0],
+            "15, 16, 2, 30, B34..45/S8,33..57, R2"
+        );
+        let reloaded = ConwayEngine::new(&path, 768.0, 1024.0, 0.0, 0);
+        assert_eq!(reloaded.get_name(), &path);
+        assert_eq!(reloaded.ruleset, engine.ruleset);
+        assert_eq!(reloaded.get_radius(), 2);
+        assert!(reloaded.grid.equals(&*engine.grid));
+
+        // a bounded run that has ended is saved with its steps, not as non-stop
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            2.0,
+            3,
+        );
+        engine.step_many(3);
+        assert!(engine.is_simulation_ended());
+        assert_eq!(engine.get_number_of_steps(), 0);
+        assert!(engine.save_to_file(&path).is_ok());
+        assert_eq!(
+            read_engine_file(&path).unwrap()[0],
+            "15, 16, 2, 3, B3/S23, R1"
+        );
+        let mut reloaded = ConwayEngine::new(&path, 768.0, 1024.0, 0.0, 0);
+        assert!(reloaded.grid.equals(&*engine.grid));
+        assert_eq!(reloaded.get_number_of_steps(), 3);
+        reloaded.step_many(5);
+        assert!(reloaded.is_simulation_ended());
+        assert_eq!(reloaded.generation, 3);
+
+        std::fs::remove_file(&path).unwrap();
+
+        // directory that doesn't exist
+        assert!(engine
+            .save_to_file("test-files/no-directory/save.txt")
+            .is_err());
+    }
+
+    #[test]
+    fn test_save_binary() {
+        let path = std::env::temp_dir().join("conway_rust_test_save_binary.bin");
+        let path = path.to_str().unwrap().to_string();
+
+        // glider on the sparse backend, the cells come back without their ages
+        let mut engine = ConwayEngine::new(
+            &"test-files/glider_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        engine.set_backend(Backend::Sparse);
+        engine.take_step();
+        assert!(engine.save_as(&path, SaveFormat::Binary).is_ok());
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().len(),
+            8 + (15 * 16_u64).div_ceil(8)
+        );
+        let reloaded =
+            ConwayEngine::try_load(&path, 768.0, 1024.0, 0.5, 7, LoadOptions::default()).unwrap();
+        assert!(reloaded.grid.same_live_cells(&*engine.grid));
+        assert_eq!(reloaded.update_rate, 0.5);
+        assert_eq!(reloaded.get_number_of_steps(), 7);
+
+        // a cut off file is an error
+        let data = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &data[..data.len() - 1]).unwrap();
+        assert_eq!(
+            ConwayEngine::try_load(&path, 768.0, 1024.0, 0.5, 7, LoadOptions::default()).err(),
+            Some("Binary grid is truncated".to_string())
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_save_format() {
+        assert_eq!("native".parse(), Ok(SaveFormat::Native));
+        assert_eq!(" Binary".parse(), Ok(SaveFormat::Binary));
+        assert!("csv".parse::<SaveFormat>().is_err());
+    }
+
+    #[test]
+    fn test_cell_age() {
+        // block never changes, every cell ages each step
+        let mut engine = ConwayEngine::new(
+            &"test-files/block_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            5,
+        );
+        engine.take_step();
+        assert_eq!(engine.get_cell(2, 2), 2);
+        assert_eq!(engine.get_cell(3, 3), 2);
+
+        // blinker, the center keeps aging while the ends are reborn every step
+        let mut engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        for _ in 0..4 {
+            engine.take_step();
+        }
+        assert_eq!(engine.get_cell(2, 2), 5);
+        assert_eq!(engine.get_cell(1, 2), 1);
+        assert_eq!(engine.get_cell(3, 2), 1);
+        assert_eq!(engine.population(), 3);
+        // ages don't hide the oscillation
+        assert_eq!(engine.detect_period(4), Some(2));
+    }
+
+    #[test]
+    fn test_update_rate_duration() {
+        let engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, 0.25, 0);
+        assert_eq!(
+            engine.get_update_rate_duration(),
+            Duration::from_millis(250)
+        );
+
+        let engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, 2.0, 0);
+        assert_eq!(engine.get_update_rate_duration(), Duration::from_secs(2));
+
+        // the decimal part is a fraction of a second
+        for (update_rate, duration) in [
+            (0.5, Duration::from_millis(500)),
+            (0.05, Duration::from_millis(50)),
+            (3.0, Duration::from_secs(3)),
+        ]
+        .iter()
+        {
+            let engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, *update_rate, 0);
+            assert_eq!(engine.get_update_rate_duration(), *duration);
+        }
+
+        // zero and negative rates fall back to the minimum
+        let engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, 0.0, 0);
+        assert_eq!(engine.get_update_rate_duration(), Duration::from_millis(1));
+        let engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, -3.0, 0);
+        assert_eq!(engine.get_update_rate_duration(), Duration::from_millis(1));
+
+        // infinite and huge rates fall back to the maximum instead of panicking
+        let engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, f64::NAN, 0);
+        assert_eq!(engine.get_update_rate_duration(), Duration::from_millis(1));
+        for update_rate in [f64::INFINITY, 1e30].iter() {
+            let engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, *update_rate, 0);
+            assert_eq!(
+                engine.get_update_rate_duration(),
+                Duration::from_secs_f64(MAX_UPDATE_RATE)
+            );
+        }
+
+        // header rates can be fractional as well
+        let data = parse_header("5, 5, 0.25, 1".to_string(), 1.0, 0).unwrap();
+        assert_eq!(data, (5, 5, 0.25, 1, ruleset::Ruleset::default(), 1));
+
+        // but not infinite, NaN or longer than the maximum
+        for update_rate in ["inf", "NaN", "1e9"].iter() {
+            let header = format!("5, 5, {}, 1", update_rate);
+            assert_eq!(
+                parse_header(header, 1.0, 0).err(),
+                Some("Update rate must be a finite number of seconds, at most one day")
+            );
+        }
+        assert!(check_update_rate(MAX_UPDATE_RATE).is_ok());
+        assert!(check_update_rate(-1.0).is_ok());
+        let data = parse_header("5, 5, 0.05, 1".to_string(), 1.0, 0).unwrap();
+        assert_eq!(data, (5, 5, 0.05, 1, ruleset::Ruleset::default(), 1));
+    }
+
+    #[test]
+    fn test_infinite_glider() {
+        // glider in the top left of a small 6 x 6 grid, heading down and to the right
+        let mut grid = grid::Grid::new(6, 6, 0);
+        for (row, column) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)].iter() {
+            grid.set(*row, *column, 1).unwrap();
+        }
+        let mut engine = ConwayEngine::from_grid(grid, "glider", 768.0, 1024.0, 1.0, 0);
+        engine.set_infinite(true);
+        let initial_spacing = engine.get_grid_spacing();
+
+        // the glider moves 10 cells, well past the original boundary
+        for _ in 0..40 {
+            engine.take_step();
+            assert_eq!(engine.population(), 5);
+        }
+        let (row_size, column_size) = engine.get_grid_dimensions();
+        assert!(row_size > 6 && column_size > 6);
+        assert!(engine.get_grid_spacing().0 < initial_spacing.0);
+        assert!(!engine.is_simulation_ended());
+
+        // without growing the glider crashes into the corner
+        let mut grid = grid::Grid::new(6, 6, 0);
+        for (row, column) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)].iter() {
+            grid.set(*row, *column, 1).unwrap();
+        }
+        let mut engine = ConwayEngine::from_grid(grid, "glider", 768.0, 1024.0, 1.0, 0);
+        for _ in 0..40 {
+            engine.take_step();
+        }
+        assert_eq!(engine.get_grid_dimensions(), (6, 6));
+        assert_ne!(engine.population(), 5);
+    }
+
+    #[test]
+    fn test_expand_if_needed_cap() {
+        // a cell on the corner keeps asking for more room
+        let mut grid = grid::Grid::new(MAX_INFINITE_GRID_SIZE / 2 + 1, 3, 0);
+        grid.set(0, 1, 1).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "cap", 768.0, 1024.0, 1.0, 0);
+        engine.expand_if_needed();
+        assert_eq!(engine.get_grid_dimensions(), (MAX_INFINITE_GRID_SIZE, 3));
+        assert_eq!(engine.population(), 1);
+
+        // the living cell is still away from the edges now, nothing changes
+        engine.expand_if_needed();
+        assert_eq!(engine.get_grid_dimensions(), (MAX_INFINITE_GRID_SIZE, 3));
+
+        // at the cap the grid doesn't grow anymore
+        let mut grid = grid::Grid::new(MAX_INFINITE_GRID_SIZE, 3, 0);
+        grid.set(0, 1, 1).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "cap", 768.0, 1024.0, 1.0, 0);
+        engine.expand_if_needed();
+        assert_eq!(engine.get_grid_dimensions(), (MAX_INFINITE_GRID_SIZE, 3));
+
+        // doubling past the most cells allowed keeps the grid as it is
+        let mut grid = grid::Grid::new(6, 6, 0);
+        grid.set(0, 0, 1).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "cap", 768.0, 1024.0, 1.0, 0);
+        engine.set_max_cells(100);
+        engine.expand_if_needed();
+        assert_eq!(engine.get_grid_dimensions(), (6, 6));
+        engine.set_max_cells(144);
+        engine.expand_if_needed();
+        assert_eq!(engine.get_grid_dimensions(), (12, 12));
+    }
+
+    #[test]
+    fn test_generate_random_grid() {
+        // the same seed always gives the same board
+        let grid = generate_random_grid(48, 64, 0.3, Some(42), MAX_CELLS).unwrap();
+        let other_grid = generate_random_grid(48, 64, 0.3, Some(42), MAX_CELLS).unwrap();
+        assert!(grid.equals(&other_grid));
+        let population = count_population(&grid);
+        assert_eq!(population, count_population(&other_grid));
+        // roughly 30% of the 3072 cells are alive
+        assert!(population > 800 && population < 1050);
+
+        // a different seed gives a different board
+        let other_grid = generate_random_grid(48, 64, 0.3, Some(43), MAX_CELLS).unwrap();
+        assert!(!grid.equals(&other_grid));
+
+        // edge densities
+        let grid = generate_random_grid(10, 10, 0.0, None, MAX_CELLS).unwrap();
+        assert_eq!(count_population(&grid), 0);
+        let grid = generate_random_grid(10, 10, 1.0, None, MAX_CELLS).unwrap();
+        assert_eq!(count_population(&grid), 100);
+
+        // bad input
+        assert!(generate_random_grid(10, 10, 1.5, None, MAX_CELLS).is_err());
+        assert!(generate_random_grid(10, 10, -0.1, None, MAX_CELLS).is_err());
+        assert!(generate_random_grid(10, 10, f64::NAN, None, MAX_CELLS).is_err());
+        assert!(generate_random_grid(0, 10, 0.5, None, MAX_CELLS).is_err());
+
+        let engine =
+            ConwayEngine::new_random(48, 64, 0.3, Some(42), 768.0, 1024.0, 1.0, 0, MAX_CELLS)
+                .unwrap();
+        assert_eq!(engine.get_grid_dimensions(), (48, 64));
+        assert_eq!(engine.population(), population);
+        assert!(
+            ConwayEngine::new_random(48, 64, 2.0, None, 768.0, 1024.0, 1.0, 0, MAX_CELLS).is_err()
+        );
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        // default blinker, vertical in the middle column of a 5x5 grid
+        let mut engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+        assert_eq!(engine.to_ascii(), ".....\n..#..\n..#..\n..#..\n.....\n");
+
+        // ages don't change the rendering
+        engine.take_step();
+        engine.take_step();
+        assert_eq!(engine.to_ascii(), ".....\n..#..\n..#..\n..#..\n.....\n");
+        engine.take_step();
+        assert_eq!(engine.to_ascii(), ".....\n.....\n.###.\n.....\n.....\n");
+    }
+
+    #[test]
+    fn test_dying_cells() {
+        // the vertical blinker loses its top and bottom cells, on either backend
+        for backend in [Backend::Dense, Backend::Sparse].iter() {
+            let mut engine = ConwayEngine::new(&"".to_string(), 80.0, 80.0, 0.0, 0);
+            engine.set_backend(*backend);
+            assert!(engine.dying_cells().is_empty());
+            engine.take_step();
+            assert_eq!(engine.dying_cells(), &[(1, 2), (3, 2)]);
+            // and the horizontal one its ends
+            engine.take_step();
+            assert_eq!(engine.dying_cells(), &[(2, 1), (2, 3)]);
+            for (row, column) in engine.dying_cells() {
+                assert_eq!(engine.get_cell(*row, *column), 0);
+            }
+
+            // stepping back forgets them
+            engine.step_back().unwrap();
+            assert!(engine.dying_cells().is_empty());
+        }
+
+        // a block never loses a cell, a lone cell is the only one to die
+        let mut grid = grid::Grid::new(6, 6, 0);
+        for (row, column) in [(1, 1), (1, 2), (2, 1), (2, 2), (4, 4)] {
+            grid.set(row, column, 1).unwrap();
+        }
+        let mut engine = ConwayEngine::from_grid(grid, "block", 768.0, 1024.0, 0.0, 0);
+        engine.take_step();
+        assert_eq!(engine.dying_cells(), &[(4, 4)]);
+    }
+
+    #[test]
+    fn test_trim_to_live() {
+        // a glider in the middle of a big board trims to its 3x3 box, on either backend
+        for backend in [Backend::Dense, Backend::Sparse].iter() {
+            let mut grid = grid::Grid::new(20, 30, 0);
+            for (row, column) in [(7, 11), (8, 12), (9, 10), (9, 11), (9, 12)] {
+                grid.set(row, column, 1).unwrap();
+            }
+            let mut engine = ConwayEngine::from_grid(grid, "glider", 768.0, 1024.0, 0.0, 0);
+            engine.set_backend(*backend);
+            let trimmed = engine.trim_to_live();
+            assert_eq!(trimmed.size(), (3, 3));
+            let cells: Vec<(usize, usize)> = trimmed
+                .iter_live()
+                .map(|(row, column, _)| (row, column))
+                .collect();
+            assert_eq!(cells, vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+
+            // the ages come along
+            engine.take_step();
+            let trimmed = engine.trim_to_live();
+            assert_eq!(trimmed.count_live(), 5);
+            assert!(trimmed.iter_live().any(|(_, _, age)| *age == 2));
+        }
+
+        // nothing alive trims to an empty grid
+        let engine =
+            ConwayEngine::from_grid(grid::Grid::new(4, 4, 0), "empty", 768.0, 1024.0, 0.0, 0);
+        assert_eq!(engine.trim_to_live().size(), (0, 0));
+    }
+
+    #[test]
+    fn test_von_neumann_neighborhood() {
+        // plus sign, every arm has 1 orthogonal neighbor and the center has 4
+        let mut grid = grid::Grid::new(5, 5, 0);
+        for (row, column) in [(1, 2), (2, 1), (2, 2), (2, 3), (3, 2)].iter() {
+            grid.set(*row, *column, 1).unwrap();
+        }
+        let mut engine = ConwayEngine::from_grid(grid, "plus", 768.0, 1024.0, 1.0, 1);
+        engine.set_neighborhood(Neighborhood::VonNeumann);
+        engine.set_ruleset("B2/S1".parse::<ruleset::Ruleset>().unwrap());
+        engine.take_step();
+
+        // the arms survive with 1 neighbor, the center dies with 4,
+        // the diagonal cells between the arms touch 2 arms and are born
+        assert_eq!(engine.get_cell(2, 2), 0);
+        for (row, column) in [(1, 2), (2, 1), (2, 3), (3, 2)].iter() {
+            assert!(engine.get_cell(*row, *column) > 0);
+        }
+        for (row, column) in [(1, 1), (1, 3), (3, 1), (3, 3)].iter() {
+            assert_eq!(engine.get_cell(*row, *column), 1);
+        }
+        assert_eq!(engine.population(), 8);
+    }
+
+    #[test]
+    fn test_radius() {
+        // with exactly 1 living neighbor, a lone cell fills its neighborhood and dies itself
+        for backend in [Backend::Dense, Backend::Sparse].iter() {
+            for (radius, neighborhood, population) in [
+                (1, Neighborhood::Moore, 8),
+                (2, Neighborhood::Moore, 24),
+                (2, Neighborhood::VonNeumann, 12),
+                (3, Neighborhood::Moore, 48),
+            ] {
+                let mut grid = grid::Grid::new(7, 7, 0);
+                grid.set(3, 3, 1).unwrap();
+                let mut engine = ConwayEngine::from_grid(grid, "dot", 768.0, 1024.0, 1.0, 1);
+                engine.set_backend(*backend);
+                engine.set_neighborhood(neighborhood);
+                engine.set_radius(radius);
+                engine.set_ruleset("B1..1/S".parse::<ruleset::Ruleset>().unwrap());
+                engine.take_step();
+                assert_eq!(engine.population(), population);
+                assert_eq!(engine.get_cell(3, 3), 0);
+            }
+        }
+
+        // the default radius is the usual neighborhood, 0 is treated as 1
+        let mut engine =
+            ConwayEngine::from_grid(grid::Grid::new(3, 3, 0), "r", 768.0, 1024.0, 1.0, 1);
+        assert_eq!(engine.get_radius(), 1);
+        engine.set_radius(0);
+        assert_eq!(engine.get_radius(), 1);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let grid = generate_random_grid(200, 200, 0.35, Some(26), MAX_CELLS).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "random", 768.0, 1024.0, 1.0, 0);
+        let mut candidates = Vec::new();
+        let (mut serial_states, mut parallel_states) = (Vec::new(), Vec::new());
+        for _ in 0..10 {
+            let b0_ruleset = engine.step_ruleset();
+            let ruleset = b0_ruleset.as_ref().unwrap_or(&engine.ruleset);
+            engine
+                .grid
+                .step_candidates(engine.boundary, &mut candidates);
+            engine.next_states_serial(ruleset, &candidates, &mut serial_states);
+            engine.next_states_parallel(ruleset, &candidates, &mut parallel_states);
+            assert_eq!(serial_states, parallel_states);
+            let mut serial_grid = engine.grid.new_empty(200, 200);
+            engine.build_next_grid(&mut *serial_grid, &candidates, &serial_states);
+            engine.take_step();
+            assert!(engine.grid.equals(&*serial_grid));
+        }
+    }
+
+    #[test]
+    fn test_sparse_matches_dense() {
+        // random soup with both neighborhoods and edge modes
+        for neighborhood in [Neighborhood::Moore, Neighborhood::VonNeumann].iter() {
+            for boundary in [
+                BoundaryCondition::Dead,
+                BoundaryCondition::Alive,
+                BoundaryCondition::Toroidal,
+            ]
+            .iter()
+            {
+                let grid = generate_random_grid(20, 25, 0.35, Some(11), MAX_CELLS).unwrap();
+                let mut dense = ConwayEngine::from_grid(grid, "dense", 768.0, 1024.0, 1.0, 0);
+                let mut sparse = ConwayEngine::from_grid(
+                    generate_random_grid(20, 25, 0.35, Some(11), MAX_CELLS).unwrap(),
+                    "sparse",
+                    768.0,
+                    1024.0,
+                    1.0,
+                    0,
+                );
+                sparse.set_backend(Backend::Sparse);
+                for engine in [&mut dense, &mut sparse].iter_mut() {
+                    engine.set_neighborhood(*neighborhood);
+                    engine.set_boundary(*boundary);
+                }
+
+                for _ in 0..30 {
+                    dense.take_step();
+                    sparse.take_step();
+                    // ages have to match too, not only the living cells
+                    assert!(sparse.grid.equals(&*dense.grid));
+                    assert_eq!(sparse.is_stable(), dense.is_stable());
+                }
+            }
+        }
+
+        // growing grid, the glider has to keep its shape after the grid expands
+        let mut grid = grid::Grid::new(6, 6, 0);
+        for (row, column) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)].iter() {
+            grid.set(*row, *column, 1).unwrap();
+        }
+        let mut dense = ConwayEngine::from_grid(grid.clone(), "glider", 768.0, 1024.0, 1.0, 0);
+        let mut sparse = ConwayEngine::from_grid(grid, "glider", 768.0, 1024.0, 1.0, 0);
+        sparse.set_backend(Backend::Sparse);
+        dense.set_infinite(true);
+        sparse.set_infinite(true);
+        for _ in 0..40 {
+            dense.take_step();
+            sparse.take_step();
+            assert!(sparse.grid.equals(&*dense.grid));
         }
-        row_counter += 1;
+        assert_eq!(sparse.population(), 5);
     }
 
-    Ok(grid)
-}
+    #[test]
+    fn test_set_backend() {
+        let mut engine =
+            ConwayEngine::new(&"test-files/test.txt".to_string(), 768.0, 1024.0, 1.0, 0);
+        engine.take_step();
+        engine.take_step();
+        let dense_grid = engine.grid.clone_backend();
 
-#[cfg(test)]
-mod test {
-    use super::*;
+        // the grid and the history are converted, stepping back still works
+        engine.set_backend(Backend::Sparse);
+        assert!(engine.grid.equals(&*dense_grid));
+        engine.step_back().unwrap();
+        engine.take_step();
+        assert!(engine.grid.equals(&*dense_grid));
+
+        engine.set_backend(Backend::Dense);
+        assert!(engine.grid.equals(&*dense_grid));
+    }
 
     #[test]
-    fn test_new() {
-        let engine = ConwayEngine::new(&"test-files/test.txt".to_string(), 768.0, 1024.0, 0, 0);
-        for row in 0..engine.get_grid_dimensions().0 {
-            let mut row_s: String = "".to_string();
-            for column in 0..engine.get_grid_dimensions().1 {
-                row_s += &engine.get_cell(row, column).to_string();
-            }
-            println!("{}", row_s);
-        }
-        assert_eq!(engine.get_cell(0, 0), 1);
-        assert_eq!(engine.get_cell(1, 1), 1);
-        assert_eq!(engine.get_cell(2, 2), 1);
-        assert_eq!(engine.get_cell(3, 3), 1);
-        assert_eq!(engine.get_cell(4, 4), 1);
+    fn test_from_pattern() {
+        // 768 x 1024 window with 16 pixel cells
+        let engine = ConwayEngine::from_pattern("Glider", 768.0, 1024.0, 1.0, 0).unwrap();
+        assert_eq!(engine.get_grid_dimensions(), (48, 64));
+        assert_eq!(engine.get_name(), "glider");
+        assert_eq!(engine.population(), 5);
+        // 3 x 3 glider in the center
+        assert_eq!(engine.get_cell(22, 31), 1);
+        assert_eq!(engine.get_cell(23, 32), 1);
+        assert_eq!(engine.get_cell(24, 30), 1);
+        assert_eq!(engine.get_cell(24, 31), 1);
+        assert_eq!(engine.get_cell(24, 32), 1);
 
-        let engine = ConwayEngine::new(&"".to_string(), 768.0, 2014.0, 0, 0);
-        for row in 0..engine.get_grid_dimensions().0 {
-            let mut row_s: String = "".to_string();
-            for column in 0..engine.get_grid_dimensions().1 {
-                row_s += &engine.get_cell(row, column).to_string();
-            }
-            println!("{}", row_s);
+        // the grid grows if the window is too small for the pattern
+        let engine = ConwayEngine::from_pattern("gosper-glider-gun", 100.0, 100.0, 1.0, 0).unwrap();
+        assert_eq!(engine.get_grid_dimensions(), (11, 38));
+        assert_eq!(engine.population(), 36);
+
+        // every bundled pattern loads
+        for (name, _) in patterns::PATTERNS.iter() {
+            assert!(ConwayEngine::from_pattern(name, 768.0, 1024.0, 1.0, 0).is_ok());
         }
-        assert_eq!(engine.get_cell(1, 2), 1);
-        assert_eq!(engine.get_cell(2, 2), 1);
-        assert_eq!(engine.get_cell(3, 2), 1);
+        assert!(ConwayEngine::from_pattern("spaceship", 768.0, 1024.0, 1.0, 0).is_err());
     }
 
     #[test]
-    fn test_read_engine_file() {
-        let result = read_engine_file(&"test-files/test.txt".to_string());
-        assert!(result.is_ok());
-        let test_file_one: Vec<String> = vec![
-            "5, 5, 1, 20".to_string(),
-            "1,0,0,0,0".to_string(),
-            "0,1,0,0,0".to_string(),
-            "0,0,1,0,0".to_string(),
-            "0,0,0,1,0".to_string(),
-            "0,0,0,0,1".to_string(),
-        ];
-        assert_eq!(result.unwrap(), test_file_one);
+    fn test_summary() {
+        // glider keeps its 5 cells while it moves
+        let mut engine =
+            ConwayEngine::new(&"test-files/glider.rle".to_string(), 768.0, 1024.0, 0.0, 4);
+        while !engine.is_simulation_ended() {
+            engine.take_step();
+        }
+        assert_eq!(
+            engine.summary(),
+            "Ran 4 generations, final population 5, peak population 5, still changing"
+        );
 
-        let result = read_engine_file(&"test-files/bad_test.txt".to_string());
-        assert!(result.is_ok());
-        let test_file_bad: Vec<String> = vec![
-            "5, 5".to_string(),
-            "1,0,0,0,0".to_string(),
-            "0,1,0,a,0".to_string(),
-            "0,0,1,0,0".to_string(),
-            "0,0,0,1,c".to_string(),
-        ];
-        assert_eq!(result.unwrap(), test_file_bad);
+        // default blinker
+        let mut engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, 0.0, 3);
+        for _ in 0..3 {
+            engine.take_step();
+        }
+        assert_eq!(
+            engine.summary(),
+            "Ran 3 generations, final population 3, peak population 3, oscillating with period 2"
+        );
 
-        let result = read_engine_file(&"test-files/test2.txt".to_string());
-        assert!(result.is_ok());
-        let test_file_two: Vec<String> = vec![
-            "5, 5, 1, 20".to_string(),
-            "0,0,0,0,0".to_string(),
-            "0,0,1,0,0".to_string(),
-            "0,0,1,0,0".to_string(),
-            "0,0,1,0,0".to_string(),
-            "0,0,0,0,0".to_string(),
-        ];
-        assert_eq!(result.unwrap(), test_file_two);
+        // the peak is kept once the population drops, a lone block stabilizes
+        let mut grid = grid::Grid::new(6, 6, 0);
+        for (row, column) in [(1, 1), (1, 2), (2, 1), (2, 2), (4, 4)].iter() {
+            grid.set(*row, *column, 1).unwrap();
+        }
+        let mut engine = ConwayEngine::from_grid(grid, "block", 768.0, 1024.0, 0.0, 5);
+        while !engine.is_simulation_ended() {
+            engine.take_step();
+        }
+        assert_eq!(engine.get_peak_population(), 5);
+        assert_eq!(
+            engine.summary(),
+            "Ran 2 generations, final population 4, peak population 5, stabilized"
+        );
+    }
 
-        let result = read_engine_file(&"random_file_name.hello_world".to_string());
-        assert!(result.is_err());
+    #[test]
+    fn test_majority_species() {
+        assert_eq!(majority_species([2, 2, 1].iter().copied()), Some(2));
+        assert_eq!(
+            majority_species([0, 3, 0, 3, 1, 0].iter().copied()),
+            Some(3)
+        );
+        // ties go to the lowest id
+        assert_eq!(majority_species([3, 2, 5].iter().copied()), Some(2));
+        assert_eq!(majority_species([4, 1, 4, 1, 2].iter().copied()), Some(1));
+        assert_eq!(majority_species([0, 0].iter().copied()), None);
+        assert_eq!(majority_species(std::iter::empty()), None);
     }
 
     #[test]
-    fn test_parse_header() {
-        let data = parse_header("5, 5, 5, 5".to_string(), 0, 0);
-        assert!(data.is_ok());
-        let data = data.unwrap();
-        assert_eq!(data, (5, 5, 5, 5));
+    fn test_species() {
+        // vertical blinker of species 3, 2 and 5, the new side cells see all three
+        let mut grid = grid::Grid::new(5, 5, 0);
+        grid.set(1, 2, 3).unwrap();
+        grid.set(2, 2, 2).unwrap();
+        grid.set(3, 2, 5).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "species", 768.0, 1024.0, 0.0, 0);
+        engine.set_species(true);
+        engine.take_step();
+        // births tie, the lowest species wins, the center keeps its species instead of aging
+        assert_eq!(engine.get_cell(2, 1), 2);
+        assert_eq!(engine.get_cell(2, 2), 2);
+        assert_eq!(engine.get_cell(2, 3), 2);
+        assert_eq!(engine.population(), 3);
 
-        let data = parse_header("1, 2, 3, 4, 6".to_string(), 0, 0);
-        assert!(data.is_ok());
-        let data = data.unwrap();
-        assert_eq!(data, (1, 2, 3, 4));
+        // a majority beats a lower id
+        let mut grid = grid::Grid::new(5, 5, 0);
+        grid.set(1, 2, 4).unwrap();
+        grid.set(2, 2, 1).unwrap();
+        grid.set(3, 2, 4).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "species", 768.0, 1024.0, 0.0, 0);
+        engine.set_species(true);
+        engine.take_step();
+        assert_eq!(engine.get_cell(2, 1), 4);
+        assert_eq!(engine.get_cell(2, 2), 1);
 
-        let data = parse_header("5, 5, 20, 1".to_string(), 0, 0);
-        assert!(data.is_ok());
-        let data = data.unwrap();
-        assert_eq!(data, (5, 5, 20, 1));
+        // a single species runs the same as without species, the cells just don't age
+        let mut engine =
+            ConwayEngine::new(&"test-files/glider.rle".to_string(), 768.0, 1024.0, 0.0, 8);
+        let mut species_engine =
+            ConwayEngine::new(&"test-files/glider.rle".to_string(), 768.0, 1024.0, 0.0, 8);
+        species_engine.set_species(true);
+        for _ in 0..8 {
+            engine.take_step();
+            species_engine.take_step();
+            assert!(species_engine.grid.same_live_cells(&*engine.grid));
+            assert!(species_engine
+                .iter_live()
+                .all(|(_, _, species)| species == 1));
+        }
+    }
 
-        let data = parse_header("5, 5, 5".to_string(), 0, 0);
-        assert!(data.is_ok());
-        let data = data.unwrap();
-        assert_eq!(data, (5, 5, 5, 0));
+    #[test]
+    fn test_extinct() {
+        // a lone cell dies on the first step, the simulation ends without waiting to stabilize
+        let mut grid = grid::Grid::new(5, 5, 0);
+        grid.set(2, 2, 1).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "lone cell", 768.0, 1024.0, 0.0, 0);
+        engine.take_step();
+        assert!(engine.is_extinct());
+        assert!(!engine.is_stable());
+        assert!(engine.is_simulation_ended());
+        assert_eq!(engine.get_generation(), 1);
+        assert!(engine.get_title_string().ends_with(" -- extinct"));
+        assert_eq!(engine.classify_outcome(), Outcome::Stable);
+        assert!(engine.summary().ends_with("died out"));
 
-        let data = parse_header("5, 5".to_string(), 0, 0);
-        assert!(data.is_ok());
-        let data = data.unwrap();
-        assert_eq!(data, (5, 5, 0, 0));
+        // bringing a cell back clears it
+        engine.toggle_cell(1, 1).unwrap();
+        assert!(!engine.is_extinct());
+        assert!(!engine.is_simulation_ended());
+        assert!(engine.get_title_string().ends_with(" -- non-stop"));
 
-        let data = parse_header("5".to_string(), 0, 0);
-        assert!(data.is_err());
+        // stepping back too
+        engine.take_step();
+        assert!(engine.is_extinct());
+        engine.step_back().unwrap();
+        assert!(!engine.is_extinct());
+        assert!(!engine.is_simulation_ended());
     }
 
     #[test]
-    fn test_empty_generated_grid() {
-        let test_grid: Vec<String> = Vec::new();
-        let grid = generate_grid(5, 5, test_grid);
-        assert!(grid.is_ok());
-        let grid = grid.unwrap();
-        let test_grid_cells: Vec<usize> = vec![
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        ];
-        for row_index in 0..grid.size().0 {
-            for column_index in 0..grid.size().1 {
-                assert_eq!(
-                    grid.get(row_index, column_index).unwrap(),
-                    &test_grid_cells[row_index * grid.size().1 + column_index]
-                );
+    fn test_with_rule() {
+        struct AlwaysDies;
+        impl ruleset::Rule for AlwaysDies {
+            fn next_state(&self, _cell: usize, _neighbors: usize) -> usize {
+                0
+            }
+        }
+
+        // a block would live forever under B3/S23
+        let mut grid = grid::Grid::new(4, 4, 0);
+        for (row, column) in [(1, 1), (1, 2), (2, 1), (2, 2)] {
+            grid.set(row, column, 1).unwrap();
+        }
+        let mut engine = ConwayEngine::from_grid(grid, "block", 768.0, 1024.0, 0.0, 10)
+            .with_rule(Box::new(AlwaysDies));
+        engine.take_step();
+        assert_eq!(engine.population(), 0);
+        assert!(engine.is_extinct());
+
+        // the rule gets the cell value and the neighbor count, here a cell lives on with its neighbor count
+        struct NeighborCount;
+        impl ruleset::Rule for NeighborCount {
+            fn next_state(&self, cell: usize, neighbors: usize) -> usize {
+                if cell > 0 {
+                    neighbors
+                } else {
+                    0
+                }
             }
         }
+        let mut grid = grid::Grid::new(3, 3, 0);
+        grid.set(1, 0, 1).unwrap();
+        grid.set(1, 1, 1).unwrap();
+        grid.set(1, 2, 1).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "line", 768.0, 1024.0, 0.0, 10)
+            .with_rule(Box::new(NeighborCount));
+        engine.take_step();
+        assert_eq!(engine.get_cell(1, 0), 1);
+        assert_eq!(engine.get_cell(1, 1), 2);
+        assert_eq!(engine.get_cell(1, 2), 1);
+        assert_eq!(engine.get_cell(0, 1), 0);
     }
 
     #[test]
-    fn test_generate_grid() {
-        let test_grid: Vec<String> = vec![
-            "1,0,0,0,0".to_string(),
-            "0,1,0,0,0".to_string(),
-            "0,0,1,0,0".to_string(),
-            "0,0,0,1,0".to_string(),
-            "0,0,0,0,1".to_string(),
-        ];
-        let test_grid_cells: Vec<usize> = vec![
-            1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1,
-        ];
-        let grid = generate_grid(5, 5, test_grid);
-        assert!(grid.is_ok());
-        let grid = grid.unwrap();
-        for row_index in 0..grid.size().0 {
-            for column_index in 0..grid.size().1 {
-                assert_eq!(
-                    grid.get(row_index, column_index).unwrap(),
-                    &test_grid_cells[row_index * grid.size().1 + column_index]
-                );
-            }
+    fn test_step_callback() {
+        // a glider on a wrapped board keeps its population, the domino next to it dies out
+        let mut grid = grid::Grid::new(10, 10, 0);
+        for (row, column) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2), (7, 7), (7, 8)] {
+            grid.set(row, column, 1).unwrap();
         }
+        let mut engine = ConwayEngine::from_grid(grid, "callback", 768.0, 1024.0, 0.0, 0);
+        engine.set_boundary(BoundaryCondition::Toroidal);
+        let populations = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&populations);
+        engine.set_step_callback(Box::new(move |generation, grid| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((generation, grid.count_live()));
+        }));
+        assert_eq!(engine.step_many(5), 5);
+        assert_eq!(
+            *populations.lock().unwrap(),
+            vec![(1, 5), (2, 5), (3, 5), (4, 5), (5, 5)]
+        );
 
-        let test_grid: Vec<String> = vec![
-            "1,0,0,0,0".to_string(),
-            "0,1,0,0,0".to_string(),
-            "0,0,1,0,0".to_string(),
-            "0,0,0,1,0".to_string(),
-            "0,0,0,0,1".to_string(),
-        ];
-        let grid = generate_grid(4, 5, test_grid);
-        assert!(grid.is_err());
+        // skipped steps don't call it
+        let mut grid = grid::Grid::new(5, 5, 0);
+        grid.set(2, 2, 1).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "lone cell", 768.0, 1024.0, 0.0, 0);
+        let calls = Arc::new(Mutex::new(0));
+        let counted = Arc::clone(&calls);
+        engine.set_step_callback(Box::new(move |_, _| *counted.lock().unwrap() += 1));
+        for _ in 0..5 {
+            engine.take_step();
+        }
+        assert!(engine.is_simulation_ended());
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
 
-        let test_grid: Vec<String> = vec![
-            "1,0,0,0,0".to_string(),
-            "0,1,0,0,0".to_string(),
-            "0,0,1,0,0".to_string(),
-            "0,0,0,1,0".to_string(),
-            "0,0,0,0,1".to_string(),
-        ];
-        let grid = generate_grid(5, 4, test_grid);
-        assert!(grid.is_err());
+    #[test]
+    #[cfg(not(feature = "parallel"))]
+    fn test_step_callback_not_send() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
 
-        let test_grid: Vec<String> = vec![
-            "1,0,0,0,0".to_string(),
-            "0,1,0,0,0".to_string(),
-            "0,0,a,0,0".to_string(),
-            "0,0,0,1,0".to_string(),
-            "0,0,0,0,1".to_string(),
-        ];
-        let grid = generate_grid(5, 5, test_grid);
-        assert!(grid.is_err());
+        // without the parallel feature a callback can hold an Rc
+        let generations = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&generations);
+        let mut engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, 0.0, 0);
+        engine.set_step_callback(Box::new(move |generation, _| {
+            recorded.borrow_mut().push(generation)
+        }));
+        engine.step_many(3);
+        assert_eq!(*generations.borrow(), vec![1, 2, 3]);
     }
 
     #[test]
-    fn test_get_grid_spacing() {
-        let engine = ConwayEngine::new(&"test-files/test2.txt".to_string(), 768.0, 1024.0, 0, 0);
-        let (x_width, y_width) = engine.get_grid_spacing();
-        assert_eq!(x_width, 204.8);
-        assert_eq!(y_width, 153.6);
+    fn test_extinct_non_stop() {
+        // a diagonal of 3 cells leaves its middle cell, which dies on the second step
+        let mut grid = grid::Grid::new(5, 5, 0);
+        for index in 1..4 {
+            grid.set(index, index, 1).unwrap();
+        }
+        for backend in [Backend::Dense, Backend::Sparse].iter() {
+            let mut engine =
+                ConwayEngine::from_grid(grid.clone(), "diagonal", 768.0, 1024.0, 0.0, 0);
+            engine.set_backend(*backend);
+            engine.take_step();
+            assert_eq!(engine.population(), 1);
+            assert!(!engine.is_extinct());
+            assert!(engine.get_title_string().ends_with(" -- non-stop"));
 
-        let engine = ConwayEngine::new(&"test-files/test3.txt".to_string(), 768.0, 1024.0, 0, 0);
-        let (x_width, y_width) = engine.get_grid_spacing();
-        assert_eq!(x_width, 64.0);
-        assert_eq!(y_width, 51.2);
+            engine.take_step();
+            assert!(engine.is_extinct(), "{:?}", backend);
+            assert!(engine.is_simulation_ended());
+            // extinct wins over the non-stop suffix
+            assert!(engine.get_title_string().ends_with("gen 2 -- extinct"));
+
+            // the empty board isn't stepped anymore
+            engine.take_step();
+            assert_eq!(engine.get_generation(), 2);
+        }
     }
 
     #[test]
-    fn test_get_title_string() {
-        let engine = ConwayEngine::new(&"test-files/test2.txt".to_string(), 768.0, 1024.0, 0, 0);
-        assert_eq!(
-            engine.get_title_string(),
-            "Conway-rust v0.3.0: test-files/test2.txt"
-        );
+    fn test_classify_outcome() {
+        // a seeded empty board and a block both stabilize
+        let mut engine =
+            ConwayEngine::new_random(8, 8, 0.0, Some(42), 768.0, 1024.0, 0.0, 10, MAX_CELLS)
+                .unwrap();
+        engine.step_many(10);
+        assert_eq!(engine.classify_outcome(), Outcome::Stable);
 
-        let engine = ConwayEngine::new(
-            &"test-files/glider_test.txt".to_string(),
+        let mut grid = grid::Grid::new(4, 4, 0);
+        for (row, column) in [(1, 1), (1, 2), (2, 1), (2, 2)].iter() {
+            grid.set(*row, *column, 1).unwrap();
+        }
+        let mut engine = ConwayEngine::from_grid(grid, "block", 768.0, 1024.0, 0.0, 10);
+        assert_eq!(engine.step_many(10), 1);
+        assert_eq!(engine.classify_outcome(), Outcome::Stable);
+
+        // default blinker, ended early by the cycle detection
+        let mut engine = ConwayEngine::new(&"".to_string(), 768.0, 1024.0, 0.0, 10);
+        engine.set_cycle_detection(4);
+        assert_eq!(engine.step_many(10), 2);
+        assert_eq!(engine.classify_outcome(), Outcome::Oscillating(2));
+
+        // glider is still moving when the steps run out
+        let mut engine =
+            ConwayEngine::new(&"test-files/glider.rle".to_string(), 768.0, 1024.0, 0.0, 4);
+        engine.set_cycle_detection(4);
+        assert_eq!(engine.step_many(10), 4);
+        assert_eq!(engine.classify_outcome(), Outcome::StepLimit);
+    }
+
+    #[test]
+    fn test_detect_cycle() {
+        // pulsar, period 3
+        let mut engine = ConwayEngine::new(
+            &"test-files/pulsar_test.txt".to_string(),
             768.0,
             1024.0,
+            0.0,
             0,
+        );
+        engine.set_cycle_detection(10);
+        engine.take_step();
+        engine.take_step();
+        assert!(!engine.is_simulation_ended());
+        engine.take_step();
+        assert!(engine.is_simulation_ended());
+        assert_eq!(engine.get_generation(), 3);
+        assert!(engine
+            .get_title_string()
+            .ends_with(" -- oscillating (period 3)"));
+        assert!(engine.summary().ends_with("oscillating with period 3"));
+
+        // the window is too short for the period
+        let mut engine = ConwayEngine::new(
+            &"test-files/pulsar_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
             0,
         );
-        assert_eq!(
-            engine.get_title_string(),
-            "Conway-rust v0.3.0: test-files/glider_test.txt -- non-stop"
+        engine.set_cycle_detection(2);
+        for _ in 0..9 {
+            engine.take_step();
+        }
+        assert!(!engine.is_simulation_ended());
+
+        // periods longer than the history are still found from the hashes alone
+        let mut engine = ConwayEngine::new(
+            &"test-files/pulsar_test.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
         );
+        engine.set_history_capacity(0);
+        engine.set_cycle_detection(3);
+        for _ in 0..3 {
+            engine.take_step();
+        }
+        assert!(engine.is_simulation_ended());
 
+        // glider on a wrapped board doesn't repeat within the window
         let mut engine = ConwayEngine::new(
             &"test-files/glider_test.txt".to_string(),
             768.0,
             1024.0,
+            0.0,
             0,
-            1,
-        );
-        engine.take_step();
-        assert_eq!(
-            engine.get_title_string(),
-            "Conway-rust v0.3.0: test-files/glider_test.txt -- simulation ended"
         );
+        engine.set_wrap_edges(true);
+        engine.set_cycle_detection(3);
+        for _ in 0..12 {
+            engine.take_step();
+        }
+        assert!(!engine.is_simulation_ended());
+        assert_eq!(engine.population(), 5);
+    }
 
-        let engine = ConwayEngine::new(&"test-files/no-file.txt".to_string(), 768.0, 1024.0, 0, 1);
-        assert_eq!(
-            engine.get_title_string(),
-            "Conway-rust v0.3.0: No file found, using default pattern"
-        );
+    #[test]
+    fn test_boundary_conditions() {
+        // lone cell in the corner of an otherwise empty board
+        let step_corner = |boundary| {
+            let mut grid = grid::Grid::new(4, 4, 0);
+            grid.set(0, 0, 1).unwrap();
+            let mut engine = ConwayEngine::from_grid(grid, "corner", 768.0, 1024.0, 0.0, 0);
+            engine.set_boundary(boundary);
+            engine.take_step();
+            engine
+        };
 
-        let engine = ConwayEngine::new(&"test-files/no-file.txt".to_string(), 768.0, 1024.0, 0, 0);
-        assert_eq!(
-            engine.get_title_string(),
-            "Conway-rust v0.3.0: No file found, using default pattern -- non-stop"
-        );
+        // dead or wrapped edges, the lone cell dies
+        assert_eq!(step_corner(BoundaryCondition::Dead).population(), 0);
+        assert_eq!(step_corner(BoundaryCondition::Toroidal).population(), 0);
 
-        let mut engine =
-            ConwayEngine::new(&"test-files/no-file.txt".to_string(), 768.0, 1024.0, 0, 1);
-        engine.take_step();
-        assert_eq!(
-            engine.get_title_string(),
-            "Conway-rust v0.3.0: No file found, using default pattern -- simulation ended"
-        );
+        // living edges, the corner cell dies with 5 living neighbors off the grid
+        let engine = step_corner(BoundaryCondition::Alive);
+        assert_eq!(engine.get_cell(0, 0), 0);
+        // edge cells have 3 neighbors off the grid and are born,
+        // except the two next to the corner cell which have a fourth neighbor
+        assert_eq!(engine.get_cell(0, 2), 1);
+        assert_eq!(engine.get_cell(2, 0), 1);
+        assert_eq!(engine.get_cell(0, 1), 0);
+        assert_eq!(engine.get_cell(1, 0), 0);
+        // the other corners have 5 neighbors off the grid
+        assert_eq!(engine.get_cell(3, 3), 0);
+        assert_eq!(engine.population(), 6);
+    }
+
+    #[test]
+    fn test_transform() {
+        // 3 x 4 board, 768 x 1024 window
+        let mut grid = grid::Grid::new(3, 4, 0);
+        grid.set(0, 1, 1).unwrap();
+        let mut engine = ConwayEngine::from_grid(grid, "transform", 768.0, 1024.0, 1.0, 0);
+        assert_eq!(engine.get_grid_spacing(), (256.0, 256.0));
+
+        // dimensions swap and the spacing follows
+        engine.transform(Rotation::Quarter, None);
+        assert_eq!(engine.get_grid_dimensions(), (4, 3));
+        assert_eq!(engine.get_grid_spacing(), (1024.0 / 3.0, 192.0));
+        assert_eq!(engine.get_cell(1, 2), 1);
+
+        engine.transform(Rotation::None, Some(Flip::Horizontal));
+        assert_eq!(engine.get_cell(1, 0), 1);
+        engine.transform(Rotation::ThreeQuarters, Some(Flip::Vertical));
+        assert_eq!(engine.get_grid_dimensions(), (3, 4));
+        assert_eq!(engine.get_cell(0, 1), 1);
+        assert_eq!(engine.population(), 1);
+
+        // the sparse backend is kept
+        engine.set_backend(Backend::Sparse);
+        engine.transform(Rotation::Half, None);
+        assert_eq!(engine.get_cell(2, 2), 1);
+        engine.set_backend(Backend::Dense);
+        assert_eq!(engine.get_cell(2, 2), 1);
     }
 }
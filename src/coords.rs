@@ -0,0 +1,296 @@
+//! Conversions between grid cells and screen positions, kept free of the window so they can be tested.
+
+/// Smallest and largest zoom factors, 1.0 fits the whole board in the window
+const MIN_ZOOM: f32 = 0.5;
+const MAX_ZOOM: f32 = 20.0;
+
+/// Pan and zoom applied on top of the board layout.
+/// Kept separate from the model so the coordinate conversions stay pure functions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewTransform {
+    /// (X, Y) pixels the board is moved from its resting position
+    pub offset: (f32, f32),
+    /// zoom factor, clamped between MIN_ZOOM and MAX_ZOOM
+    pub scale: f32,
+}
+
+impl ViewTransform {
+    /// Transform that shows the whole board, no pan or zoom.
+    pub fn identity() -> ViewTransform {
+        ViewTransform {
+            offset: (0.0, 0.0),
+            scale: 1.0,
+        }
+    }
+
+    /// Move a position from the board layout onto the screen.
+    /// # Params
+    /// position: (f32, f32), (X, Y) position without any pan or zoom
+    /// # Returns
+    /// (f32, f32), (X, Y) screen position
+    pub fn apply(&self, position: (f32, f32)) -> (f32, f32) {
+        (
+            position.0 * self.scale + self.offset.0,
+            position.1 * self.scale + self.offset.1,
+        )
+    }
+
+    /// Move a screen position back onto the board layout, the inverse of ```apply```.
+    /// # Params
+    /// position: (f32, f32), (X, Y) screen position
+    /// # Returns
+    /// (f32, f32), (X, Y) position without any pan or zoom
+    pub fn invert(&self, position: (f32, f32)) -> (f32, f32) {
+        (
+            (position.0 - self.offset.0) / self.scale,
+            (position.1 - self.offset.1) / self.scale,
+        )
+    }
+
+    /// Zoom in (factor > 1) or out (factor < 1) keeping the board position under the anchor fixed.
+    /// The resulting zoom is clamped between MIN_ZOOM and MAX_ZOOM.
+    /// # Params
+    /// - factor: f32, amount to multiply the current zoom by
+    /// - anchor: (f32, f32), (X, Y) screen position to zoom around, usually the mouse cursor
+    pub fn zoom(&mut self, factor: f32, anchor: (f32, f32)) {
+        let board_anchor = self.invert(anchor);
+        self.scale = (self.scale * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.offset = (
+            anchor.0 - board_anchor.0 * self.scale,
+            anchor.1 - board_anchor.1 * self.scale,
+        );
+    }
+
+    /// Move the board on the screen.
+    /// # Params
+    /// - delta_x: f32, pixels to move right
+    /// - delta_y: f32, pixels to move up
+    pub fn pan(&mut self, delta_x: f32, delta_y: f32) {
+        self.offset.0 += delta_x;
+        self.offset.1 += delta_y;
+    }
+}
+
+/// Given the row and column index, calculate the center draw position of the cell.
+/// The window origin is in the center, row 0 is drawn at the top of the window.
+/// # Params
+/// - row_index: usize, row index in the grid
+/// - column_index: usize, column index in the grid
+/// - spacing: (f32, f32), (X, Y) size of a cell in pixels
+/// - window: (f32, f32), (width, height) of the board in pixels, centered on the origin (the whole window unless cells are square)
+/// - view: &ViewTransform, pan and zoom applied after the cell is laid out
+/// # Returns
+/// - (f32, f32), (X, Y) screen coordinates for the center of the given grid cell
+pub fn grid_to_screen(
+    row_index: usize,
+    column_index: usize,
+    spacing: (f32, f32),
+    window: (f32, f32),
+    view: &ViewTransform,
+) -> (f32, f32) {
+    let (x_width, y_width) = spacing;
+    let (lower_x, lower_y) = (-(window.0 / 2.0), -(window.1 / 2.0));
+
+    let coordinate_x = lower_x + (column_index as f32 * x_width + x_width / 2.0);
+    let coordinate_y = -lower_y - (row_index as f32 * y_width + y_width / 2.0);
+    view.apply((coordinate_x, coordinate_y))
+}
+
+/// Find the grid cell under a screen position, the inverse of ```grid_to_screen```.
+/// # Params
+/// - x: f32, X screen coordinate
+/// - y: f32, Y screen coordinate
+/// - spacing: (f32, f32), (X, Y) size of a cell in pixels
+/// - window: (f32, f32), (width, height) of the board in pixels, centered on the origin (the whole window unless cells are square)
+/// - dimensions: (usize, usize), (row, column) count of the grid
+/// - view: &ViewTransform, pan and zoom the screen position is undone from
+/// # Returns
+/// - Option<(usize, usize)>, (row, column) of the cell, None if the position is outside of the grid
+pub fn screen_to_grid(
+    x: f32,
+    y: f32,
+    spacing: (f32, f32),
+    window: (f32, f32),
+    dimensions: (usize, usize),
+    view: &ViewTransform,
+) -> Option<(usize, usize)> {
+    let (x, y) = view.invert((x, y));
+    let (x_width, y_width) = spacing;
+    let column = ((x + window.0 / 2.0) / x_width).floor();
+    let row = ((window.1 / 2.0 - y) / y_width).floor();
+    if row < 0.0 || column < 0.0 {
+        return None;
+    }
+
+    let (row, column) = (row as usize, column as usize);
+    if row >= dimensions.0 || column >= dimensions.1 {
+        return None;
+    }
+    Some((row, column))
+}
+
+/// Move the keyboard cursor by a number of rows and columns, stopping at the edges of the grid.
+/// A delta of (0, 0) pulls a cursor left outside of a grid that shrank back onto it.
+/// # Params
+/// - cursor: (usize, usize), (row, column) of the cursor
+/// - delta: (isize, isize), rows to move down and columns to move right, negative to move up or left
+/// - dimensions: (usize, usize), (row, column) count of the grid
+/// # Returns
+/// - (usize, usize), (row, column) of the moved cursor, always on the grid
+pub fn move_cursor(
+    cursor: (usize, usize),
+    delta: (isize, isize),
+    dimensions: (usize, usize),
+) -> (usize, usize) {
+    let clamp = |index: usize, delta: isize, count: usize| {
+        index
+            .saturating_add_signed(delta)
+            .min(count.saturating_sub(1))
+    };
+    (
+        clamp(cursor.0, delta.0, dimensions.0),
+        clamp(cursor.1, delta.1, dimensions.1),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_center() {
+        // 3x3 grid in a 300x300 window, the middle cell sits on the origin
+        let window = (300.0, 300.0);
+        let spacing = (100.0, 100.0);
+        let view = ViewTransform::identity();
+        assert_eq!(grid_to_screen(1, 1, spacing, window, &view), (0.0, 0.0));
+        assert_eq!(
+            screen_to_grid(0.0, 0.0, spacing, window, (3, 3), &view),
+            Some((1, 1))
+        );
+
+        // the corner cells are a cell away from the origin in each direction, row 0 is at the top
+        assert_eq!(
+            grid_to_screen(0, 0, spacing, window, &view),
+            (-100.0, 100.0)
+        );
+        assert_eq!(grid_to_screen(0, 2, spacing, window, &view), (100.0, 100.0));
+        assert_eq!(
+            grid_to_screen(2, 0, spacing, window, &view),
+            (-100.0, -100.0)
+        );
+        assert_eq!(
+            grid_to_screen(2, 2, spacing, window, &view),
+            (100.0, -100.0)
+        );
+    }
+
+    #[test]
+    fn test_screen_to_grid_round_trip() {
+        let window = (1024.0, 768.0);
+        let dimensions = (15, 16);
+        let spacing = (window.0 / 16.0, window.1 / 15.0);
+        let view = ViewTransform::identity();
+        for row_index in 0..dimensions.0 {
+            for column_index in 0..dimensions.1 {
+                let (x, y) = grid_to_screen(row_index, column_index, spacing, window, &view);
+                assert_eq!(
+                    screen_to_grid(x, y, spacing, window, dimensions, &view),
+                    Some((row_index, column_index))
+                );
+            }
+        }
+
+        // top left corner is the first cell, bottom right is the last
+        assert_eq!(
+            screen_to_grid(-511.0, 383.0, spacing, window, dimensions, &view),
+            Some((0, 0))
+        );
+        assert_eq!(
+            screen_to_grid(511.0, -383.0, spacing, window, dimensions, &view),
+            Some((14, 15))
+        );
+
+        // outside of the grid
+        assert_eq!(
+            screen_to_grid(-513.0, 0.0, spacing, window, dimensions, &view),
+            None
+        );
+        assert_eq!(
+            screen_to_grid(513.0, 0.0, spacing, window, dimensions, &view),
+            None
+        );
+        assert_eq!(
+            screen_to_grid(0.0, 385.0, spacing, window, dimensions, &view),
+            None
+        );
+        assert_eq!(
+            screen_to_grid(0.0, -385.0, spacing, window, dimensions, &view),
+            None
+        );
+    }
+
+    #[test]
+    fn test_screen_to_grid_with_view() {
+        let window = (1024.0, 768.0);
+        let dimensions = (15, 16);
+        let spacing = (window.0 / 16.0, window.1 / 15.0);
+        let mut view = ViewTransform::identity();
+        view.zoom(3.0, (100.0, -50.0));
+        view.pan(-40.0, 25.0);
+        for row_index in 0..dimensions.0 {
+            for column_index in 0..dimensions.1 {
+                let (x, y) = grid_to_screen(row_index, column_index, spacing, window, &view);
+                assert_eq!(
+                    screen_to_grid(x, y, spacing, window, dimensions, &view),
+                    Some((row_index, column_index))
+                );
+            }
+        }
+
+        // panning moves the top left cell with the view
+        let mut view = ViewTransform::identity();
+        view.pan(100.0, 0.0);
+        assert_eq!(
+            screen_to_grid(-511.0, 383.0, spacing, window, dimensions, &view),
+            None
+        );
+        assert_eq!(
+            screen_to_grid(-411.0, 383.0, spacing, window, dimensions, &view),
+            Some((0, 0))
+        );
+    }
+
+    #[test]
+    fn test_move_cursor() {
+        let dimensions = (4, 6);
+        assert_eq!(move_cursor((1, 1), (1, 0), dimensions), (2, 1));
+        assert_eq!(move_cursor((1, 1), (0, -1), dimensions), (1, 0));
+        // the edges stop the cursor instead of wrapping
+        assert_eq!(move_cursor((0, 0), (-1, 0), dimensions), (0, 0));
+        assert_eq!(move_cursor((0, 0), (0, -1), dimensions), (0, 0));
+        assert_eq!(move_cursor((3, 5), (1, 0), dimensions), (3, 5));
+        assert_eq!(move_cursor((3, 5), (0, 1), dimensions), (3, 5));
+        assert_eq!(move_cursor((2, 2), (-10, 10), dimensions), (0, 5));
+        // a cursor off a grid that shrank is pulled back to the last row and column
+        assert_eq!(move_cursor((9, 9), (0, 0), dimensions), (3, 5));
+        assert_eq!(move_cursor((9, 9), (-1, -1), dimensions), (3, 5));
+    }
+
+    #[test]
+    fn test_view_zoom() {
+        let mut view = ViewTransform::identity();
+
+        // the anchor stays over the same spot on the board
+        view.zoom(2.0, (100.0, 100.0));
+        assert_eq!(view.scale, 2.0);
+        assert_eq!(view.apply((100.0, 100.0)), (100.0, 100.0));
+        assert_eq!(view.invert(view.apply((-30.0, 12.0))), (-30.0, 12.0));
+
+        // zoom is clamped
+        view.zoom(1000.0, (0.0, 0.0));
+        assert_eq!(view.scale, MAX_ZOOM);
+        view.zoom(0.0001, (0.0, 0.0));
+        assert_eq!(view.scale, MIN_ZOOM);
+    }
+}
@@ -1,143 +1,1235 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Which surrounding cells count as neighbors.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Neighborhood {
+    /// All 8 surrounding cells, used by Conway's Game of Life
+    #[default]
+    Moore,
+    /// Only the 4 orthogonal cells, the diagonals are skipped
+    VonNeumann,
+}
+
+impl Neighborhood {
+    /// Number of cells in the neighborhood, the most living neighbors a cell can have.
+    /// # Returns
+    /// usize, 8 for Moore and 4 for von Neumann
+    pub fn max_neighbors(&self) -> usize {
+        match self {
+            Neighborhood::Moore => 8,
+            Neighborhood::VonNeumann => 4,
+        }
+    }
+
+    /// Number of cells in the neighborhood stretched to a radius, for Larger than Life rules.
+    /// Moore covers the square around the cell and von Neumann the diamond, the cell itself isn't counted.
+    /// # Params
+    /// radius: usize, distance the neighborhood reaches, 1 is the usual neighborhood
+    /// # Returns
+    /// usize, (2r + 1)^2 - 1 for Moore and 2r(r + 1) for von Neumann
+    pub fn max_neighbors_in_radius(&self, radius: usize) -> usize {
+        match self {
+            Neighborhood::Moore => (2 * radius + 1) * (2 * radius + 1) - 1,
+            Neighborhood::VonNeumann => 2 * radius * (radius + 1),
+        }
+    }
+}
+
+impl FromStr for Neighborhood {
+    type Err = &'static str;
+
+    /// Parse a neighborhood name, case insensitive: "moore" or "vonneumann" ("von-neumann" also works).
+    /// # Params
+    /// name: &str, name of the neighborhood
+    /// # Returns
+    /// Result<Neighborhood, &'static str>, the neighborhood or an error if the name is unknown
+    fn from_str(name: &str) -> Result<Neighborhood, &'static str> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "moore" => Ok(Neighborhood::Moore),
+            "vonneumann" | "von-neumann" => Ok(Neighborhood::VonNeumann),
+            _ => Err("Neighborhood must be moore or vonneumann"),
+        }
+    }
+}
+
+/// How cells on the edge of the grid see the neighbors that fall off the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BoundaryCondition {
+    /// Neighbors off the grid are dead
+    #[default]
+    Dead,
+    /// Neighbors off the grid are alive
+    Alive,
+    /// The edges wrap around, neighbors off one side come from the opposite side
+    Toroidal,
+}
+
+impl FromStr for BoundaryCondition {
+    type Err = &'static str;
+
+    /// Parse a boundary condition name, case insensitive: "dead", "alive" or "toroidal" ("wrap" also works).
+    /// # Params
+    /// name: &str, name of the boundary condition
+    /// # Returns
+    /// Result<BoundaryCondition, &'static str>, the boundary condition or an error if the name is unknown
+    fn from_str(name: &str) -> Result<BoundaryCondition, &'static str> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "dead" => Ok(BoundaryCondition::Dead),
+            "alive" => Ok(BoundaryCondition::Alive),
+            "toroidal" | "wrap" => Ok(BoundaryCondition::Toroidal),
+            _ => Err("Boundary must be dead, alive or toroidal"),
+        }
+    }
+}
+
+/// Clockwise rotation applied to a loaded pattern.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Rotation {
+    #[default]
+    None,
+    Quarter,
+    Half,
+    ThreeQuarters,
+}
+
+impl Rotation {
+    /// Number of clockwise 90 degree turns.
+    /// # Returns
+    /// usize, 0 to 3 turns
+    pub fn quarter_turns(&self) -> usize {
+        match self {
+            Rotation::None => 0,
+            Rotation::Quarter => 1,
+            Rotation::Half => 2,
+            Rotation::ThreeQuarters => 3,
+        }
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = &'static str;
+
+    /// Parse a rotation in degrees: "0", "90", "180" or "270".
+    /// # Params
+    /// degrees: &str, clockwise rotation in degrees
+    /// # Returns
+    /// Result<Rotation, &'static str>, the rotation or an error if it isn't a multiple of 90 below 360
+    fn from_str(degrees: &str) -> Result<Rotation, &'static str> {
+        match degrees.trim() {
+            "0" => Ok(Rotation::None),
+            "90" => Ok(Rotation::Quarter),
+            "180" => Ok(Rotation::Half),
+            "270" => Ok(Rotation::ThreeQuarters),
+            _ => Err("Rotation must be 0, 90, 180 or 270"),
+        }
+    }
+}
+
+/// Mirror applied to a loaded pattern.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flip {
+    /// Mirror left to right, the columns are reversed
+    Horizontal,
+    /// Mirror top to bottom, the rows are reversed
+    Vertical,
+}
+
+impl FromStr for Flip {
+    type Err = &'static str;
+
+    /// Parse a flip direction, case insensitive: "h" or "v" ("horizontal" and "vertical" also work).
+    /// # Params
+    /// direction: &str, direction of the mirror
+    /// # Returns
+    /// Result<Flip, &'static str>, the flip or an error if the direction is unknown
+    fn from_str(direction: &str) -> Result<Flip, &'static str> {
+        match direction.trim().to_ascii_lowercase().as_str() {
+            "h" | "horizontal" => Ok(Flip::Horizontal),
+            "v" | "vertical" => Ok(Flip::Vertical),
+            _ => Err("Flip must be h or v"),
+        }
+    }
+}
+
+/// Which storage the engine keeps its board in.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Backend {
+    /// Every cell is stored, fastest for busy boards
+    #[default]
+    Dense,
+    /// Only living cells are stored, best for large mostly empty boards
+    Sparse,
+}
+
+impl FromStr for Backend {
+    type Err = &'static str;
+
+    /// Parse a backend name, case insensitive: "dense" or "sparse".
+    /// # Params
+    /// name: &str, name of the backend
+    /// # Returns
+    /// Result<Backend, &'static str>, the backend or an error if the name is unknown
+    fn from_str(name: &str) -> Result<Backend, &'static str> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "dense" => Ok(Backend::Dense),
+            "sparse" => Ok(Backend::Sparse),
+            _ => Err("Backend must be dense or sparse"),
+        }
+    }
+}
+
+/// Storage for the engine's board.  Cells hold the age of a living cell, 0 is a dead cell.
+/// Implemented by the dense ```Grid<usize>``` and the sparse ```SparseGrid```, so the engine can use either.
+pub trait GridBackend: Send + Sync {
+    /// Get the row and column sizes of the grid.
+    /// # Returns
+    /// (usize, usize), Tuple representing (row size, column size)
+    fn size(&self) -> (usize, usize);
+
+    /// Get the value of a cell.
+    /// # Params
+    /// - row: usize, row of the cell
+    /// - column: usize, column of the cell
+    /// # Returns
+    /// Result<usize, &'static str>, the cell value or an error if the cell is outside of the grid
+    fn get(&self, row: usize, column: usize) -> Result<usize, &'static str>;
+
+    /// Set the value of a cell.
+    /// # Params
+    /// - row: usize, row of the cell
+    /// - column: usize, column of the cell
+    /// - data: usize, new value of the cell
+    /// # Returns
+    /// Result<(), &'static str>, empty OK if successful, an error if the cell is outside of the grid
+    fn set(&mut self, row: usize, column: usize, data: usize) -> Result<(), &'static str>;
+
+    /// Iterate over the living cells (greater than 0), the order depends on the backend.
+    /// # Returns
+    /// Box<dyn Iterator<Item = (usize, usize, usize)>>, (row, column, value) for each living cell
+    fn iter_live(&self) -> Box<dyn Iterator<Item = (usize, usize, usize)> + '_>;
+
+    /// Visit the living cells in the same order as ```iter_live```, stopping at the first cell the check fails for.
+    /// Backends override it to skip boxing the iterator, so stepping doesn't allocate.
+    /// # Params
+    /// check: &mut dyn FnMut(usize, usize, usize) -> bool, called with (row, column, value) of each living cell
+    /// # Returns
+    /// bool, true if the check passed for every living cell
+    fn all_live(&self, check: &mut dyn FnMut(usize, usize, usize) -> bool) -> bool {
+        self.iter_live()
+            .all(|(row, column, cell)| check(row, column, cell))
+    }
+
+    /// Create an empty grid of the same backend.
+    /// # Params
+    /// - row_size: usize, row size of the new grid
+    /// - column_size: usize, column size of the new grid
+    /// # Returns
+    /// Box<dyn GridBackend>, grid with every cell dead
+    fn new_empty(&self, row_size: usize, column_size: usize) -> Box<dyn GridBackend>;
+
+    /// Copy the grid.
+    /// # Returns
+    /// Box<dyn GridBackend>, identical grid of the same backend
+    fn clone_backend(&self) -> Box<dyn GridBackend>;
+
+    /// Change the size of the grid and kill every cell, keeping the storage so a grid can be reused without allocating.
+    /// # Params
+    /// - row_size: usize, new row size
+    /// - column_size: usize, new column size
+    fn reset(&mut self, row_size: usize, column_size: usize);
+
+    /// Cells that could be alive after the next step, every cell unless the backend can narrow it down.
+    /// The list is written into a buffer so the engine can keep reusing it.
+    /// # Params
+    /// - _boundary: BoundaryCondition, how the edge cells see neighbors off the grid
+    /// - candidates: &mut Vec<(usize, usize)>, cleared and filled with the (row, column) of each cell to compute
+    fn step_candidates(&self, _boundary: BoundaryCondition, candidates: &mut Vec<(usize, usize)>) {
+        all_cells(self.size(), candidates);
+    }
+
+    /// Count the living cells within a radius of a cell, for Larger than Life rules.
+    /// With a radius of 1 this is the same count as ```count_neighbors```.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// - radius: usize, distance the neighborhood reaches, see ```Neighborhood::max_neighbors_in_radius```
+    /// - kind: Neighborhood, square (Moore) or diamond (von Neumann) around the cell
+    /// - boundary: BoundaryCondition, how neighbors off the grid are counted
+    /// # Return
+    /// - Result<usize, &'static str>, either the number of living cells around the coordinates, or an error string.
+    fn count_neighbors_in_radius(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        radius: usize,
+        kind: Neighborhood,
+        boundary: BoundaryCondition,
+    ) -> Result<usize, &'static str> {
+        let (row_size, column_size) = self.size();
+        if row_index >= row_size {
+            return Err("Center row is out of bounds");
+        } else if column_index >= column_size {
+            return Err("Center column is out of bounds");
+        }
+
+        let radius = radius as isize;
+        let mut number_of_neighbors = 0;
+        for row_offset in -radius..=radius {
+            for column_offset in -radius..=radius {
+                // skip center coordinate, and the corners outside of the diamond for von Neumann
+                if row_offset == 0 && column_offset == 0 {
+                    continue;
+                }
+                if kind == Neighborhood::VonNeumann
+                    && row_offset.abs() + column_offset.abs() > radius
+                {
+                    continue;
+                }
+                let neighbor_row = row_index as isize + row_offset;
+                let neighbor_column = column_index as isize + column_offset;
+                let on_grid = (0..row_size as isize).contains(&neighbor_row)
+                    && (0..column_size as isize).contains(&neighbor_column);
+                let alive = if on_grid {
+                    self.get(neighbor_row as usize, neighbor_column as usize)? > 0
+                } else {
+                    match boundary {
+                        BoundaryCondition::Dead => false,
+                        BoundaryCondition::Alive => true,
+                        // a radius wider than the grid wraps around more than once
+                        BoundaryCondition::Toroidal => {
+                            self.get(
+                                neighbor_row.rem_euclid(row_size as isize) as usize,
+                                neighbor_column.rem_euclid(column_size as isize) as usize,
+                            )? > 0
+                        }
+                    }
+                };
+                if alive {
+                    number_of_neighbors += 1;
+                }
+            }
+        }
+        Ok(number_of_neighbors)
+    }
+
+    /// Count the living neighbors of a cell.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// - kind: Neighborhood, which surrounding cells are counted
+    /// - boundary: BoundaryCondition, how neighbors off the grid are counted
+    /// # Return
+    /// - Result<usize, &'static str>, either the number of living cells surrounding the coordinates, or an error string.
+    fn count_neighbors(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        kind: Neighborhood,
+        boundary: BoundaryCondition,
+    ) -> Result<usize, &'static str> {
+        let (row_size, column_size) = self.size();
+        if row_index >= row_size {
+            return Err("Center row is out of bounds");
+        } else if column_index >= column_size {
+            return Err("Center column is out of bounds");
+        }
+
+        let wrap_edges = boundary == BoundaryCondition::Toroidal;
+        let mut number_of_neighbors = 0;
+        for (neighbor_row_index, neighbor_column_index) in
+            neighbor_coordinates(row_index, column_index, self.size(), kind, wrap_edges)
+        {
+            if self.get(neighbor_row_index, neighbor_column_index)? > 0 {
+                number_of_neighbors += 1;
+            }
+        }
+        if boundary == BoundaryCondition::Alive {
+            number_of_neighbors += off_grid_neighbors(row_index, column_index, self.size(), kind);
+        }
+        Ok(number_of_neighbors)
+    }
+
+    /// Count the living cells in the Moore neighborhood, cells off the grid are treated as dead.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// # Return
+    /// - Result<usize, &'static str>, either the number of living cells surrounding the coordinates, or an error string.
+    fn get_number_of_neighbors(
+        &self,
+        row_index: usize,
+        column_index: usize,
+    ) -> Result<usize, &'static str> {
+        self.count_neighbors(
+            row_index,
+            column_index,
+            Neighborhood::Moore,
+            BoundaryCondition::Dead,
+        )
+    }
+
+    /// Hash the grid size and the positions of the living cells with 64 bit FNV-1a.
+    /// The values of the living cells (their ages) are left out, so grids with the same living cells hash the same.
+    /// Different grids can collide, confirm a match with ```same_live_cells```.
+    /// # Returns
+    /// u64, hash of the living cells
+    fn hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        let (row_size, column_size) = self.size();
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut add = |value: usize| {
+            for byte in (value as u64).to_le_bytes().iter() {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+        add(row_size);
+        add(column_size);
+        // both backends iterate in row-major order, so the hash doesn't depend on the backend
+        self.all_live(&mut |row, column, _| {
+            add(row);
+            add(column);
+            true
+        });
+        hash
+    }
+
+    /// Count the living cells.
+    /// # Returns
+    /// usize, number of cells greater than 0
+    fn population(&self) -> usize {
+        self.iter_live().count()
+    }
+
+    /// Check if every cell is dead.
+    /// # Returns
+    /// bool, true if no cell is greater than 0
+    fn is_empty(&self) -> bool {
+        self.iter_live().next().is_none()
+    }
+
+    /// Compare the living cells of two grids, the values of the living cells are ignored.
+    /// # Params
+    /// other: &dyn GridBackend, grid to compare against, can be a different backend
+    /// # Returns
+    /// bool, true if the grids have the same dimensions and the same cells are alive
+    fn same_live_cells(&self, other: &dyn GridBackend) -> bool {
+        self.size() == other.size()
+            && self.population() == other.population()
+            && self.all_live(
+                &mut |row, column, _| matches!(other.get(row, column), Ok(cell) if cell > 0),
+            )
+    }
+
+    /// Find the cells that changed value since a previous grid, see ```Grid::diff```.
+    /// Only the living cells of both grids are visited, so sparse grids don't scan their dead cells.
+    /// # Params
+    /// previous: &dyn GridBackend, earlier state of the grid, can be a different backend
+    /// # Returns
+    /// Result<Vec<(usize, usize, usize)>, &'static str>, (row, column, new value) of each changed cell in row-major order,
+    /// err if the dimensions differ
+    fn diff(&self, previous: &dyn GridBackend) -> Result<Vec<(usize, usize, usize)>, &'static str> {
+        if self.size() != previous.size() {
+            return Err("Grids must have the same dimensions to diff");
+        }
+        // cells that are alive now and weren't, or aged
+        let mut changes: Vec<(usize, usize, usize)> = self
+            .iter_live()
+            .filter(|(row, column, cell)| previous.get(*row, *column) != Ok(*cell))
+            .collect();
+        // cells that died
+        changes.extend(
+            previous
+                .iter_live()
+                .filter(|(row, column, _)| self.get(*row, *column) == Ok(0))
+                .map(|(row, column, _)| (row, column, 0)),
+        );
+        changes.sort_unstable_by_key(|(row, column, _)| (*row, *column));
+        Ok(changes)
+    }
+
+    /// Compare two grids, they are equal if the dimensions and every cell match.
+    /// # Params
+    /// other: &dyn GridBackend, grid to compare against, can be a different backend
+    /// # Returns
+    /// bool, true if the grids are identical
+    fn equals(&self, other: &dyn GridBackend) -> bool {
+        self.size() == other.size()
+            && self.population() == other.population()
+            && self
+                .iter_live()
+                .all(|(row, column, cell)| other.get(row, column) == Ok(cell))
+    }
+
+    /// Create a grid of a new size with the contents copied into its center, see ```Grid::resize_centered```.
+    /// # Params
+    /// - new_row_size: usize, row size of the new grid
+    /// - new_column_size: usize, column size of the new grid
+    /// # Returns
+    /// Box<dyn GridBackend>, resized grid of the same backend
+    fn resize_centered(&self, new_row_size: usize, new_column_size: usize) -> Box<dyn GridBackend> {
+        let (row_size, column_size) = self.size();
+        let row_offset = (new_row_size as isize - row_size as isize) / 2;
+        let column_offset = (new_column_size as isize - column_size as isize) / 2;
+        let mut grid = self.new_empty(new_row_size, new_column_size);
+        for (row, column, cell) in self.iter_live() {
+            let new_row = row as isize + row_offset;
+            let new_column = column as isize + column_offset;
+            if new_row >= 0 && new_column >= 0 {
+                // cells cropped off the new grid are dropped
+                let _ = grid.set(new_row as usize, new_column as usize, cell);
+            }
+        }
+        grid
+    }
+}
+
+// (row, column) offsets of the 8 surrounding cells in row-major order, 0, 1, 2 map to -1, 0, +1
+static NEIGHBOR_OFFSETS: [(usize, usize); 8] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (1, 0),
+    (1, 2),
+    (2, 0),
+    (2, 1),
+    (2, 2),
+];
+
+/// Coordinates of the neighbors of a cell.
+/// The coordinates are made as they're iterated, so counting neighbors in a step doesn't allocate.
+/// # Params
+/// - row_index: usize, row coordinate of center cell
+/// - column_index: usize, column coordinate of center cell
+/// - size: (usize, usize), (row, column) size of the grid
+/// - kind: Neighborhood, which surrounding cells are included
+/// - wrap_edges: bool, true to wrap around the edges, false to skip cells off the grid
+/// # Returns
+/// impl Iterator<Item = (usize, usize)>, (row, column) of each neighbor in row-major order,
+/// can repeat on grids smaller than 3 cells with wrapped edges
+pub(crate) fn neighbor_coordinates(
+    row_index: usize,
+    column_index: usize,
+    size: (usize, usize),
+    kind: Neighborhood,
+    wrap_edges: bool,
+) -> impl Iterator<Item = (usize, usize)> {
+    let (row_size, column_size) = size;
+    NEIGHBOR_OFFSETS
+        .iter()
+        // the von Neumann neighborhood skips the diagonals
+        .filter(move |(row_offset, column_offset)| {
+            kind == Neighborhood::Moore || *row_offset == 1 || *column_offset == 1
+        })
+        .filter_map(move |&(row_offset, column_offset)| {
+            if wrap_edges {
+                return Some((
+                    (row_index + row_size + row_offset - 1) % row_size,
+                    (column_index + column_size + column_offset - 1) % column_size,
+                ));
+            }
+            let (neighbor_row, neighbor_column) =
+                (row_index + row_offset, column_index + column_offset);
+            if neighbor_row == 0
+                || neighbor_column == 0
+                || neighbor_row > row_size
+                || neighbor_column > column_size
+            {
+                return None;
+            }
+            Some((neighbor_row - 1, neighbor_column - 1))
+        })
+}
+
+/// Fill a list with every cell of a grid, in row-major order.
+/// # Params
+/// - size: (usize, usize), (row, column) size of the grid
+/// - cells: &mut Vec<(usize, usize)>, cleared and filled with the (row, column) of each cell
+pub(crate) fn all_cells(size: (usize, usize), cells: &mut Vec<(usize, usize)>) {
+    let (row_size, column_size) = size;
+    cells.clear();
+    cells.extend((0..row_size).flat_map(|row| (0..column_size).map(move |column| (row, column))));
+}
+
+/// Count the neighbors of a cell that fall off the grid.
+/// # Params
+/// - row_index: usize, row coordinate of center cell
+/// - column_index: usize, column coordinate of center cell
+/// - size: (usize, usize), (row, column) size of the grid
+/// - kind: Neighborhood, which surrounding cells are included
+/// # Returns
+/// usize, number of neighbors outside of the grid, 0 for cells away from the edges
+pub(crate) fn off_grid_neighbors(
+    row_index: usize,
+    column_index: usize,
+    size: (usize, usize),
+    kind: Neighborhood,
+) -> usize {
+    kind.max_neighbors() - neighbor_coordinates(row_index, column_index, size, kind, false).count()
+}
+
 /// Handles a logical grid layout, each cell contains a ganeric type of data
+pub struct Grid<T> {
+    row_size: usize,
+    column_size: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Copy> Clone for Grid<T> {
+    /// Simple cloning function.  Produces a brand new Grid that is identical to self.
+    /// # Returns
+    /// Grid<T>, identical Grid to self
+    fn clone(&self) -> Grid<T> {
+        Grid {
+            row_size: self.row_size,
+            column_size: self.column_size,
+            cells: self.cells.to_vec(),
+        }
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    /// Creates a new Grid object.
+    /// # Params
+    /// - row_size, usize: size of the row
+    /// - column_size, usize: size of the columns
+    /// - init_data, T:  Initial state of the cells to be filled in
+    /// # Returns
+    /// - Grid<T>, a grid of the given dimensions with cells filled tihe init_data
+    pub fn new(row_size: usize, column_size: usize, init_data: T) -> Grid<T> {
+        // exactly one cell per coordinate, the capacity of a Vec can be larger than asked for
+        Grid {
+            row_size,
+            column_size,
+            cells: vec![init_data; row_size * column_size],
+        }
+    }
+
+    /// Wrap cells already laid out in row-major order in a grid, without copying them.
+    /// # Params
+    /// - row_size: usize, size of the rows
+    /// - column_size: usize, size of the columns
+    /// - cells: Vec<T>, every cell of the first row, then the second row and so on
+    /// # Returns
+    /// Result<Grid<T>, &'static str>, the grid or an error if there isn't exactly one cell per coordinate
+    pub fn from_vec(
+        row_size: usize,
+        column_size: usize,
+        cells: Vec<T>,
+    ) -> Result<Grid<T>, &'static str> {
+        if row_size.checked_mul(column_size) != Some(cells.len()) {
+            return Err("Number of cells must be the row size times the column size");
+        }
+        Ok(Grid {
+            row_size,
+            column_size,
+            cells,
+        })
+    }
+
+    /// Get the row and column sizes of the grid.
+    /// # Returns
+    /// (usize, usize), Tuple representing (row size, column size)
+    pub fn size(&self) -> (usize, usize) {
+        (self.row_size, self.column_size)
+    }
+
+    /// Get the number of cells in the grid, alive or dead (```is_empty``` checks for living cells instead).
+    /// # Returns
+    /// usize, row size * column size
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Create a grid of a new size with the contents of self copied into its center.
+    /// New cells are filled with the default value of T, if the new grid is smaller the edges are cropped.
+    /// # Params
+    /// - new_row_size: usize, row size of the new grid
+    /// - new_column_size: usize, column size of the new grid
+    /// # Returns
+    /// Grid<T>, resized grid
+    pub fn resize_centered(&self, new_row_size: usize, new_column_size: usize) -> Grid<T>
+    where
+        T: Default,
+    {
+        let mut grid = Grid::new(new_row_size, new_column_size, T::default());
+        // offsets can be negative when shrinking, cells that land outside the new grid are dropped
+        let row_offset = (new_row_size as isize - self.row_size as isize) / 2;
+        let column_offset = (new_column_size as isize - self.column_size as isize) / 2;
+        for row_index in 0..self.row_size {
+            let new_row_index = row_index as isize + row_offset;
+            if new_row_index < 0 || new_row_index >= new_row_size as isize {
+                continue;
+            }
+            for column_index in 0..self.column_size {
+                let new_column_index = column_index as isize + column_offset;
+                if new_column_index < 0 || new_column_index >= new_column_size as isize {
+                    continue;
+                }
+                grid.cells[new_row_index as usize * new_column_size + new_column_index as usize] =
+                    self.cells[row_index * self.column_size + column_index];
+            }
+        }
+        grid
+    }
+
+    /// Copy every cell of a smaller grid into self, with its top left corner at the given offset.
+    /// Dead cells of the pattern are copied too, so the pattern's whole rectangle is replaced.
+    /// # Params
+    /// - top_row: usize, row in self where the first row of the pattern goes
+    /// - left_column: usize, column in self where the first column of the pattern goes
+    /// - pattern: &Grid<T>, grid to copy
+    /// # Returns
+    /// Result<(), &'static str>, empty OK if successful, err if the pattern doesn't fit (self is left unchanged)
+    pub fn stamp(
+        &mut self,
+        top_row: usize,
+        left_column: usize,
+        pattern: &Grid<T>,
+    ) -> Result<(), &'static str> {
+        let (pattern_rows, pattern_columns) = pattern.size();
+        // checked, an offset near usize::MAX would wrap around instead of overflowing the grid
+        if top_row
+            .checked_add(pattern_rows)
+            .is_none_or(|bottom| bottom > self.row_size)
+        {
+            return Err("Stamped pattern overflows the grid rows");
+        }
+        if left_column
+            .checked_add(pattern_columns)
+            .is_none_or(|right| right > self.column_size)
+        {
+            return Err("Stamped pattern overflows the grid columns");
+        }
+        for (row_index, column_index, cell) in pattern.iter_cells() {
+            self.cells[(top_row + row_index) * self.column_size + left_column + column_index] =
+                *cell;
+        }
+        Ok(())
+    }
+
+    /// Rotate the grid 90 degrees clockwise, the row and column sizes are swapped.
+    /// # Returns
+    /// Grid<T>, rotated grid, the top row of self becomes the rightmost column
+    pub fn rotate_90(&self) -> Grid<T> {
+        let mut cells = Vec::with_capacity(self.row_size * self.column_size);
+        for new_row_index in 0..self.column_size {
+            for new_column_index in 0..self.row_size {
+                cells.push(
+                    self.cells
+                        [(self.row_size - 1 - new_column_index) * self.column_size + new_row_index],
+                );
+            }
+        }
+        Grid {
+            row_size: self.column_size,
+            column_size: self.row_size,
+            cells,
+        }
+    }
+
+    /// Mirror the grid left to right.
+    /// # Returns
+    /// Grid<T>, grid with the columns in reverse order
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let mut cells = Vec::with_capacity(self.row_size * self.column_size);
+        for row_index in 0..self.row_size {
+            for column_index in (0..self.column_size).rev() {
+                cells.push(self.cells[row_index * self.column_size + column_index]);
+            }
+        }
+        Grid {
+            row_size: self.row_size,
+            column_size: self.column_size,
+            cells,
+        }
+    }
+
+    /// Mirror the grid top to bottom.
+    /// # Returns
+    /// Grid<T>, grid with the rows in reverse order
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let mut cells = Vec::with_capacity(self.row_size * self.column_size);
+        for row_index in (0..self.row_size).rev() {
+            let row_start = row_index * self.column_size;
+            cells.extend_from_slice(&self.cells[row_start..row_start + self.column_size]);
+        }
+        Grid {
+            row_size: self.row_size,
+            column_size: self.column_size,
+            cells,
+        }
+    }
+
+    /// Iterate over every cell in row-major order.
+    /// # Returns
+    /// impl Iterator<Item = (usize, usize, &T)>, (row, column, cell) for each cell
+    pub fn iter_cells(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        let column_size = self.column_size;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| (index / column_size, index % column_size, cell))
+    }
+
+    /// Gets a specified element in the grid.  Will check row and column input ranges.
+    /// # Params
+    /// row, usize:  0 based row of the desired cell
+    /// column, usize, 0 based column of the desired cell
+    /// # Returns
+    /// Result<T, &'static str>, returns a result with either the cell data or an error
+    pub fn get(&self, row: usize, column: usize) -> Result<&T, &'static str> {
+        // check inputs
+        if row >= self.row_size {
+            return Err("Get row is out of bounds");
+        }
+        if column >= self.column_size {
+            return Err("Get column is out of bounds");
+        }
+        let data = self
+            .cells
+            .get(row * self.column_size + column)
+            .expect("Failed to get data from grid");
+        Ok(data)
+    }
+
+    /// Gets a mutable reference to a specified element in the grid, for changing a cell in place.
+    /// Checks the row and column like ```get```.
+    /// # Params
+    /// row, usize:  0 based row of the desired cell
+    /// column, usize, 0 based column of the desired cell
+    /// # Returns
+    /// Result<&mut T, &'static str>, returns a result with either the cell data or an error
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Result<&mut T, &'static str> {
+        // check inputs
+        if row >= self.row_size {
+            return Err("Get row is out of bounds");
+        }
+        if column >= self.column_size {
+            return Err("Get column is out of bounds");
+        }
+        let data = self
+            .cells
+            .get_mut(row * self.column_size + column)
+            .expect("Failed to get data from grid");
+        Ok(data)
+    }
+
+    /// Sets a cell with the given data.
+    pub fn set(&mut self, row: usize, column: usize, data: T) -> Result<(), &'static str> {
+        // check inputs
+        if row >= self.row_size {
+            return Err("Given row is out of grid bounds");
+        }
+        if column >= self.column_size {
+            return Err("Given column is out of grid bounds");
+        }
+        self.cells[row * self.column_size + column] = data;
+        Ok(())
+    }
+
+    /// Build a grid of the same size with every cell transformed, e.g. ```grid.map(|cell| *cell > 0)``` for an alive mask.
+    /// The output type only has to be Copy, like a grid of bools or colors.
+    /// # Params
+    /// f: F, function from a cell of self to the cell of the new grid
+    /// # Returns
+    /// Grid<U>, grid with the same dimensions holding f of each cell
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            row_size: self.row_size,
+            column_size: self.column_size,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Copy + std::cmp::PartialOrd<usize>> Grid<T> {
+    /// Iterate over the living cells (greater than 0) in row-major order.
+    /// # Returns
+    /// impl Iterator<Item = (usize, usize, &T)>, (row, column, cell) for each living cell
+    pub fn iter_live(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.iter_cells().filter(|(_, _, cell)| **cell > 0)
+    }
+
+    /// Count the living cells (greater than 0).
+    /// # Returns
+    /// usize, number of living cells
+    pub fn count_live(&self) -> usize {
+        self.iter_live().count()
+    }
+
+    /// Check if every cell is dead, stops at the first living cell.
+    /// # Returns
+    /// bool, true if no cell is greater than 0
+    pub fn is_empty(&self) -> bool {
+        self.iter_live().next().is_none()
+    }
+
+    /// Find the smallest rectangle holding every living cell (greater than 0).
+    /// # Returns
+    /// Option<(usize, usize, usize, usize)>, (min row, min column, max row, max column) with the max inclusive,
+    /// None if every cell is dead
+    pub fn live_bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        self.iter_live().fold(None, |bounds, (row, column, _)| {
+            Some(match bounds {
+                None => (row, column, row, column),
+                Some((min_row, min_column, max_row, max_column)) => (
+                    min_row.min(row),
+                    min_column.min(column),
+                    max_row.max(row),
+                    max_column.max(column),
+                ),
+            })
+        })
+    }
+
+    /// Compare two grids, they are equal if the dimensions and every cell match.
+    /// # Params
+    /// other: &Grid<T>, grid to compare against
+    /// # Returns
+    /// bool, true if the grids are identical
+    pub fn equals(&self, other: &Grid<T>) -> bool
+    where
+        T: PartialEq,
+    {
+        self.size() == other.size() && self.cells == other.cells
+    }
+
+    /// Compare the living cells of two grids, the values of the living cells are ignored.
+    /// # Params
+    /// other: &Grid<T>, grid to compare against
+    /// # Returns
+    /// bool, true if the grids have the same dimensions and the same cells are alive (greater than 0)
+    pub fn same_live_cells(&self, other: &Grid<T>) -> bool {
+        self.size() == other.size()
+            && self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .all(|(cell, other_cell)| (*cell > 0) == (*other_cell > 0))
+    }
+
+    /// Find the cells that changed value since a previous grid, used to only redraw what changed.
+    /// # Params
+    /// previous: &Grid<T>, earlier state of the grid, must have the same dimensions
+    /// # Returns
+    /// Result<Vec<(usize, usize, T)>, &'static str>, (row, column, new value) of each changed cell in row-major order,
+    /// err if the dimensions differ
+    pub fn diff(&self, previous: &Grid<T>) -> Result<Vec<(usize, usize, T)>, &'static str>
+    where
+        T: PartialEq,
+    {
+        if self.size() != previous.size() {
+            return Err("Grids must have the same dimensions to diff");
+        }
+        Ok(self
+            .iter_cells()
+            .zip(previous.iter_cells())
+            .filter(|((_, _, cell), (_, _, previous_cell))| cell != previous_cell)
+            .map(|((row, column, cell), _)| (row, column, *cell))
+            .collect())
+    }
+
+    /// Overwrite every cell with the given data, the dimensions don't change.
+    /// # Params
+    /// value: T, new value of every cell
+    pub fn fill(&mut self, value: T) {
+        for cell in self.cells.iter_mut() {
+            *cell = value;
+        }
+    }
+
+    /// Reset every cell to the type's zero (its default), for a grid of usize every cell is dead.
+    pub fn clear(&mut self)
+    where
+        T: Default,
+    {
+        self.fill(T::default());
+    }
+
+    /// This function will check all surrounding cells for living cells and return the number of cells around the given
+    /// coordinates that have a value greater than 0.  Neighbors off the grid are skipped, they're expected at the edges.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// # Return
+    /// - Result<usize, &'static str>, either the number of living cells surrounding the coordinates, or an error string.
+    pub fn get_number_of_neighbors(
+        &self,
+        row_index: usize,
+        column_index: usize,
+    ) -> Result<usize, &'static str> {
+        self.get_number_of_neighbors_with(row_index, column_index, Neighborhood::Moore)
+    }
+
+    /// Coordinates of the neighbors of a cell that are on the grid, neighbors off the edges are left out.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// - kind: Neighborhood, which surrounding cells are included
+    /// # Return
+    /// - Vec<(usize, usize)>, (row, column) of each neighbor in row-major order, empty if the center is off the grid
+    pub fn neighbor_coords(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        kind: Neighborhood,
+    ) -> Vec<(usize, usize)> {
+        if row_index >= self.size().0 || column_index >= self.size().1 {
+            return Vec::new();
+        }
+        neighbor_coordinates(row_index, column_index, self.size(), kind, false).collect()
+    }
+
+    /// Count the living neighbors of a cell using the given neighborhood, cells off the grid are treated as dead.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// - kind: Neighborhood, which surrounding cells are counted
+    /// # Return
+    /// - Result<usize, &'static str>, either the number of living cells surrounding the coordinates, or an error string.
+    pub fn get_number_of_neighbors_with(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        kind: Neighborhood,
+    ) -> Result<usize, &'static str> {
+        // verify the inputs are valid
+        if row_index >= self.size().0 {
+            return Err("Center row is out of bounds");
+        } else if column_index >= self.size().1 {
+            return Err("Center column is out of bounds");
+        }
+
+        // counted straight from the coordinates, the step calls this for every cell and a Vec would allocate each time
+        self.count_living(neighbor_coordinates(
+            row_index,
+            column_index,
+            self.size(),
+            kind,
+            false,
+        ))
+    }
+
+    /// Same as ```get_number_of_neighbors_with``` but the edges wrap around, see ```get_number_of_neighbors_wrapped```.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// - kind: Neighborhood, which surrounding cells are counted
+    /// # Return
+    /// - Result<usize, &'static str>, either the number of living cells surrounding the coordinates, or an error string.
+    pub fn get_number_of_neighbors_wrapped_with(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        kind: Neighborhood,
+    ) -> Result<usize, &'static str> {
+        // verify the inputs are valid
+        if row_index >= self.size().0 {
+            return Err("Center row is out of bounds");
+        } else if column_index >= self.size().1 {
+            return Err("Center column is out of bounds");
+        }
+
+        self.count_living(neighbor_coordinates(
+            row_index,
+            column_index,
+            self.size(),
+            kind,
+            true,
+        ))
+    }
+
+    /// Count how many of the given coordinates hold a living cell (greater than 0).
+    /// # Params
+    /// coordinates: impl IntoIterator<Item = (usize, usize)>, (row, column) coordinates to check
+    /// # Return
+    /// - Result<usize, &'static str>, number of living cells, or an error if a coordinate is out of bounds
+    fn count_living(
+        &self,
+        coordinates: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Result<usize, &'static str> {
+        let mut number_of_living = 0;
+        for (row_index, column_index) in coordinates {
+            if *self.get(row_index, column_index)? > 0 {
+                number_of_living += 1;
+            }
+        }
+        Ok(number_of_living)
+    }
+
+    /// Same as ```get_number_of_neighbors``` but the grid is treated as a torus, the edges wrap around.
+    /// Row -1 maps to row_size - 1 and column column_size maps to column 0.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// # Return
+    /// - Result<usize, &'static str>, either the number of living cells surrounding the coordinates, or an error string.
+    pub fn get_number_of_neighbors_wrapped(
+        &self,
+        row_index: usize,
+        column_index: usize,
+    ) -> Result<usize, &'static str> {
+        self.get_number_of_neighbors_wrapped_with(row_index, column_index, Neighborhood::Moore)
+    }
+}
+
+// Bytes in the binary header, the row and column sizes as little endian u32s
+const BINARY_HEADER_SIZE: usize = 8;
+
+impl Grid<usize> {
+    /// Count the living cells in the square of the given radius around a cell, cells off the grid are treated as dead.
+    /// A radius of 1 is the Moore neighborhood, see ```GridBackend::count_neighbors_in_radius``` for the other options.
+    /// # Params
+    /// - row_index: usize, row coordinate of center cell
+    /// - column_index: usize, column coordinate of center cell
+    /// - radius: usize, distance the square reaches from the cell
+    /// # Return
+    /// - Result<usize, &'static str>, either the number of living cells around the coordinates, or an error string.
+    pub fn get_live_count_in_radius(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        radius: usize,
+    ) -> Result<usize, &'static str> {
+        self.count_neighbors_in_radius(
+            row_index,
+            column_index,
+            radius,
+            Neighborhood::Moore,
+            BoundaryCondition::Dead,
+        )
+    }
 
-pub struct Grid<T> {
-    row_size: usize,
-    column_size: usize,
-    cells: Vec<T>,
-}
+    /// Pack the grid into a compact binary form, see ```from_bytes```.
+    /// The header is the row and column sizes as little endian u32s, followed by one bit per cell in row-major order
+    /// (the lowest bit of each byte first), the last byte is padded with dead cells.  Ages are dropped, a living cell is 1.
+    /// # Returns
+    /// Vec<u8>, the packed grid
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let number_of_cells = self.row_size * self.column_size;
+        let mut data = Vec::with_capacity(BINARY_HEADER_SIZE + number_of_cells.div_ceil(8));
+        for size in [self.row_size, self.column_size] {
+            let size = u32::try_from(size).expect("Grid sizes fit in a u32");
+            data.extend_from_slice(&size.to_le_bytes());
+        }
+        data.resize(BINARY_HEADER_SIZE + number_of_cells.div_ceil(8), 0);
+        for (row, column, _) in self.iter_live() {
+            let index = row * self.column_size + column;
+            data[BINARY_HEADER_SIZE + index / 8] |= 1 << (index % 8);
+        }
+        data
+    }
 
-impl<T: Copy + std::cmp::PartialOrd<usize>> Grid<T> {
-    /// Creates a new Grid object.
+    /// Unpack a grid written by ```to_bytes```, living cells have an age of 1.
     /// # Params
-    /// - row_size, usize: size of the row
-    /// - column_size, usize: size of the columns
-    /// - init_data, T:  Initial state of the cells to be filled in
+    /// - data: &[u8], packed grid
+    /// - max_cells: usize, largest rows * columns allowed, see ```conway_engine::MAX_CELLS```
     /// # Returns
-    /// - Grid<T>, a grid of the given dimensions with cells filled tihe init_data
-    pub fn new(row_size: usize, column_size: usize, init_data: T) -> Grid<T> {
-        let mut grid = Grid {
+    /// Result<Grid<usize>, &'static str>, the grid or an error if the data is truncated, corrupt or too large
+    pub fn from_bytes(data: &[u8], max_cells: usize) -> Result<Grid<usize>, &'static str> {
+        if data.len() < BINARY_HEADER_SIZE {
+            return Err("Binary grid is too short for its header");
+        }
+        let row_size = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        let column_size = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        if row_size == 0 || column_size == 0 {
+            return Err("Binary grid row and column size must be greater than zero");
+        }
+        let number_of_cells = row_size
+            .checked_mul(column_size)
+            .ok_or("Binary grid size is too large")?;
+        crate::conway_engine::check_grid_size(row_size, column_size, max_cells)?;
+        let cells = &data[BINARY_HEADER_SIZE..];
+        match cells.len().cmp(&number_of_cells.div_ceil(8)) {
+            std::cmp::Ordering::Less => return Err("Binary grid is truncated"),
+            std::cmp::Ordering::Greater => return Err("Binary grid has data past its last cell"),
+            std::cmp::Ordering::Equal => {}
+        }
+        // the padding of the last byte is always dead, anything else means the data is corrupt
+        if number_of_cells % 8 != 0 && cells[cells.len() - 1] >> (number_of_cells % 8) != 0 {
+            return Err("Binary grid has living cells past its last cell");
+        }
+
+        Grid::from_vec(
             row_size,
             column_size,
-            cells: Vec::with_capacity(row_size * column_size),
-        };
+            (0..number_of_cells)
+                .map(|index| ((cells[index / 8] >> (index % 8)) & 1) as usize)
+                .collect(),
+        )
+    }
+}
 
-        // setup the data
-        for _ in 0..grid.cells.capacity() {
-            grid.cells.push(init_data.clone());
-        }
+impl GridBackend for Grid<usize> {
+    fn size(&self) -> (usize, usize) {
+        Grid::size(self)
+    }
 
-        grid
+    fn get(&self, row: usize, column: usize) -> Result<usize, &'static str> {
+        Grid::get(self, row, column).copied()
     }
 
-    /// Get the row and column sizes of the grid.
-    /// # Returns
-    /// (usize, usize), Tuple representing (row size, column size)
-    pub fn size(&self) -> (usize, usize) {
-        (self.row_size, self.column_size)
+    fn set(&mut self, row: usize, column: usize, data: usize) -> Result<(), &'static str> {
+        Grid::set(self, row, column, data)
     }
 
-    /// Simple cloning function.  Produces a brand new Grid that is identical to self.
-    /// # Returns
-    /// Grid<T>, identical Grid to self
-    pub fn clone(&self) -> Grid<T> {
-        Grid {
-            row_size: self.row_size,
-            column_size: self.column_size,
-            cells: self.cells.to_vec(),
-        }
+    fn iter_live(&self) -> Box<dyn Iterator<Item = (usize, usize, usize)> + '_> {
+        Box::new(Grid::iter_live(self).map(|(row, column, cell)| (row, column, *cell)))
     }
 
-    /// Gets a specified element in the grid.  Will check row and column input ranges.
-    /// # Params
-    /// row, usize:  0 based row of the desired cell
-    /// column, usize, 0 based column of the desired cell
-    /// # Returns
-    /// Result<T, &'static str>, returns a result with either the cell data or an error
-    pub fn get(&self, row: usize, column: usize) -> Result<&T, &'static str> {
-        // check inputs
-        if row >= self.row_size {
-            return Err("Get row is out of bounds");
-        }
-        if column >= self.column_size {
-            return Err("Get column is out of bounds");
-        }
-        let data = self
-            .cells
-            .get(row * self.column_size + column)
-            .expect("Failed to get data from grid");
-        Ok(data)
+    fn all_live(&self, check: &mut dyn FnMut(usize, usize, usize) -> bool) -> bool {
+        Grid::iter_live(self).all(|(row, column, cell)| check(row, column, *cell))
     }
 
-    /// Sets a cell with the given data.
-    pub fn set(&mut self, row: usize, column: usize, data: T) -> Result<(), &'static str> {
-        // check inputs
-        if row >= self.row_size {
-            return Err("Given row is out of grid bounds");
-        }
-        if column >= self.column_size {
-            return Err("Given column is out of grid bounds");
-        }
-        self.cells[row * self.column_size + column] = data;
-        Ok(())
+    fn new_empty(&self, row_size: usize, column_size: usize) -> Box<dyn GridBackend> {
+        Box::new(Grid::new(row_size, column_size, 0))
     }
 
-    /// This function will check all surrounding cells for living cells and return the number of cells around the given
-    /// coordinates that have a value greater than 0.
-    /// # Params
-    /// - row_index: usize, row coordinate of center cell
-    /// - column_index: usize, column coordinate of center cell
-    /// # Return
-    /// - Result<usize, &'static str>, either the number of living cells surrounding the coordinates, or an error string.
-    pub fn get_number_of_neighbors(
+    fn population(&self) -> usize {
+        self.count_live()
+    }
+
+    fn is_empty(&self) -> bool {
+        Grid::is_empty(self)
+    }
+
+    fn clone_backend(&self) -> Box<dyn GridBackend> {
+        Box::new(Grid::clone(self))
+    }
+
+    fn reset(&mut self, row_size: usize, column_size: usize) {
+        // clearing keeps the capacity, so a grid of the same size doesn't reallocate
+        self.cells.clear();
+        self.cells.resize(row_size * column_size, 0);
+        self.row_size = row_size;
+        self.column_size = column_size;
+    }
+
+    fn count_neighbors(
         &self,
         row_index: usize,
         column_index: usize,
+        kind: Neighborhood,
+        boundary: BoundaryCondition,
     ) -> Result<usize, &'static str> {
-        // verify the inputs are valid
-        if row_index > self.size().0 {
-            return Err("Center row is out of bounds");
-        } else if column_index > self.size().1 {
-            return Err("Center column is out of bounds");
-        }
-
-        let mut number_of_neighbors = 0;
-        // setup row range
-        let row_min = if row_index == 0 { 0 } else { row_index - 1 };
-        let row_max = if row_index + 1 >= self.size().0 {
-            self.size().0 - 1
-        } else {
-            row_index + 1
-        };
-        // setup column range
-        let column_min = if column_index == 0 {
-            0
-        } else {
-            column_index - 1
-        };
-        let column_max = if column_index + 1 >= self.size().1 {
-            self.size().1 - 1
-        } else {
-            column_index + 1
-        };
-
-        // loop through neighbor coordinates
-        for neighbor_row_index in row_min..=row_max {
-            for neighbor_column_index in column_min..=column_max {
-                // skip center coordinate
-                if neighbor_row_index == row_index && neighbor_column_index == column_index {
-                    continue;
-                }
-                match self.get(neighbor_row_index, neighbor_column_index) {
-                    Ok(cell_data) => {
-                        if *cell_data > 0 {
-                            number_of_neighbors += 1;
-                        }
-                    }
-                    Err(_err) => (println!("{}", _err)), // skip over error, probably out of bounds
-                };
+        match boundary {
+            BoundaryCondition::Dead => {
+                self.get_number_of_neighbors_with(row_index, column_index, kind)
+            }
+            BoundaryCondition::Alive => {
+                Ok(
+                    self.get_number_of_neighbors_with(row_index, column_index, kind)?
+                        + off_grid_neighbors(row_index, column_index, self.size(), kind),
+                )
+            }
+            BoundaryCondition::Toroidal => {
+                self.get_number_of_neighbors_wrapped_with(row_index, column_index, kind)
             }
         }
-        Ok(number_of_neighbors)
     }
 }
 
@@ -153,6 +1245,35 @@ mod test {
     fn test_grid() {
         let grid = setup_grid();
         assert_eq!(grid.size(), (5, 6));
+        // one cell per coordinate, never more
+        for (row_size, column_size) in [(5, 6), (1, 1), (3, 7), (0, 4), (17, 13)] {
+            let grid = Grid::new(row_size, column_size, 0);
+            assert_eq!(grid.len(), row_size * column_size);
+            assert_eq!(grid.cells.len(), row_size * column_size);
+        }
+    }
+
+    #[test]
+    fn test_from_vec() {
+        // row-major, the second row starts after the first column_size cells
+        let grid = Grid::from_vec(2, 3, vec![0, 1, 0, 2, 0, 3]).unwrap();
+        assert_eq!(grid.size(), (2, 3));
+        assert_eq!(grid.get(0, 1), Ok(&1));
+        assert_eq!(grid.get(1, 0), Ok(&2));
+        assert_eq!(grid.get(1, 2), Ok(&3));
+        assert_eq!(grid.count_live(), 3);
+        assert!(Grid::<usize>::from_vec(0, 4, Vec::new())
+            .unwrap()
+            .is_empty());
+
+        // one cell short, one too many, and a size that overflows
+        assert!(Grid::from_vec(2, 3, vec![0; 5]).is_err());
+        assert!(Grid::from_vec(2, 3, vec![0; 7]).is_err());
+        assert_eq!(
+            Grid::from_vec(3, 2, vec![true; 5]).err(),
+            Some("Number of cells must be the row size times the column size")
+        );
+        assert!(Grid::from_vec(usize::MAX, 2, vec![0]).is_err());
     }
 
     #[test]
@@ -179,6 +1300,29 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_get_mut() {
+        let mut grid = setup_grid();
+        // age a cell in place, like a surviving cell
+        *grid.get_mut(2, 3).unwrap() = 1;
+        *grid.get_mut(2, 3).unwrap() += 1;
+        assert_eq!(grid.get(2, 3), Ok(&2));
+        // only that cell changed
+        assert_eq!(grid.count_live(), 1);
+
+        // the last cell, then out of bounds like get
+        *grid.get_mut(4, 5).unwrap() = 7;
+        assert_eq!(grid.get(4, 5), Ok(&7));
+        assert_eq!(grid.get_mut(5, 0), Err("Get row is out of bounds"));
+        assert_eq!(grid.get_mut(0, 6), Err("Get column is out of bounds"));
+
+        // works for any cell type
+        let mut mask = Grid::new(2, 2, false);
+        *mask.get_mut(1, 0).unwrap() = true;
+        assert_eq!(mask.get(1, 0), Ok(&true));
+        assert_eq!(mask.get(0, 1), Ok(&false));
+    }
+
     #[test]
     fn test_number_neighbors() {
         let mut grid = Grid::new(3, 3, 0);
@@ -302,4 +1446,725 @@ mod test {
         assert!(num_n.is_ok());
         assert_eq!(1, num_n.unwrap());
     }
+
+    #[test]
+    fn test_number_neighbors_wrapped() {
+        // living cells in every corner, each corner sees the other three through two wraps
+        let mut grid = Grid::new(4, 5, 0);
+        assert!(grid.set(0, 0, 1).is_ok());
+        assert!(grid.set(0, 4, 1).is_ok());
+        assert!(grid.set(3, 0, 1).is_ok());
+        assert!(grid.set(3, 4, 1).is_ok());
+
+        assert_eq!(grid.get_number_of_neighbors_wrapped(0, 0).unwrap(), 3);
+        assert_eq!(grid.get_number_of_neighbors_wrapped(0, 4).unwrap(), 3);
+        assert_eq!(grid.get_number_of_neighbors_wrapped(3, 0).unwrap(), 3);
+        assert_eq!(grid.get_number_of_neighbors_wrapped(3, 4).unwrap(), 3);
+        // bounded counting can't see across the edges
+        assert_eq!(grid.get_number_of_neighbors(0, 0).unwrap(), 0);
+
+        // edge cells only wrap once
+        assert_eq!(grid.get_number_of_neighbors_wrapped(0, 2).unwrap(), 0);
+        assert_eq!(grid.get_number_of_neighbors_wrapped(1, 0).unwrap(), 2);
+        assert_eq!(grid.get_number_of_neighbors_wrapped(2, 4).unwrap(), 2);
+        assert_eq!(grid.get_number_of_neighbors_wrapped(1, 2).unwrap(), 0);
+
+        // access outside grid
+        assert!(grid.get_number_of_neighbors_wrapped(4, 0).is_err());
+        assert!(grid.get_number_of_neighbors_wrapped(0, 5).is_err());
+    }
+
+    #[test]
+    fn test_equals() {
+        let mut grid = setup_grid();
+        let mut other = setup_grid();
+        assert!(grid.equals(&other));
+
+        assert!(grid.set(2, 3, 1).is_ok());
+        assert!(!grid.equals(&other));
+        assert!(other.set(2, 3, 1).is_ok());
+        assert!(grid.equals(&other));
+        assert!(grid.equals(&grid.clone()));
+
+        // same cells, different dimensions
+        let grid = Grid::new(6, 5, 0);
+        assert!(!grid.equals(&Grid::new(5, 6, 0)));
+    }
+
+    #[test]
+    fn test_same_live_cells() {
+        let mut grid = setup_grid();
+        let mut other = setup_grid();
+        assert!(grid.same_live_cells(&other));
+
+        // different ages, same living cells
+        assert!(grid.set(1, 1, 1).is_ok());
+        assert!(other.set(1, 1, 7).is_ok());
+        assert!(grid.same_live_cells(&other));
+        assert!(!grid.equals(&other));
+
+        assert!(other.set(1, 2, 1).is_ok());
+        assert!(!grid.same_live_cells(&other));
+        assert!(!grid.same_live_cells(&Grid::new(6, 5, 0)));
+    }
+
+    #[test]
+    fn test_resize_centered() {
+        let mut grid = Grid::new(2, 3, 0);
+        grid.set(0, 0, 1).unwrap();
+        grid.set(1, 2, 2).unwrap();
+
+        // growing keeps the contents in the center
+        let bigger = grid.resize_centered(6, 7);
+        assert_eq!(bigger.size(), (6, 7));
+        assert_eq!(*bigger.get(2, 2).unwrap(), 1);
+        assert_eq!(*bigger.get(3, 4).unwrap(), 2);
+        let total: usize = (0..6)
+            .flat_map(|row| (0..7).map(move |column| (row, column)))
+            .map(|(row, column)| *bigger.get(row, column).unwrap())
+            .sum();
+        assert_eq!(total, 3);
+
+        // shrinking crops the edges
+        let smaller = bigger.resize_centered(2, 3);
+        assert!(smaller.equals(&grid));
+        let cropped = grid.resize_centered(1, 1);
+        assert_eq!(cropped.size(), (1, 1));
+        assert_eq!(*cropped.get(0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_live_count_in_radius() {
+        // every cell alive, radius 2 is the 5x5 square or the 12 cell diamond
+        let grid = Grid::new(5, 5, 1);
+        assert_eq!(grid.get_live_count_in_radius(2, 2, 2), Ok(24));
+        assert_eq!(grid.get_live_count_in_radius(0, 0, 2), Ok(8));
+        assert_eq!(grid.get_live_count_in_radius(0, 2, 2), Ok(14));
+        assert_eq!(
+            grid.count_neighbors_in_radius(
+                2,
+                2,
+                2,
+                Neighborhood::VonNeumann,
+                BoundaryCondition::Dead
+            ),
+            Ok(Neighborhood::VonNeumann.max_neighbors_in_radius(2))
+        );
+        assert_eq!(
+            grid.count_neighbors_in_radius(0, 0, 2, Neighborhood::Moore, BoundaryCondition::Alive),
+            Ok(Neighborhood::Moore.max_neighbors_in_radius(2))
+        );
+
+        let mut grid = Grid::new(6, 6, 0);
+        for (row, column) in [(0, 0), (2, 2), (4, 4), (1, 3), (5, 5)] {
+            grid.set(row, column, 1).unwrap();
+        }
+        assert_eq!(grid.get_live_count_in_radius(2, 2, 2), Ok(3));
+        assert_eq!(grid.get_live_count_in_radius(2, 2, 1), Ok(1));
+        assert_eq!(
+            grid.count_neighbors_in_radius(
+                2,
+                2,
+                2,
+                Neighborhood::VonNeumann,
+                BoundaryCondition::Dead
+            ),
+            Ok(1)
+        );
+        // the corner sees across the edges when they wrap, or 16 living cells off the grid
+        assert_eq!(grid.get_live_count_in_radius(0, 0, 2), Ok(1));
+        assert_eq!(
+            grid.count_neighbors_in_radius(
+                0,
+                0,
+                2,
+                Neighborhood::Moore,
+                BoundaryCondition::Toroidal
+            ),
+            Ok(3)
+        );
+        assert_eq!(
+            grid.count_neighbors_in_radius(0, 0, 2, Neighborhood::Moore, BoundaryCondition::Alive),
+            Ok(17)
+        );
+        assert!(grid.get_live_count_in_radius(6, 0, 2).is_err());
+
+        // radius 1 is the usual neighbor count
+        for boundary in [
+            BoundaryCondition::Dead,
+            BoundaryCondition::Alive,
+            BoundaryCondition::Toroidal,
+        ] {
+            for kind in [Neighborhood::Moore, Neighborhood::VonNeumann] {
+                for row in 0..6 {
+                    for column in 0..6 {
+                        assert_eq!(
+                            grid.count_neighbors_in_radius(row, column, 1, kind, boundary),
+                            grid.count_neighbors(row, column, kind, boundary)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbor_coords() {
+        let grid = setup_grid();
+        // corner
+        assert_eq!(
+            grid.neighbor_coords(0, 0, Neighborhood::Moore),
+            vec![(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(
+            grid.neighbor_coords(0, 0, Neighborhood::VonNeumann),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbor_coords(4, 5, Neighborhood::Moore),
+            vec![(3, 4), (3, 5), (4, 4)]
+        );
+        assert_eq!(
+            grid.neighbor_coords(4, 5, Neighborhood::VonNeumann),
+            vec![(3, 5), (4, 4)]
+        );
+        // edge
+        assert_eq!(
+            grid.neighbor_coords(0, 2, Neighborhood::Moore),
+            vec![(0, 1), (0, 3), (1, 1), (1, 2), (1, 3)]
+        );
+        assert_eq!(
+            grid.neighbor_coords(2, 5, Neighborhood::VonNeumann),
+            vec![(1, 5), (2, 4), (3, 5)]
+        );
+        // interior
+        assert_eq!(
+            grid.neighbor_coords(2, 3, Neighborhood::Moore),
+            vec![
+                (1, 2),
+                (1, 3),
+                (1, 4),
+                (2, 2),
+                (2, 4),
+                (3, 2),
+                (3, 3),
+                (3, 4)
+            ]
+        );
+        assert_eq!(
+            grid.neighbor_coords(2, 3, Neighborhood::VonNeumann),
+            vec![(1, 3), (2, 2), (2, 4), (3, 3)]
+        );
+        // off the grid
+        assert!(grid.neighbor_coords(5, 0, Neighborhood::Moore).is_empty());
+        assert!(grid
+            .neighbor_coords(0, 6, Neighborhood::VonNeumann)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_number_neighbors_with() {
+        // every cell alive
+        let grid = Grid::new(3, 3, 1);
+        assert_eq!(
+            grid.get_number_of_neighbors_with(1, 1, Neighborhood::Moore),
+            Ok(8)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_with(1, 1, Neighborhood::VonNeumann),
+            Ok(4)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_with(0, 0, Neighborhood::Moore),
+            Ok(3)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_with(0, 0, Neighborhood::VonNeumann),
+            Ok(2)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_with(0, 1, Neighborhood::VonNeumann),
+            Ok(3)
+        );
+
+        // only the diagonals alive
+        let mut grid = Grid::new(3, 3, 0);
+        for (row, column) in [(0, 0), (0, 2), (2, 0), (2, 2)].iter() {
+            grid.set(*row, *column, 1).unwrap();
+        }
+        assert_eq!(
+            grid.get_number_of_neighbors_with(1, 1, Neighborhood::Moore),
+            Ok(4)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_with(1, 1, Neighborhood::VonNeumann),
+            Ok(0)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_with(0, 1, Neighborhood::Moore),
+            Ok(2)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_with(0, 1, Neighborhood::VonNeumann),
+            Ok(2)
+        );
+
+        // wrapped, the corners touch each other across the edges
+        assert_eq!(
+            grid.get_number_of_neighbors_wrapped_with(0, 0, Neighborhood::Moore),
+            Ok(3)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_wrapped_with(0, 0, Neighborhood::VonNeumann),
+            Ok(2)
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors_wrapped_with(1, 1, Neighborhood::VonNeumann),
+            Ok(0)
+        );
+
+        // out of bounds
+        assert!(grid
+            .get_number_of_neighbors_with(3, 0, Neighborhood::VonNeumann)
+            .is_err());
+        assert!(grid
+            .get_number_of_neighbors_wrapped_with(0, 3, Neighborhood::VonNeumann)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_neighborhood() {
+        assert_eq!("moore".parse::<Neighborhood>(), Ok(Neighborhood::Moore));
+        assert_eq!(
+            "VonNeumann".parse::<Neighborhood>(),
+            Ok(Neighborhood::VonNeumann)
+        );
+        assert_eq!(
+            "von-neumann".parse::<Neighborhood>(),
+            Ok(Neighborhood::VonNeumann)
+        );
+        assert!("hex".parse::<Neighborhood>().is_err());
+        assert_eq!(Neighborhood::default(), Neighborhood::Moore);
+    }
+
+    #[test]
+    fn test_parse_backend() {
+        assert_eq!("dense".parse::<Backend>(), Ok(Backend::Dense));
+        assert_eq!(" Sparse ".parse::<Backend>(), Ok(Backend::Sparse));
+        assert!("hash".parse::<Backend>().is_err());
+        assert_eq!(Backend::default(), Backend::Dense);
+    }
+
+    #[test]
+    fn test_iter_cells() {
+        let mut grid = Grid::new(3, 3, 0);
+        grid.set(0, 2, 4).unwrap();
+        grid.set(2, 1, 7).unwrap();
+
+        let cells: Vec<(usize, usize, usize)> = grid
+            .iter_cells()
+            .map(|(row, column, cell)| (row, column, *cell))
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                (0, 0, 0),
+                (0, 1, 0),
+                (0, 2, 4),
+                (1, 0, 0),
+                (1, 1, 0),
+                (1, 2, 0),
+                (2, 0, 0),
+                (2, 1, 7),
+                (2, 2, 0),
+            ]
+        );
+
+        let live: Vec<(usize, usize, usize)> = grid
+            .iter_live()
+            .map(|(row, column, cell)| (row, column, *cell))
+            .collect();
+        assert_eq!(live, vec![(0, 2, 4), (2, 1, 7)]);
+
+        // uneven grid keeps rows and columns straight
+        let mut grid = Grid::new(2, 4, 0);
+        grid.set(1, 3, 1).unwrap();
+        assert_eq!(grid.iter_cells().count(), 8);
+        assert_eq!(grid.iter_live().next(), Some((1, 3, &1)));
+    }
+
+    #[test]
+    fn test_map() {
+        // ages to an alive mask, the dimensions are kept
+        let mut grid = Grid::new(2, 3, 0);
+        grid.set(0, 1, 4).unwrap();
+        grid.set(1, 2, 1).unwrap();
+        let mask: Grid<bool> = grid.map(|cell| *cell > 0);
+        assert_eq!(mask.size(), (2, 3));
+        assert_eq!(mask.len(), 6);
+        let cells: Vec<bool> = mask.iter_cells().map(|(_, _, alive)| *alive).collect();
+        assert_eq!(cells, vec![false, true, false, false, false, true]);
+        assert_eq!(mask.get(0, 1), Ok(&true));
+        assert!(mask.get(2, 0).is_err());
+
+        // and back, a mapped grid is a full grid again
+        let ones = mask.map(|alive| *alive as usize);
+        assert!(ones.same_live_cells(&grid));
+        assert_eq!(ones.count_live(), 2);
+        assert_eq!(ones.get(0, 1), Ok(&1));
+    }
+
+    #[test]
+    fn test_hash() {
+        let mut grid = Grid::new(4, 4, 0);
+        let empty_hash = GridBackend::hash(&grid);
+        grid.set(1, 2, 1).unwrap();
+        let hash = GridBackend::hash(&grid);
+        assert_ne!(hash, empty_hash);
+
+        // ages are ignored
+        grid.set(1, 2, 7).unwrap();
+        assert_eq!(GridBackend::hash(&grid), hash);
+
+        // position and size matter
+        let mut moved = Grid::new(4, 4, 0);
+        moved.set(2, 1, 1).unwrap();
+        assert_ne!(GridBackend::hash(&moved), hash);
+        assert_ne!(GridBackend::hash(&Grid::new(4, 5, 0)), empty_hash);
+    }
+
+    #[test]
+    fn test_boundary_conditions() {
+        // corner cell with one living neighbor inside the grid and one across the wrapped edges
+        let mut grid = Grid::new(4, 4, 0);
+        grid.set(0, 1, 1).unwrap();
+        grid.set(3, 3, 1).unwrap();
+
+        let count = |kind, boundary| GridBackend::count_neighbors(&grid, 0, 0, kind, boundary);
+        // 5 of the 8 Moore neighbors are off the grid
+        assert_eq!(count(Neighborhood::Moore, BoundaryCondition::Dead), Ok(1));
+        assert_eq!(count(Neighborhood::Moore, BoundaryCondition::Alive), Ok(6));
+        assert_eq!(
+            count(Neighborhood::Moore, BoundaryCondition::Toroidal),
+            Ok(2)
+        );
+        // 2 of the 4 von Neumann neighbors are off the grid, the diagonal (3, 3) isn't a neighbor
+        assert_eq!(
+            count(Neighborhood::VonNeumann, BoundaryCondition::Dead),
+            Ok(1)
+        );
+        assert_eq!(
+            count(Neighborhood::VonNeumann, BoundaryCondition::Alive),
+            Ok(3)
+        );
+        assert_eq!(
+            count(Neighborhood::VonNeumann, BoundaryCondition::Toroidal),
+            Ok(1)
+        );
+
+        // cells away from the edges don't change
+        assert_eq!(
+            off_grid_neighbors(1, 1, grid.size(), Neighborhood::Moore),
+            0
+        );
+        assert_eq!(
+            GridBackend::count_neighbors(
+                &grid,
+                1,
+                1,
+                Neighborhood::Moore,
+                BoundaryCondition::Alive
+            ),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_parse_boundary() {
+        assert_eq!(
+            "dead".parse::<BoundaryCondition>(),
+            Ok(BoundaryCondition::Dead)
+        );
+        assert_eq!(
+            "Alive".parse::<BoundaryCondition>(),
+            Ok(BoundaryCondition::Alive)
+        );
+        assert_eq!(
+            "toroidal".parse::<BoundaryCondition>(),
+            Ok(BoundaryCondition::Toroidal)
+        );
+        assert_eq!(
+            "wrap".parse::<BoundaryCondition>(),
+            Ok(BoundaryCondition::Toroidal)
+        );
+        assert!("mirror".parse::<BoundaryCondition>().is_err());
+        assert_eq!(BoundaryCondition::default(), BoundaryCondition::Dead);
+    }
+
+    #[test]
+    fn test_diff() {
+        // vertical blinker steps to horizontal
+        let mut previous = Grid::new(5, 5, 0);
+        for row in 1..4 {
+            previous.set(row, 2, 1).unwrap();
+        }
+        let mut grid = Grid::new(5, 5, 0);
+        grid.set(2, 1, 1).unwrap();
+        grid.set(2, 2, 2).unwrap();
+        grid.set(2, 3, 1).unwrap();
+
+        let expected = vec![(1, 2, 0), (2, 1, 1), (2, 2, 2), (2, 3, 1), (3, 2, 0)];
+        assert_eq!(grid.diff(&previous), Ok(expected.clone()));
+        assert_eq!(GridBackend::diff(&grid, &previous), Ok(expected));
+        assert_eq!(grid.diff(&grid.clone()), Ok(vec![]));
+
+        assert!(grid.diff(&Grid::new(5, 6, 0)).is_err());
+        assert!(GridBackend::diff(&grid, &Grid::new(6, 5, 0)).is_err());
+    }
+
+    #[test]
+    fn test_corner_neighbors() {
+        // full 3x3 grid, corners see 3 neighbors, edges 5 and the center 8
+        let grid = Grid::new(3, 3, 1);
+        assert_eq!(grid.get_number_of_neighbors(0, 0), Ok(3));
+        assert_eq!(grid.get_number_of_neighbors(0, 2), Ok(3));
+        assert_eq!(grid.get_number_of_neighbors(2, 0), Ok(3));
+        assert_eq!(grid.get_number_of_neighbors(2, 2), Ok(3));
+        assert_eq!(grid.get_number_of_neighbors(0, 1), Ok(5));
+        assert_eq!(grid.get_number_of_neighbors(1, 1), Ok(8));
+
+        // a single cell grid has no neighbors
+        assert_eq!(Grid::new(1, 1, 1).get_number_of_neighbors(0, 0), Ok(0));
+
+        // the center has to be on the grid, one past the last row or column is an error
+        assert!(grid.get_number_of_neighbors(3, 0).is_err());
+        assert!(grid.get_number_of_neighbors(0, 3).is_err());
+
+        // rejected by the center check, not later by ```get```, on a grid that isn't square
+        let grid = Grid::new(2, 4, 1);
+        assert_eq!(
+            grid.get_number_of_neighbors(2, 0),
+            Err("Center row is out of bounds")
+        );
+        assert_eq!(
+            grid.get_number_of_neighbors(0, 4),
+            Err("Center column is out of bounds")
+        );
+        assert_eq!(grid.get_number_of_neighbors(1, 3), Ok(3));
+    }
+
+    #[test]
+    fn test_stamp() {
+        let mut glider = Grid::new(3, 3, 0);
+        for (row, column) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)].iter() {
+            glider.set(*row, *column, 1).unwrap();
+        }
+
+        let mut grid = Grid::new(10, 10, 0);
+        assert!(grid.stamp(2, 2, &glider).is_ok());
+        let live: Vec<(usize, usize)> = grid
+            .iter_live()
+            .map(|(row, column, _)| (row, column))
+            .collect();
+        assert_eq!(live, vec![(2, 3), (3, 4), (4, 2), (4, 3), (4, 4)]);
+
+        // a second glider in the bottom right corner fits exactly
+        assert!(grid.stamp(7, 7, &glider).is_ok());
+        assert_eq!(grid.iter_live().count(), 10);
+
+        // overflowing stamps leave the grid alone
+        let before = grid.clone();
+        assert!(grid.stamp(8, 2, &glider).is_err());
+        assert!(grid.stamp(2, 8, &glider).is_err());
+        assert!(grid.stamp(usize::MAX - 1, 0, &glider).is_err());
+        assert!(grid.equals(&before));
+    }
+
+    #[test]
+    fn test_count_live() {
+        let mut grid = Grid::new(3, 4, 0);
+        assert_eq!(grid.count_live(), 0);
+        assert!(grid.is_empty());
+
+        // ages count as a single living cell
+        grid.set(0, 1, 5).unwrap();
+        grid.set(2, 3, 1).unwrap();
+        assert_eq!(grid.count_live(), 2);
+        assert!(!grid.is_empty());
+        assert_eq!(GridBackend::population(&grid), 2);
+        assert!(!GridBackend::is_empty(&grid));
+
+        grid.clear();
+        assert_eq!(grid.count_live(), 0);
+        assert!(grid.is_empty());
+        assert!(Grid::new(0, 0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_live_bounding_box() {
+        assert_eq!(Grid::new(5, 5, 0).live_bounding_box(), None);
+        assert_eq!(Grid::new(0, 0, 0).live_bounding_box(), None);
+
+        // a glider away from the edges has a tight 3x3 box
+        let mut grid = Grid::new(10, 12, 0);
+        for (row, column) in [(3, 5), (4, 6), (5, 4), (5, 5), (5, 6)] {
+            grid.set(row, column, 1).unwrap();
+        }
+        assert_eq!(grid.live_bounding_box(), Some((3, 4, 5, 6)));
+
+        // a single old cell in the corner
+        let mut grid = Grid::new(4, 4, 0);
+        grid.set(3, 3, 9).unwrap();
+        assert_eq!(grid.live_bounding_box(), Some((3, 3, 3, 3)));
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let grid = crate::conway_engine::generate_random_grid(
+            64,
+            64,
+            0.4,
+            Some(75),
+            crate::conway_engine::MAX_CELLS,
+        )
+        .unwrap();
+        let data = grid.to_bytes();
+        // 8 byte header and a bit per cell
+        assert_eq!(data.len(), 8 + 64 * 64 / 8);
+        assert_eq!(&data[..8], &[64, 0, 0, 0, 64, 0, 0, 0]);
+        assert!(Grid::from_bytes(&data, crate::conway_engine::MAX_CELLS)
+            .unwrap()
+            .equals(&grid));
+
+        // sizes that don't fill the last byte, ages come back as 1
+        let mut grid = Grid::new(3, 5, 0);
+        grid.set(0, 0, 4).unwrap();
+        grid.set(2, 4, 1).unwrap();
+        let data = grid.to_bytes();
+        assert_eq!(data, vec![3, 0, 0, 0, 5, 0, 0, 0, 0b0000_0001, 0b0100_0000]);
+        let reloaded = Grid::from_bytes(&data, crate::conway_engine::MAX_CELLS).unwrap();
+        assert_eq!(reloaded.size(), (3, 5));
+        assert_eq!(*reloaded.get(0, 0).unwrap(), 1);
+        assert_eq!(*reloaded.get(2, 4).unwrap(), 1);
+        assert_eq!(reloaded.count_live(), 2);
+    }
+
+    #[test]
+    fn test_bytes_errors() {
+        let data = Grid::new(3, 5, 0).to_bytes();
+        assert_eq!(
+            Grid::from_bytes(&data[..5], crate::conway_engine::MAX_CELLS).err(),
+            Some("Binary grid is too short for its header")
+        );
+        assert_eq!(
+            Grid::from_bytes(&data[..9], crate::conway_engine::MAX_CELLS).err(),
+            Some("Binary grid is truncated")
+        );
+        let mut longer = data.clone();
+        longer.push(0);
+        assert_eq!(
+            Grid::from_bytes(&longer, crate::conway_engine::MAX_CELLS).err(),
+            Some("Binary grid has data past its last cell")
+        );
+        let mut padded = data.clone();
+        padded[9] |= 0b1000_0000;
+        assert_eq!(
+            Grid::from_bytes(&padded, crate::conway_engine::MAX_CELLS).err(),
+            Some("Binary grid has living cells past its last cell")
+        );
+        let huge = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+        assert!(Grid::from_bytes(&huge, crate::conway_engine::MAX_CELLS).is_err());
+        for empty in [[0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 5, 0, 0, 0]].iter() {
+            assert_eq!(
+                Grid::from_bytes(empty, crate::conway_engine::MAX_CELLS).err(),
+                Some("Binary grid row and column size must be greater than zero")
+            );
+        }
+        // the cell limit is checked before the cells are unpacked
+        assert_eq!(
+            Grid::from_bytes(&data, 14).err(),
+            Some("Grid has more cells than the maximum allowed")
+        );
+        assert!(Grid::from_bytes(&data, 15).is_ok());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut grid = setup_grid();
+        grid.set(1, 1, 3).unwrap();
+        let capacity = grid.cells.capacity();
+        GridBackend::reset(&mut grid, 2, 4);
+        assert_eq!(grid.size(), (2, 4));
+        assert!(grid.iter_cells().all(|(_, _, cell)| *cell == 0));
+        // shrinking keeps the storage
+        assert_eq!(grid.cells.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_fill_clear() {
+        let mut grid = Grid::new(3, 4, 0);
+        grid.set(0, 1, 5).unwrap();
+        grid.set(2, 3, 1).unwrap();
+
+        grid.fill(2);
+        assert_eq!(grid.size(), (3, 4));
+        assert!(grid.iter_cells().all(|(_, _, cell)| *cell == 2));
+
+        grid.set(1, 1, 9).unwrap();
+        grid.clear();
+        assert_eq!(grid.size(), (3, 4));
+        assert!(grid.iter_cells().all(|(_, _, cell)| *cell == 0));
+        assert_eq!(grid.iter_live().count(), 0);
+    }
+
+    #[test]
+    fn test_rotate_flip() {
+        // glider in a 3 x 4 grid, the extra column shows the dimensions swapping
+        let mut glider = Grid::new(3, 4, 0);
+        for (row, column) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)].iter() {
+            glider.set(*row, *column, 1).unwrap();
+        }
+
+        let rotated = glider.rotate_90();
+        assert_eq!(rotated.size(), (4, 3));
+        // top row ends up in the rightmost column
+        assert_eq!(*rotated.get(1, 2).unwrap(), 1);
+        assert_eq!(*rotated.get(0, 0).unwrap(), 1);
+        assert_eq!(*rotated.get(0, 2).unwrap(), 0);
+
+        // four quarter turns are the original grid
+        let full_turn = glider.rotate_90().rotate_90().rotate_90().rotate_90();
+        assert_eq!(full_turn.size(), glider.size());
+        assert!(full_turn.equals(&glider));
+
+        let flipped = glider.flip_horizontal();
+        assert_eq!(flipped.size(), (3, 4));
+        assert_eq!(*flipped.get(0, 2).unwrap(), 1);
+        assert_eq!(*flipped.get(2, 3).unwrap(), 1);
+        assert_eq!(*flipped.get(2, 0).unwrap(), 0);
+        assert!(flipped.flip_horizontal().equals(&glider));
+
+        let flipped = glider.flip_vertical();
+        assert_eq!(*flipped.get(0, 0).unwrap(), 1);
+        assert_eq!(*flipped.get(2, 1).unwrap(), 1);
+        assert_eq!(*flipped.get(0, 3).unwrap(), 0);
+        assert!(flipped.flip_vertical().equals(&glider));
+
+        // a half turn is both flips
+        assert!(glider
+            .rotate_90()
+            .rotate_90()
+            .equals(&glider.flip_horizontal().flip_vertical()));
+    }
+
+    #[test]
+    fn test_parse_transforms() {
+        assert_eq!("90".parse::<Rotation>(), Ok(Rotation::Quarter));
+        assert_eq!("270".parse::<Rotation>().unwrap().quarter_turns(), 3);
+        assert!("45".parse::<Rotation>().is_err());
+        assert!("360".parse::<Rotation>().is_err());
+        assert_eq!("h".parse::<Flip>(), Ok(Flip::Horizontal));
+        assert_eq!("Vertical".parse::<Flip>(), Ok(Flip::Vertical));
+        assert!("d".parse::<Flip>().is_err());
+    }
 }
@@ -1,182 +1,1125 @@
 /// Handles drawing and updating the GUI.
 /// This allows for CLI parameters to be fed in a read from Nannou's model function.
-#[path = "conway_engine.rs"]
-mod conway_engine;
-use nannou::color::named;
+use crate::config::Config;
+use crate::control::{Command, ControlServer};
+use crate::coords::{grid_to_screen, move_cursor, screen_to_grid, ViewTransform};
+use crate::engine_exit_code;
+use crate::population_log::PopulationLog;
+use conway_rust::conway_engine;
+use nannou::color::rgb::Srgb;
+use nannou::color::Mix;
+use nannou::image::{ImageResult, Rgb, RgbImage};
 use nannou::prelude::*;
 use nannou::winit::window::Icon;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::str::FromStr;
+use std::sync::OnceLock;
 use std::time::Duration;
 
+/// Shape living cells are drawn with.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CellShape {
+    /// Rectangle filling the cell
+    #[default]
+    Square,
+    /// Ellipse filling the cell, round when the cell is square
+    Circle,
+}
+
+/// Window corner the generation and population HUD is drawn in.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HudCorner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl FromStr for HudCorner {
+    type Err = &'static str;
+
+    /// Parse a corner name, case insensitive: "top-left", "top-right", "bottom-left" or "bottom-right".
+    /// # Params
+    /// name: &str, name of the corner
+    /// # Returns
+    /// Result<HudCorner, &'static str>, the corner or an error if the name is unknown
+    fn from_str(name: &str) -> Result<HudCorner, &'static str> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "top-left" => Ok(HudCorner::TopLeft),
+            "top-right" => Ok(HudCorner::TopRight),
+            "bottom-left" => Ok(HudCorner::BottomLeft),
+            "bottom-right" => Ok(HudCorner::BottomRight),
+            _ => Err("HUD corner must be top-left, top-right, bottom-left or bottom-right"),
+        }
+    }
+}
+
+impl FromStr for CellShape {
+    type Err = &'static str;
+
+    /// Parse a cell shape name, case insensitive: "square" or "circle".
+    /// # Params
+    /// name: &str, name of the shape
+    /// # Returns
+    /// Result<CellShape, &'static str>, the shape or an error if the name is unknown
+    fn from_str(name: &str) -> Result<CellShape, &'static str> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "square" => Ok(CellShape::Square),
+            "circle" => Ok(CellShape::Circle),
+            _ => Err("Cell shape must be square or circle"),
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 struct ConfigParams {
-    file_name: &'static str,
+    /// Files played one after the other, the next one starts when a simulation ends (see ```next_in_playlist```)
+    file_names: &'static [&'static str],
+    /// (alive, dead) characters the files are read as ASCII art with, None reads them by their extension
+    ascii_cells: Option<(char, char)>,
+    /// Checks applied while the files are loaded, the most cells apply to random boards and infinite grids as well
+    load_options: conway_engine::LoadOptions,
+    pattern: &'static str,
     number_of_steps: usize,
-    update_rate: usize,
+    update_rate: f64,
     height: f32,
     width: f32,
+    /// Pixels on each side of a cell, the window is sized to fit the starting grid instead of height and width
+    cell_size: Option<f32>,
     alive_color: nannou::color::rgb::Srgb<u8>,
     dead_color: nannou::color::rgb::Srgb<u8>,
+    /// Color behind the cells, None fills the window with the dead color and skips drawing dead cells
+    background: Option<nannou::color::rgb::Srgb<u8>>,
     enable_grid: bool,
+    grid_color: nannou::color::rgb::Srgb<u8>,
+    grid_thickness: f32,
+    square_cells: bool,
+    cell_shape: CellShape,
+    /// Stroke the border of every cell, alive or dead, see ```outline_color```
+    outline_cells: bool,
+    show_fps: bool,
+    /// Corner of the generation and population HUD, None starts with it hidden
+    hud: Option<HudCorner>,
+    start_paused: bool,
+    pause_on_blur: bool,
+    boundary: conway_engine::BoundaryCondition,
+    infinite: bool,
+    neighborhood: conway_engine::Neighborhood,
+    /// Distance the neighborhood reaches, see ```ConwayEngine::set_radius```, None keeps the file's radius
+    radius: Option<usize>,
+    backend: conway_engine::Backend,
+    rotation: conway_engine::Rotation,
+    flip: Option<conway_engine::Flip>,
+    detect_cycle: usize,
+    /// Rule given on the command line, None keeps the rule from the file (see ```build_engine```)
+    rule: Option<&'static str>,
+    save_on_exit: &'static str,
+    save_format: conway_engine::SaveFormat,
+    species: bool,
+    max_age: usize,
+    old_color: nannou::color::rgb::Srgb<u8>,
+    /// Color of the cells that died in the last step, None skips them like any dead cell
+    dying_color: Option<Srgb<u8>>,
+    random_density: Option<f64>,
+    seed: Option<u64>,
+    random_size: (usize, usize),
 }
 
 // Empty struct, needed to expose start function
+#[allow(clippy::upper_case_acronyms)]
 pub struct GUI {}
 
-// set the global params to default values
-// NOTE:  Needed since the ```model``` function can't take extra parameters.
-static mut GLOBAL_PARAMS: ConfigParams = ConfigParams {
-    file_name: "",
-    number_of_steps: 20,
-    update_rate: 1,
-    height: 768.0,
-    width: 1024.0,
-    alive_color: BLACK,
-    dead_color: WHITE,
-    enable_grid: false,
-};
+// Configuration handed from ```start``` to ```model```, written once before the GUI starts.
+// NOTE:  Needed since nannou's ```model``` function is a plain fn pointer and can't capture the parameters.
+static CONFIG_PARAMS: OnceLock<ConfigParams> = OnceLock::new();
+
+// Population log handed from ```start``` to every engine ```build_engine``` makes, kept apart since ConfigParams is Copy.
+static POPULATION_LOG: OnceLock<PopulationLog> = OnceLock::new();
+
+// Control port server the commands are polled from in ```update```, see ```apply_command```.
+static CONTROL_SERVER: OnceLock<ControlServer> = OnceLock::new();
+
+impl Default for ConfigParams {
+    /// Same defaults as the CLI
+    fn default() -> ConfigParams {
+        ConfigParams {
+            file_names: &[""],
+            ascii_cells: None,
+            load_options: conway_engine::LoadOptions::default(),
+            pattern: "",
+            number_of_steps: 20,
+            update_rate: 1.0,
+            height: 768.0,
+            width: 1024.0,
+            cell_size: None,
+            alive_color: BLACK,
+            dead_color: WHITE,
+            background: None,
+            enable_grid: false,
+            grid_color: SLATEGREY,
+            grid_thickness: 1.0,
+            square_cells: false,
+            cell_shape: CellShape::Square,
+            outline_cells: false,
+            show_fps: false,
+            hud: None,
+            start_paused: false,
+            pause_on_blur: false,
+            boundary: conway_engine::BoundaryCondition::Dead,
+            infinite: false,
+            neighborhood: conway_engine::Neighborhood::Moore,
+            radius: None,
+            backend: conway_engine::Backend::Dense,
+            rotation: conway_engine::Rotation::None,
+            flip: None,
+            detect_cycle: 0,
+            rule: None,
+            save_on_exit: "",
+            save_format: conway_engine::SaveFormat::Native,
+            species: false,
+            max_age: 0,
+            old_color: RED,
+            dying_color: None,
+            random_density: None,
+            seed: None,
+            random_size: (48, 64),
+        }
+    }
+}
+
+impl ConfigParams {
+    /// Copy the configuration the window runs with out of the resolved CLI options.
+    /// # NOTE
+    /// The strings are leaked, the GUI is up for the rest of the program and nannou's ```model``` needs them 'static.
+    /// # Params
+    /// config: &Config, effective configuration, see ```Config::from_matches```
+    /// # Returns
+    /// ConfigParams, parameters for ```GUI::model```
+    fn from_config(config: &Config) -> ConfigParams {
+        let file_names: Vec<&'static str> = config
+            .file_locations
+            .iter()
+            .map(|file_name| &*Box::leak(file_name.clone().into_boxed_str()))
+            .collect();
+        ConfigParams {
+            // without a file the default pattern is loaded
+            file_names: if file_names.is_empty() {
+                &[""]
+            } else {
+                Box::leak(file_names.into_boxed_slice())
+            },
+            ascii_cells: config.ascii_cells,
+            load_options: config.load_options(),
+            pattern: Box::leak(config.pattern.clone().into_boxed_str()),
+            number_of_steps: config.number_of_steps,
+            update_rate: config.update_rate,
+            height: config.height,
+            width: config.width,
+            cell_size: config.cell_size,
+            alive_color: config.alive_color,
+            dead_color: config.dead_color,
+            background: config.background,
+            enable_grid: config.enable_grid,
+            grid_color: config.grid_color,
+            grid_thickness: config.grid_thickness,
+            square_cells: config.square_cells,
+            cell_shape: config.cell_shape,
+            outline_cells: config.outline_cells,
+            show_fps: config.show_fps,
+            hud: config.hud,
+            start_paused: config.start_paused,
+            pause_on_blur: config.pause_on_blur,
+            boundary: config.boundary,
+            infinite: config.infinite,
+            neighborhood: config.neighborhood,
+            radius: config.radius,
+            backend: config.backend,
+            rotation: config.rotation,
+            flip: config.flip,
+            detect_cycle: config.detect_cycle,
+            rule: config
+                .rule
+                .as_ref()
+                .map(|rule| &*Box::leak(rule.clone().into_boxed_str())),
+            save_on_exit: Box::leak(config.save_on_exit.clone().into_boxed_str()),
+            save_format: config.save_format,
+            species: config.species,
+            max_age: config.max_age,
+            old_color: config.old_color,
+            dying_color: config.dying_color,
+            random_density: config.random_density,
+            seed: config.seed,
+            random_size: (config.random_rows, config.random_columns),
+        }
+    }
+}
 
 struct Model {
     engine: conway_engine::ConwayEngine,
     window_height: f32,
     window_width: f32,
+    /// Time owed to the simulation, whole step intervals are taken out of it by ```drain_steps```
     time: Duration,
+    /// Time since the last frame that stepped, spread over its steps for the generation rate
+    since_step: Duration,
     params: ConfigParams,
     window_id: window::Id,
+    paused: bool,
+    view: ViewTransform,
+    drag_position: Option<(f32, f32)>,
+    /// Playback speed, the engine's update rate is divided by it
+    speed: f32,
+    /// Set once the end of simulation summary is printed, so it only prints once
+    summary_printed: bool,
+    /// Index of the running file in ```ConfigParams::file_names```
+    playlist_index: usize,
+    /// Draw the frame rate and generation rate overlay
+    show_fps: bool,
+    /// Draw the generation and population HUD
+    show_hud: bool,
+    /// Corner the HUD is drawn in, toggling the HUD on without --hud uses the top left
+    hud_corner: HudCorner,
+    /// Frames drawn per second
+    frame_rate: FpsCounter,
+    /// Generations stepped per second
+    generation_rate: FpsCounter,
+    /// Set while the window is unfocused with --pause-on-blur, kept apart from ```paused``` so focusing doesn't undo a manual pause
+    unfocused: bool,
+    /// Set after a step or input that changes the view, the frame isn't drawn again without it
+    redraw: RedrawFlag,
+    /// (row, column) of the keyboard editing cursor, None while the cursor is hidden
+    cursor: Option<(usize, usize)>,
+}
+
+/// Pixels the view moves for each arrow key press
+const PAN_STEP: f32 = 20.0;
+/// Outline of the keyboard editing cursor
+const CURSOR_COLOR: Srgb<u8> = DODGERBLUE;
+const CURSOR_THICKNESS: f32 = 2.0;
+/// Most frames written to a GIF, keeps long or non-stop runs from making huge files
+const MAX_GIF_FRAMES: usize = 500;
+/// Shortest and longest time the speed multiplier can take the time between steps to,
+/// an update rate already outside of them is left as it is, see ```clamp_rate```
+const MIN_STEP_INTERVAL: Duration = Duration::from_millis(16);
+const MAX_STEP_INTERVAL: Duration = Duration::from_secs(10);
+/// Most steps taken in one frame to catch up after a hitch, more would make the next frame late as well
+const MAX_STEPS_PER_FRAME: usize = 8;
+/// Number of samples in the rolling averages of the frame rate and generation rate
+const FPS_WINDOW: usize = 60;
+
+/// Width and height in pixels of the HUD text box
+const HUD_SIZE: (f32, f32) = (220.0, 50.0);
+/// Pixels between the HUD text box and the window edges
+const HUD_MARGIN: f32 = 10.0;
+
+/// Tracks whether what the window shows changed since the last frame was drawn.
+/// Nannou keeps the last frame's image, so a frame that draws nothing still shows the previous board.
+/// ```view``` only gets a shared reference to the model, so the flag is a Cell it can clear.
+struct RedrawFlag {
+    needs_redraw: Cell<bool>,
+}
+
+impl RedrawFlag {
+    /// Create a flag that is set, the first frame is always drawn.
+    /// # Returns
+    /// RedrawFlag, flag asking for a redraw
+    fn new() -> RedrawFlag {
+        RedrawFlag {
+            needs_redraw: Cell::new(true),
+        }
+    }
+
+    /// Ask for the next frame to be drawn, after a step or input that changes the view.
+    fn set(&self) {
+        self.needs_redraw.set(true);
+    }
+
+    /// Check if the frame has to be drawn and clear the flag, called once per frame by ```view```.
+    /// # Params
+    /// always: bool, flag indicating something on screen changes every frame (like the frame rate overlay)
+    /// # Returns
+    /// bool, true if the frame has to be drawn
+    fn take(&self, always: bool) -> bool {
+        self.needs_redraw.replace(false) || always
+    }
+}
+
+/// Rolling average of how many events (frames or generations) happen per second.
+struct FpsCounter {
+    /// Time between each of the most recent events, oldest first
+    samples: VecDeque<Duration>,
+    /// Max number of samples kept
+    window: usize,
+}
+
+impl FpsCounter {
+    /// Create an empty counter.
+    /// # Params
+    /// window: usize, number of most recent events averaged
+    /// # Returns
+    /// FpsCounter, counter with no samples
+    fn new(window: usize) -> FpsCounter {
+        FpsCounter {
+            samples: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    /// Record an event, the oldest sample is dropped once the window is full.
+    /// # Params
+    /// dt: Duration, time since the previous event
+    fn tick(&mut self, dt: Duration) {
+        if self.samples.len() >= self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(dt);
+    }
+
+    /// Average events per second over the window.
+    /// # Returns
+    /// f32, number of samples divided by their total time, 0.0 without samples
+    fn value(&self) -> f32 {
+        let total: Duration = self.samples.iter().sum();
+        if total.as_secs_f32() <= 0.0 {
+            return 0.0;
+        }
+        self.samples.len() as f32 / total.as_secs_f32()
+    }
 }
 
 impl GUI {
-    /// Start the GUI up with the given parameters.
+    /// Start the GUI up with the given configuration.
     /// # NOTE
     /// This function will take over the main thread calling it and not exit (it's running the GUI after all).
     /// # Params
-    /// - file_name: String, location of file to load
-    /// - number_of_steps: usize, number of steps for simulation to take; 0 is infinite
-    /// - update_rate: usize, in seconds how long between each simulation step
-    /// - height: u32, height of window GUI in pixels
-    /// - width: u32, width of window GUI in pixels
-    /// - alive_color: String, representation of the expected color of the living cells
-    /// - dead_color: String, representation of the expected color of the dead cells
-    /// - enable_grid: bool, flag indicating if the grid should be drawn
+    /// - config: &Config, effective configuration from the command line, see ```Config::from_matches```
+    /// - population_log: Option<PopulationLog>, log the population of every generation is written to, None to skip it
+    /// - control: Option<ControlServer>, server the control port commands are taken from, None to skip them
     pub fn start(
-        file_name: String,
-        number_of_steps: usize,
-        update_rate: usize,
-        height: f32,
-        width: f32,
-        alive_color: String,
-        dead_color: String,
-        enable_grid: bool,
+        config: &Config,
+        population_log: Option<PopulationLog>,
+        control: Option<ControlServer>,
     ) {
-        // Since the GUI application is static (we intend for the GUI to be up for the duration of the program), we need to copy
-        // the String to a String with a 'static lifetime
-        let copy_file_name: &'static str = Box::leak(file_name.into_boxed_str());
+        let params = ConfigParams::from_config(config);
 
-        let alive_color = match named::from_str(&alive_color) {
-            Some(color) => color,
-            None => BLACK,
-        };
+        // a grid can only grow at its edges if the cells off the grid are dead
+        if params.infinite && params.boundary != conway_engine::BoundaryCondition::Dead {
+            eprintln!("The --infinite option only works with the dead boundary");
+            std::process::exit(1);
+        }
 
-        let dead_color = match named::from_str(&dead_color) {
-            Some(color) => color,
-            None => WHITE,
-        };
+        // validate the random board before the window opens
+        if let Some(density) = params.random_density {
+            if let Err(err) = conway_engine::generate_random_grid(
+                params.random_size.0,
+                params.random_size.1,
+                density,
+                params.seed,
+                params.load_options.max_cells,
+            ) {
+                eprintln!("Invalid random board: {}", err);
+                std::process::exit(1);
+            }
+        }
 
-        // Updating static data for model access
-        unsafe {
-            GLOBAL_PARAMS.file_name = &copy_file_name;
-            GLOBAL_PARAMS.number_of_steps = number_of_steps;
-            GLOBAL_PARAMS.update_rate = update_rate;
-            GLOBAL_PARAMS.height = height;
-            GLOBAL_PARAMS.width = width;
-            GLOBAL_PARAMS.alive_color = alive_color;
-            GLOBAL_PARAMS.dead_color = dead_color;
-            GLOBAL_PARAMS.enable_grid = enable_grid;
+        // validate the files before the window opens, a bad file later in the playlist would close it mid-run
+        if params.random_density.is_none() && params.pattern.is_empty() {
+            for file_name in params.file_names {
+                if let Err(err) = GUI::load_file(
+                    file_name,
+                    params.ascii_cells,
+                    params.load_options,
+                    params.height,
+                    params.width,
+                    params.update_rate,
+                    params.number_of_steps,
+                ) {
+                    eprintln!("Invalid file '{}': {}", file_name, err);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        // validate the rule before the window opens
+        if let Some(rule) = params.rule {
+            if let Err(err) = rule.parse::<conway_rust::Ruleset>() {
+                eprintln!("Invalid rule '{}': {}", rule, err);
+                std::process::exit(1);
+            }
+        }
+
+        // with a cell size the window fits the starting grid, built the same way the model builds it
+        let (width, height) = match params.cell_size {
+            Some(cell_size) => window_size(
+                GUI::build_engine(&params, 0).get_grid_dimensions(),
+                cell_size,
+            ),
+            None => (params.width, params.height),
+        };
+        if CONFIG_PARAMS.set(params).is_err() {
+            eprintln!("The GUI can only be started once");
+            std::process::exit(1);
+        }
+        if let Some(log) = population_log {
+            // set along with the parameters above, it can't be set already
+            let _ = POPULATION_LOG.set(log);
+        }
+        if let Some(server) = control {
+            let _ = CONTROL_SERVER.set(server);
         }
 
         // start the GUI application
         nannou::app(GUI::model)
             .size(width as u32, height as u32)
             .update(GUI::update)
+            .exit(GUI::exit)
             .run();
     }
 
-    /// Create the model for the Nannou GUI.  This will also read in the GLOBAL_PARAMS static mut config object.
-    /// Static global config object is needed to feed in data from the CLI options entered during start up.
-    /// # NOTE
-    /// ```unsafe``` code is used in this function to get the static mut global configuration object.
+    /// Write a run to an animated GIF without opening a window, one frame per generation.
+    /// Frames are rendered like the PNG snapshots, see ```render_to_gif```.
+    /// # Params
+    /// - engine: conway_engine::ConwayEngine, engine to run, its update rate sets the frame delay
+    /// - path: &str, location of the GIF to write, an existing file is overwritten
+    /// - size: (f32, f32), (width, height) of the frames in pixels, the engine is resized to match
+    /// - alive_color: Srgb<u8>, color of the living cells
+    /// - dead_color: Srgb<u8>, color of the dead cells
+    /// - grid_style: Option<(Srgb<u8>, f32)>, color and thickness in pixels of the grid lines, None to leave them out
+    /// - heat_map: (usize, Srgb<u8>), max age and old color of the heat map, an age of 0 disables it
+    /// - species: bool, flag indicating if living cells hold a species id instead of their age
+    /// # Returns
+    /// i32, exit code, 0 if the GIF was written and 1 otherwise
+    #[allow(clippy::too_many_arguments)]
+    pub fn export_gif(
+        mut engine: conway_engine::ConwayEngine,
+        path: &str,
+        size: (f32, f32),
+        alive_color: Srgb<u8>,
+        dead_color: Srgb<u8>,
+        grid_style: Option<(Srgb<u8>, f32)>,
+        heat_map: (usize, Srgb<u8>),
+        species: bool,
+    ) -> i32 {
+        // the cell spacing comes from the engine, so it has to be rendered at the frame size
+        engine.set_dimensions(size.0, size.1);
+        let defaults = ConfigParams::default();
+        let params = ConfigParams {
+            width: size.0,
+            height: size.1,
+            alive_color,
+            dead_color,
+            enable_grid: grid_style.is_some(),
+            grid_color: grid_style.map_or(defaults.grid_color, |style| style.0),
+            grid_thickness: grid_style.map_or(defaults.grid_thickness, |style| style.1),
+            max_age: heat_map.0,
+            old_color: heat_map.1,
+            species,
+            ..defaults
+        };
+        match render_to_gif(&mut engine, &params, path) {
+            Ok(frames) => {
+                if !engine.is_simulation_ended() {
+                    eprintln!(
+                        "Stopped the GIF at {} frames, the simulation was still running",
+                        MAX_GIF_FRAMES
+                    );
+                }
+                println!("Saved {} frames to {}", frames, path);
+                0
+            }
+            Err(err) => {
+                eprintln!("Failed to save the GIF to {}: {}", path, err);
+                1
+            }
+        }
+    }
+
+    /// Create the model for the Nannou GUI.  This will also read in the CONFIG_PARAMS set by ```start```.
+    /// The config object is needed to feed in data from the CLI options entered during start up.
     /// # Params
     /// app: &App, reference to the Nannou App object (primary object that represents the GUI)
     /// # Returns
     /// Model, Model object that contains the business state of the GUI.
     fn model(app: &App) -> Model {
-        // }:)  unsafe saves the day, since GLOBAL_PARMS or its mutable data might be garbage
-        // NOTE:  Feel like I'm making a noob mistake having to declare unsafe here
-        unsafe {
-            // setup the game
-            let engine = conway_engine::ConwayEngine::new(
-                &GLOBAL_PARAMS.file_name.to_string(),
-                GLOBAL_PARAMS.height,
-                GLOBAL_PARAMS.width,
-                GLOBAL_PARAMS.update_rate,
-                GLOBAL_PARAMS.number_of_steps,
-            );
+        let params = *CONFIG_PARAMS
+            .get()
+            .expect("GUI::start sets the parameters before the model is built");
 
-            // generate the window title
-            let name = engine.get_title_string();
-
-            // add a window to the view
-            let id = app
-                .new_window()
-                .title(name)
-                .view(GUI::view)
-                .resizable(false)
-                .build()
-                .unwrap();
-
-            // try to load icon
-            let icon = GUI::load_icon("icons/main-icon.png".to_string());
-            if  icon.is_ok() {
-                app.window(id).unwrap().set_window_icon(Some(icon.unwrap()));
-            }
+        // setup the game
+        let mut engine = GUI::build_engine(&params, 0);
+        let (window_width, window_height) = match params.cell_size {
+            Some(cell_size) => window_size(engine.get_grid_dimensions(), cell_size),
+            None => (params.width, params.height),
+        };
+        engine.set_dimensions(window_width, window_height);
 
-            // return the model
-            Model {
-                engine,
-                window_height: GLOBAL_PARAMS.height,
-                window_width: GLOBAL_PARAMS.width,
-                time: Duration::new(0, 0),
-                params: GLOBAL_PARAMS,
-                window_id: id,
-            }
+        // generate the window title
+        let name = format_title(&engine, 1.0, params.start_paused, false);
+
+        // add a window to the view
+        let id = app
+            .new_window()
+            .title(name)
+            .view(GUI::view)
+            .mouse_pressed(GUI::mouse_pressed)
+            .mouse_released(GUI::mouse_released)
+            .mouse_moved(GUI::mouse_moved)
+            .mouse_wheel(GUI::mouse_wheel)
+            .key_pressed(GUI::key_pressed)
+            .focused(GUI::focused)
+            .unfocused(GUI::unfocused)
+            .resized(GUI::resized)
+            .build()
+            .unwrap();
+
+        // try to load icon
+        if let Ok(icon) = GUI::load_icon("icons/main-icon.png".to_string()) {
+            app.window(id).unwrap().set_window_icon(Some(icon));
+        }
+
+        // return the model
+        Model {
+            engine,
+            window_height,
+            window_width,
+            time: Duration::new(0, 0),
+            since_step: Duration::new(0, 0),
+            params,
+            window_id: id,
+            paused: params.start_paused,
+            view: ViewTransform::identity(),
+            drag_position: None,
+            speed: 1.0,
+            summary_printed: false,
+            playlist_index: 0,
+            show_fps: params.show_fps,
+            show_hud: params.hud.is_some(),
+            hud_corner: params.hud.unwrap_or_default(),
+            frame_rate: FpsCounter::new(FPS_WINDOW),
+            generation_rate: FpsCounter::new(FPS_WINDOW),
+            unfocused: false,
+            redraw: RedrawFlag::new(),
+            cursor: None,
         }
     }
 
+    /// Build a fresh engine from the configuration parameters.
+    /// Used on start up and when the board is reset, an unseeded random board is different after every reset.
+    /// # Params
+    /// - params: &ConfigParams, configuration from the CLI
+    /// - playlist_index: usize, index of the file to load in ```ConfigParams::file_names```
+    /// # Returns
+    /// conway_engine::ConwayEngine, engine in its initial state
+    fn build_engine(params: &ConfigParams, playlist_index: usize) -> conway_engine::ConwayEngine {
+        let mut engine = match params.random_density {
+            Some(density) => conway_engine::ConwayEngine::new_random(
+                params.random_size.0,
+                params.random_size.1,
+                density,
+                params.seed,
+                params.height,
+                params.width,
+                params.update_rate,
+                params.number_of_steps,
+                params.load_options.max_cells,
+            )
+            .expect("Random board was validated in start"),
+            None if !params.pattern.is_empty() => conway_engine::ConwayEngine::from_pattern(
+                params.pattern,
+                params.height,
+                params.width,
+                params.update_rate,
+                params.number_of_steps,
+            )
+            .expect("Pattern was validated during parsing"),
+            None => GUI::load_file(
+                params.file_names[playlist_index],
+                params.ascii_cells,
+                params.load_options,
+                params.height,
+                params.width,
+                params.update_rate,
+                params.number_of_steps,
+            )
+            .expect("File was validated in start"),
+        };
+        engine.transform(params.rotation, params.flip);
+        engine.set_boundary(params.boundary);
+        engine.set_infinite(params.infinite);
+        engine.set_max_cells(params.load_options.max_cells);
+        engine.set_species(params.species);
+        engine.set_neighborhood(params.neighborhood);
+        // like the rule, the radius given on the command line wins over the file's radius
+        if let Some(radius) = params.radius {
+            engine.set_radius(radius);
+        }
+        engine.set_backend(params.backend);
+        // the rule given on the command line wins over the file's rule, which wins over B3/S23
+        if let Some(rule) = params.rule {
+            engine.set_ruleset(rule.parse().expect("Rule was validated in start"));
+        }
+        engine.set_cycle_detection(params.detect_cycle);
+        if let Some(log) = POPULATION_LOG.get() {
+            log.attach(&mut engine);
+        }
+        engine
+    }
+
+    /// Load a pattern file, as ASCII art when the characters are given and by its extension otherwise.
+    /// # Params
+    /// - file_name: &str, location of the file, a missing file loads the default pattern unless it's ASCII art
+    /// - ascii_cells: Option<(char, char)>, (alive, dead) characters of the ASCII art, see ```conway_engine::parse_ascii```
+    /// - load_options: conway_engine::LoadOptions, checks applied to files that aren't ASCII art
+    /// - height: f32, height of the window in pixels
+    /// - width: f32, width of the window in pixels
+    /// - update_rate: f64, seconds between steps if the file doesn't set it
+    /// - number_of_steps: usize, number of steps if the file doesn't set it, 0 is infinite
+    /// # Returns
+    /// Result<conway_engine::ConwayEngine, String>, the engine or an error if the file is invalid
+    pub fn load_file(
+        file_name: &str,
+        ascii_cells: Option<(char, char)>,
+        load_options: conway_engine::LoadOptions,
+        height: f32,
+        width: f32,
+        update_rate: f64,
+        number_of_steps: usize,
+    ) -> Result<conway_engine::ConwayEngine, String> {
+        let file_name = file_name.to_string();
+        match ascii_cells {
+            Some(cells) => conway_engine::ConwayEngine::try_load_ascii(
+                &file_name,
+                cells,
+                height,
+                width,
+                update_rate,
+                number_of_steps,
+                load_options,
+            ),
+            None => conway_engine::ConwayEngine::try_new(
+                &file_name,
+                height,
+                width,
+                update_rate,
+                number_of_steps,
+                load_options,
+            ),
+        }
+    }
+
+    /// Build the engine for the file after the running one, for when the running simulation ends.
+    /// Bundled patterns and random boards aren't played from the file list, so they have no next engine.
+    /// # Params
+    /// - params: &ConfigParams, configuration from the CLI
+    /// - playlist_index: usize, index of the running file in ```ConfigParams::file_names```
+    /// # Returns
+    /// Option<(usize, conway_engine::ConwayEngine)>, index and engine of the next file, None after the last file
+    fn next_in_playlist(
+        params: &ConfigParams,
+        playlist_index: usize,
+    ) -> Option<(usize, conway_engine::ConwayEngine)> {
+        let next_index = playlist_index + 1;
+        if params.random_density.is_some()
+            || !params.pattern.is_empty()
+            || next_index >= params.file_names.len()
+        {
+            return None;
+        }
+        Some((next_index, GUI::build_engine(params, next_index)))
+    }
+
     fn update(app: &App, model: &mut Model, _update: Update) {
-        // use _update.since_last as how long it has been since last step
+        // update runs once per frame
+        model.frame_rate.tick(_update.since_last);
+
+        // commands from the control port are applied even while paused
+        if let Some(server) = CONTROL_SERVER.get() {
+            while let Some(request) = server.next_request() {
+                let reply = GUI::apply_command(model, request.command);
+                request.reply(reply);
+                GUI::update_title(app, model);
+            }
+        }
+
+        // nothing moves while paused, the time isn't accumulated either so there's no burst of steps on resume
+        if model.paused || model.unfocused {
+            return;
+        }
+
+        // take every step owed since the last frame, so a slow frame doesn't lose generations
         model.time += _update.since_last;
-        if model.time > model.engine.get_update_rate_duration() {
-            model.engine.take_step();
-            model.time = Duration::new(0, 0);
-
-            // update the window title if the simulation has eneded
-            if model.engine.is_simulation_ended() {
-                app.window(model.window_id)
-                    .unwrap()
-                    .set_title(&model.engine.get_title_string());
-            } else if model.engine.is_simulation_non_stop() {
-                app.window(model.window_id)
-                    .unwrap()
-                    .set_title(&model.engine.get_title_string());
+        model.since_step += _update.since_last;
+        let interval = clamp_rate(model.engine.get_update_rate_duration(), model.speed);
+        let (steps, remaining) = drain_steps(model.time, interval, MAX_STEPS_PER_FRAME);
+        model.time = remaining;
+        if steps > 0 {
+            // an ended simulation doesn't step, so there's nothing new to draw
+            let taken = model.engine.step_many(steps);
+            if taken > 0 {
+                model.redraw.set();
+            }
+            for _ in 0..taken {
+                model.generation_rate.tick(model.since_step / taken as u32);
+            }
+            model.since_step = Duration::new(0, 0);
+
+            // the title shows the generation, refresh it every step
+            GUI::update_title(app, model);
+
+            if model.engine.is_simulation_ended() && !model.summary_printed {
+                println!("{}", model.engine.summary());
+                model.summary_printed = true;
+                GUI::flush_population_log();
+
+                // move on to the next file, the last file stays up once it ends
+                if let Some((index, engine)) =
+                    GUI::next_in_playlist(&model.params, model.playlist_index)
+                {
+                    model.playlist_index = index;
+                    model.engine = engine;
+                    model
+                        .engine
+                        .set_dimensions(model.window_width, model.window_height);
+                    model.summary_printed = false;
+                    model.redraw.set();
+                    GUI::update_title(app, model);
+                }
             }
         };
     }
 
+    /// Apply a command sent to the control port, like the matching key would.
+    /// # Params
+    /// - model: &mut Model, model holding the engine
+    /// - command: Command, command to apply
+    /// # Returns
+    /// String, text sent back to the client, the board for a dump and empty otherwise
+    fn apply_command(model: &mut Model, command: Command) -> String {
+        model.redraw.set();
+        match command {
+            Command::Step => model.engine.take_step(),
+            Command::Pause | Command::Resume => {
+                model.paused = command == Command::Pause;
+                model.time = Duration::new(0, 0);
+            }
+            Command::Reset => GUI::reset(model),
+            Command::Dump => return format_dump(&model.engine),
+        }
+        String::new()
+    }
+
+    /// Rebuild the board from its file, pattern or random density, the view and speed are kept.
+    /// # Params
+    /// model: &mut Model, model holding the engine
+    fn reset(model: &mut Model) {
+        model.engine = GUI::build_engine(&model.params, model.playlist_index);
+        model
+            .engine
+            .set_dimensions(model.window_width, model.window_height);
+        model.time = Duration::new(0, 0);
+        model.summary_printed = false;
+    }
+
+    /// Keep the board filling the window after it is resized, the cells stretch (or stay square) to the new size.
+    /// # Params
+    /// - _app: &App, reference to the Nannou App object
+    /// - model: &mut Model, model holding the window size and the engine
+    /// - size: Vector2, new (width, height) of the window in pixels
+    fn resized(_app: &App, model: &mut Model, size: Vector2) {
+        model.redraw.set();
+        model.window_width = size.x;
+        model.window_height = size.y;
+        model.engine.set_dimensions(size.x, size.y);
+    }
+
+    /// Resume the simulation once the window is focused again, only when --pause-on-blur paused it.
+    /// # Params
+    /// - app: &App, reference to the Nannou App object
+    /// - model: &mut Model, model holding the pause state
+    fn focused(app: &App, model: &mut Model) {
+        if !model.unfocused {
+            return;
+        }
+        model.unfocused = false;
+        // start the next step from scratch, the frame gap while unfocused doesn't count
+        model.time = Duration::new(0, 0);
+        GUI::update_title(app, model);
+    }
+
+    /// Pause the simulation while the window is unfocused, if --pause-on-blur is on.
+    /// # Params
+    /// - app: &App, reference to the Nannou App object
+    /// - model: &mut Model, model holding the pause state
+    fn unfocused(app: &App, model: &mut Model) {
+        if !model.params.pause_on_blur {
+            return;
+        }
+        model.unfocused = true;
+        GUI::update_title(app, model);
+    }
+
+    /// Write out the rest of the population log, if there is one.
+    fn flush_population_log() {
+        if let Some(Err(err)) = POPULATION_LOG.get().map(PopulationLog::flush) {
+            eprintln!("Failed to write the population log: {}", err);
+        }
+    }
+
+    /// Called once the window closes, flushes the population log and saves the board if a save location was given.
+    /// The process exits with the code for how the playing file ended, see ```engine_exit_code```.
+    /// # Params
+    /// - _app: &App, reference to the Nannou App object
+    /// - model: Model, final state of the GUI
+    fn exit(_app: &App, model: Model) {
+        GUI::flush_population_log();
+        if !model.params.save_on_exit.is_empty() {
+            match model
+                .engine
+                .save_as(model.params.save_on_exit, model.params.save_format)
+            {
+                Ok(()) => println!("Saved board to {}", model.params.save_on_exit),
+                Err(err) => eprintln!(
+                    "Failed to save board to {}: {}",
+                    model.params.save_on_exit, err
+                ),
+            }
+        }
+        std::process::exit(engine_exit_code(&model.engine));
+    }
+
+    /// Multiply the playback speed, the multiplier can't take the time between steps past MIN_STEP_INTERVAL or MAX_STEP_INTERVAL.
+    /// # Params
+    /// - model: &mut Model, model holding the speed multiplier
+    /// - factor: f32, amount to multiply the speed by, 2.0 steps twice as often
+    fn change_speed(model: &mut Model, factor: f32) {
+        let base = model.engine.get_update_rate_duration();
+        let interval = clamp_rate(base, model.speed * factor);
+        // keep the multiplier in line with the clamped interval, so pressing the opposite key undoes it
+        model.speed = base.as_secs_f32() / interval.as_secs_f32();
+    }
+
+    /// Save the board to a PNG in the working directory, named after the generation so snapshots don't overwrite each other.
+    /// # Params
+    /// model: &Model, model holding the engine and colors
+    fn save_snapshot(model: &Model) {
+        let path = format!("conway-gen-{}.png", model.engine.get_generation());
+        // the window may have been resized since start up
+        let params = ConfigParams {
+            width: model.window_width,
+            height: model.window_height,
+            ..model.params
+        };
+        match render_to_png(&model.engine, &params, &path) {
+            Ok(()) => println!("Saved snapshot to {}", path),
+            Err(err) => eprintln!("Failed to save snapshot to {}: {}", path, err),
+        }
+    }
+
+    /// Set the window title from the engine state, while paused the title also lists the keyboard controls.
+    /// # Params
+    /// - app: &App, reference to the Nannou App object
+    /// - model: &Model, model holding the engine and window id
+    fn update_title(app: &App, model: &Model) {
+        let title = format_title(&model.engine, model.speed, model.paused, model.unfocused);
+        app.window(model.window_id).unwrap().set_title(&title);
+    }
+
+    /// Keyboard controls:
+    /// - Space: pause or resume the simulation
+    /// - S: take a single step while paused
+    /// - R: reset the board to its initial pattern and step count
+    /// - Arrow keys: pan the view
+    /// - V: reset the view, no pan or zoom
+    /// - P: save a PNG snapshot of the board
+    /// - +/-: double or halve the playback speed
+    /// - F: show or hide the frame rate and generation rate
+    /// - H: show or hide the generation and population HUD
+    /// - D: print the board to stdout, see ```format_dump```
+    /// - C: show or hide the keyboard editing cursor, see ```cursor_key_pressed```
+    /// # Params
+    /// - app: &App, reference to the Nannou App object
+    /// - model: &mut Model, model holding the engine
+    /// - key: Key, key that was pressed
+    fn key_pressed(app: &App, model: &mut Model, key: Key) {
+        // most keys change the board, the view or an overlay
+        model.redraw.set();
+        if GUI::cursor_key_pressed(model, key) {
+            GUI::update_title(app, model);
+            return;
+        }
+        match key {
+            Key::Space => {
+                model.paused = !model.paused;
+                model.time = Duration::new(0, 0);
+            }
+            Key::S => {
+                if !model.paused {
+                    return;
+                }
+                model.engine.take_step();
+            }
+            Key::R => GUI::reset(model),
+            Key::Left => model.view.pan(PAN_STEP, 0.0),
+            Key::Right => model.view.pan(-PAN_STEP, 0.0),
+            Key::Up => model.view.pan(0.0, -PAN_STEP),
+            Key::Down => model.view.pan(0.0, PAN_STEP),
+            Key::V => model.view = ViewTransform::identity(),
+            Key::P => {
+                GUI::save_snapshot(model);
+                return;
+            }
+            Key::D => {
+                print!("{}", format_dump(&model.engine));
+                return;
+            }
+            Key::F => {
+                model.show_fps = !model.show_fps;
+                return;
+            }
+            Key::H => {
+                model.show_hud = !model.show_hud;
+                return;
+            }
+            Key::C => {
+                // the cursor starts in the middle of the board
+                let (row_count, column_count) = model.engine.get_grid_dimensions();
+                model.cursor = match model.cursor {
+                    Some(_) => None,
+                    None => Some((row_count / 2, column_count / 2)),
+                };
+                return;
+            }
+            Key::Equals | Key::Add => GUI::change_speed(model, 2.0),
+            Key::Minus | Key::Subtract => GUI::change_speed(model, 0.5),
+            _ => return,
+        }
+        GUI::update_title(app, model);
+    }
+
+    /// Keyboard editing while the cursor is shown, takes over the keys the cursor needs from panning, stepping and dumping.
+    /// - Arrow keys or WASD: move the cursor a cell, it stops at the edges of the grid
+    /// - Enter: toggle the cell under the cursor, only while paused like a left click
+    /// # Params
+    /// - model: &mut Model, model holding the engine and cursor
+    /// - key: Key, key that was pressed
+    /// # Returns
+    /// bool, true if the cursor handled the key
+    fn cursor_key_pressed(model: &mut Model, key: Key) -> bool {
+        let cursor = match model.cursor {
+            Some(cursor) => cursor,
+            None => return false,
+        };
+        let dimensions = model.engine.get_grid_dimensions();
+        let delta = match key {
+            Key::Up | Key::W => (-1, 0),
+            Key::Down | Key::S => (1, 0),
+            Key::Left | Key::A => (0, -1),
+            Key::Right | Key::D => (0, 1),
+            Key::Return | Key::NumpadEnter => {
+                // the grid can shrink under the cursor on a reset
+                let (row_index, column_index) = move_cursor(cursor, (0, 0), dimensions);
+                if model.paused {
+                    model
+                        .engine
+                        .toggle_cell(row_index, column_index)
+                        .expect("Failed to toggle cell");
+                }
+                return true;
+            }
+            _ => return false,
+        };
+        model.cursor = Some(move_cursor(cursor, delta, dimensions));
+        true
+    }
+
+    /// Toggle the cell under the mouse cursor.  Cells can only be edited while the simulation is paused,
+    /// clicks outside of the grid are ignored.  Holding the right button drags the view.
+    /// # Params
+    /// - app: &App, reference to the Nannou App object, has the mouse position
+    /// - model: &mut Model, model holding the engine to edit
+    /// - button: MouseButton, button that was pressed, left clicks edit cells, right clicks start a drag
+    fn mouse_pressed(app: &App, model: &mut Model, button: MouseButton) {
+        if button == MouseButton::Right {
+            model.drag_position = Some((app.mouse.x, app.mouse.y));
+            return;
+        }
+        if button != MouseButton::Left || !model.paused {
+            return;
+        }
+
+        let (spacing, board) = GUI::get_layout(model);
+        if let Some((row_index, column_index)) = screen_to_grid(
+            app.mouse.x,
+            app.mouse.y,
+            spacing,
+            board,
+            model.engine.get_grid_dimensions(),
+            &model.view,
+        ) {
+            model
+                .engine
+                .toggle_cell(row_index, column_index)
+                .expect("Failed to toggle cell");
+            model.redraw.set();
+        }
+    }
+
+    /// Stop dragging the view once the right button is let go.
+    /// # Params
+    /// - _app: &App, reference to the Nannou App object
+    /// - model: &mut Model, model holding the drag state
+    /// - button: MouseButton, button that was released
+    fn mouse_released(_app: &App, model: &mut Model, button: MouseButton) {
+        if button == MouseButton::Right {
+            model.drag_position = None;
+        }
+    }
+
+    /// Pan the view by how far the mouse moved while dragging.
+    /// # Params
+    /// - _app: &App, reference to the Nannou App object
+    /// - model: &mut Model, model holding the view and drag state
+    /// - position: Point2, new mouse position
+    fn mouse_moved(_app: &App, model: &mut Model, position: Point2) {
+        if let Some((last_x, last_y)) = model.drag_position {
+            model.view.pan(position.x - last_x, position.y - last_y);
+            model.drag_position = Some((position.x, position.y));
+            model.redraw.set();
+        }
+    }
+
+    /// Zoom the view around the mouse cursor with the scroll wheel.
+    /// # Params
+    /// - app: &App, reference to the Nannou App object, has the mouse position
+    /// - model: &mut Model, model holding the view
+    /// - delta: MouseScrollDelta, how far the wheel was scrolled
+    /// - _phase: TouchPhase, unused
+    fn mouse_wheel(app: &App, model: &mut Model, delta: MouseScrollDelta, _phase: TouchPhase) {
+        let lines = match delta {
+            MouseScrollDelta::LineDelta(_x, y) => y,
+            // touchpads report pixels, treat ~20 pixels as one wheel notch
+            MouseScrollDelta::PixelDelta(position) => position.y as f32 / 20.0,
+        };
+        model
+            .view
+            .zoom(1.1_f32.powf(lines), (app.mouse.x, app.mouse.y));
+        model.redraw.set();
+    }
+
+    /// Draw the board and overlays, skipped when nothing changed since the last frame (see ```RedrawFlag```).
+    /// The frame rate overlay changes every frame, so the window is always drawn while it's shown.
     fn view(app: &App, model: &Model, frame: Frame) {
+        if !model.redraw.take(model.show_fps) {
+            return;
+        }
         // get canvas to draw on
         let draw = app.draw();
 
-        // set background to blue
-        draw.background().color(model.params.dead_color);
+        draw.background().color(background_color(&model.params));
 
         // Draw the scene
         GUI::draw_scene(model, &draw);
@@ -184,70 +1127,231 @@ impl GUI {
         if model.params.enable_grid {
             GUI::draw_grid(model, &draw);
         }
+        if model.show_fps {
+            GUI::draw_fps(model, &draw);
+        }
+        if let Some(cursor) = model.cursor {
+            GUI::draw_cursor(model, &draw, cursor);
+        }
+        if model.show_hud {
+            GUI::draw_hud(model, &draw);
+        }
 
         // put everything on the frame
         draw.to_frame(app, &frame).unwrap();
     }
 
-    /// Draws cells based on if they are > 1
+    /// Outline the cell under the keyboard editing cursor, on top of the cells and the grid lines.
+    /// # Params
+    /// - model: &Model, model holding the engine and view
+    /// - draw: &Draw, reference for drawing objects to the screen
+    /// - cursor: (usize, usize), (row, column) of the cursor, pulled back onto the grid if it shrank
+    fn draw_cursor(model: &Model, draw: &Draw, cursor: (usize, usize)) {
+        let (row_index, column_index) =
+            move_cursor(cursor, (0, 0), model.engine.get_grid_dimensions());
+        let (x, y) = GUI::convert_coordinates(row_index, column_index, model);
+        let (row_width, column_width) = GUI::get_view_spacing(model);
+        draw.rect()
+            .no_fill()
+            .stroke(CURSOR_COLOR)
+            .stroke_weight(CURSOR_THICKNESS)
+            .w_h(row_width, column_width)
+            .x_y(x, y);
+    }
+
+    /// Draw the frame rate and generation rate in the top left corner of the window.
+    fn draw_fps(model: &Model, draw: &Draw) {
+        let text = format!(
+            "{:.0} fps, {:.1} gen/s",
+            model.frame_rate.value(),
+            model.generation_rate.value()
+        );
+        draw.text(&text)
+            .color(model.params.alive_color)
+            .font_size(14)
+            .w_h(model.window_width - 20.0, 20.0)
+            .x_y(0.0, model.window_height / 2.0 - 15.0)
+            .left_justify()
+            .align_text_top();
+    }
+
+    /// Draw the generation, population and time per step in the HUD corner, justified towards the window edge.
+    fn draw_hud(model: &Model, draw: &Draw) {
+        let interval = clamp_rate(model.engine.get_update_rate_duration(), model.speed);
+        let text = format!(
+            "Generation {}\nPopulation {}\n{:.3}s per step",
+            model.engine.get_generation(),
+            model.engine.population(),
+            interval.as_secs_f32()
+        );
+        let (x, y) = hud_anchor(model.hud_corner, (model.window_width, model.window_height));
+        let text = draw
+            .text(&text)
+            .color(model.params.alive_color)
+            .font_size(14)
+            .w_h(HUD_SIZE.0, HUD_SIZE.1)
+            .x_y(x, y);
+        let text = match model.hud_corner {
+            HudCorner::TopLeft | HudCorner::BottomLeft => text.left_justify(),
+            HudCorner::TopRight | HudCorner::BottomRight => text.right_justify(),
+        };
+        match model.hud_corner {
+            HudCorner::TopLeft | HudCorner::TopRight => text.align_text_top(),
+            HudCorner::BottomLeft | HudCorner::BottomRight => text.align_text_bottom(),
+        };
+    }
+
+    /// Draws cells based on if they are > 1, colored by ```cell_color```.
+    /// With a separate background the dead cells are drawn too, otherwise the background already is the dead color.
+    /// With --outline-cells every cell's border is stroked first, the living cells are filled on top.
+    /// With --dying the cells that died in the last step are drawn in its color, cells dead for longer are skipped.
     fn draw_scene(model: &Model, draw: &Draw) {
-        let (row_width, column_width) = model.engine.get_grid_spacing();
-        let (row_count, column_count) = model.engine.get_grid_dimensions();
-        for row_number in 0..row_count {
-            for column_number in 0..column_count {
-                let (x, y) = GUI::convert_coordinates(row_number, column_number, model);
-                if model.engine.get_cell(row_number, column_number) > 0 {
+        let (row_width, column_width) = GUI::get_view_spacing(model);
+        if model.params.outline_cells {
+            let color = outline_color(&model.params);
+            let (row_count, column_count) = model.engine.get_grid_dimensions();
+            for row_number in 0..row_count {
+                for column_number in 0..column_count {
+                    let position = GUI::convert_coordinates(row_number, column_number, model);
+                    let (x, y, w, h) = cell_rect(position, (row_width, column_width));
                     draw.rect()
-                        .color(model.params.alive_color)
-                        .w(row_width - 1.0)
-                        .h(column_width - 1.0)
-                        .x_y(x + 0.5, y + 0.5);
+                        .no_fill()
+                        .stroke(color)
+                        .stroke_weight(1.0)
+                        .w_h(w, h)
+                        .x_y(x, y);
+                }
+            }
+        }
+        if model.params.background.is_some() {
+            let (row_count, column_count) = model.engine.get_grid_dimensions();
+            for row_number in 0..row_count {
+                for column_number in 0..column_count {
+                    if model.engine.get_cell(row_number, column_number) == 0 {
+                        let (x, y) = GUI::convert_coordinates(row_number, column_number, model);
+                        GUI::draw_cell(
+                            draw,
+                            model.params.cell_shape,
+                            model.params.dead_color,
+                            (x, y),
+                            (row_width, column_width),
+                        );
+                    }
+                }
+            }
+        }
+        // cells that just died get one more generation in the dying color, a cell toggled back to life is skipped
+        if let Some(dying_color) = model.params.dying_color {
+            for &(row_number, column_number) in model.engine.dying_cells() {
+                if model.engine.get_cell(row_number, column_number) > 0 {
+                    continue;
                 }
+                let (x, y) = GUI::convert_coordinates(row_number, column_number, model);
+                GUI::draw_cell(
+                    draw,
+                    model.params.cell_shape,
+                    dying_color,
+                    (x, y),
+                    (row_width, column_width),
+                );
+            }
+        }
+        for (row_number, column_number, value) in model.engine.iter_live() {
+            let (x, y) = GUI::convert_coordinates(row_number, column_number, model);
+            GUI::draw_cell(
+                draw,
+                model.params.cell_shape,
+                cell_color(value, &model.params),
+                (x, y),
+                (row_width, column_width),
+            );
+        }
+    }
+
+    /// Draw a single cell, inset by a pixel so the cells don't run together.
+    /// # Params
+    /// - draw: &Draw, reference for drawing objects to the screen
+    /// - shape: CellShape, rectangle or ellipse
+    /// - color: Srgb<u8>, color of the cell
+    /// - position: (f32, f32), center of the cell on screen
+    /// - size: (f32, f32), width and height of the cell
+    fn draw_cell(
+        draw: &Draw,
+        shape: CellShape,
+        color: Srgb<u8>,
+        position: (f32, f32),
+        size: (f32, f32),
+    ) {
+        let (x, y, w, h) = cell_rect(position, size);
+        match shape {
+            CellShape::Square => {
+                draw.rect().color(color).w(w).h(h).x_y(x, y);
+            }
+            CellShape::Circle => {
+                draw.ellipse().color(color).w(w).h(h).x_y(x, y);
             }
         }
     }
 
-    /// Draw a grid on the display.  Color of gird is defaulted to ```SLATEGREY```.
+    /// Draw a grid on the display.  Color of gird is defaulted to ```SLATEGREY``` and the lines to 1 pixel, see ```grid_line_color```.
     /// # PARAMS
     /// - model: &Model, reference holding engine and window data
     /// - draw: &Draw, reference for drawing objects to the screen
     fn draw_grid(model: &Model, draw: &Draw) {
-        let grid_color = SLATEGREY;
-        let (lower_x, lower_y) = GUI::get_lower_window_coordinates(model);
+        let boundary = model.engine.get_boundary();
+        let grid_color = model.params.grid_color;
+        // lines get thicker with the zoom, like the cells
+        let thickness = model.params.grid_thickness * model.view.scale;
+        let (lower_x, lower_y) = model.view.apply(GUI::get_lower_board_coordinates(model));
+        // the board is always centered in the window, so its center is wherever the view moved the origin
+        let (center_x, center_y) = model.view.offset;
+        let (_spacing, (board_width, board_height)) = GUI::get_layout(model);
+        let grid_width = board_width * model.view.scale;
+        let grid_height = board_height * model.view.scale;
 
-        let (row_width, column_width) = model.engine.get_grid_spacing();
+        let (row_width, column_width) = GUI::get_view_spacing(model);
         let (row_count, column_count) = model.engine.get_grid_dimensions();
 
         // draw ROW grid lines
         let mut y_position = lower_y;
         draw.rect()
-            .color(grid_color)
-            .w(model.window_width)
-            .h(1.0)
-            .x_y(0.0, y_position + 0.5);
-        for _row_index in 0..row_count {
+            .color(grid_line_color(0, row_count, boundary, grid_color))
+            .w(grid_width)
+            .h(thickness)
+            .x_y(center_x, y_position + thickness / 2.0);
+        for row_index in 0..row_count {
             y_position += column_width;
             draw.rect()
-                .color(grid_color)
-                .w(model.window_width)
-                .h(1.0)
-                .x_y(0.0, y_position + 0.5);
+                .color(grid_line_color(
+                    row_index + 1,
+                    row_count,
+                    boundary,
+                    grid_color,
+                ))
+                .w(grid_width)
+                .h(thickness)
+                .x_y(center_x, y_position + thickness / 2.0);
         }
 
         // draw the COLUMN grid lines
         let mut x_position = lower_x;
         draw.rect()
-            .color(grid_color)
-            .w(1.0)
-            .h(model.window_height)
-            .x_y(x_position + 0.5, 0.0);
-        for _column_index in 0..column_count {
+            .color(grid_line_color(0, column_count, boundary, grid_color))
+            .w(thickness)
+            .h(grid_height)
+            .x_y(x_position + thickness / 2.0, center_y);
+        for column_index in 0..column_count {
             x_position += row_width;
             draw.rect()
-                .color(grid_color)
-                .w(1.0)
-                .h(model.window_height)
-                .x_y(x_position + 0.5, 0.0);
+                .color(grid_line_color(
+                    column_index + 1,
+                    column_count,
+                    boundary,
+                    grid_color,
+                ))
+                .w(thickness)
+                .h(grid_height)
+                .x_y(x_position + thickness / 2.0, center_y);
         }
     }
 
@@ -255,7 +1359,7 @@ impl GUI {
     /// Simple test function that will print out a red and black checkerboard.
     /// This is a flagged option allowing users to see individual cells if all are dead or alive.
     fn draw_checkerboard(model: &Model, draw: &Draw) {
-        let (row_width, column_width) = model.engine.get_grid_spacing();
+        let (row_width, column_width) = GUI::get_view_spacing(model);
         let (row_count, column_count) = model.engine.get_grid_dimensions();
         for row_number in 0..row_count {
             for column_number in 0..column_count {
@@ -279,7 +1383,7 @@ impl GUI {
         }
     }
 
-    /// Given the row and column index, calculate the center draw position.
+    /// Given the row and column index, calculate the center draw position with the current pan and zoom.
     /// Return float values as (X, Y).
     /// # Params
     /// - row_index: usize, row index in the grid
@@ -289,23 +1393,59 @@ impl GUI {
     /// # Returns
     /// - (f32, f32), (X, Y) screen coordinates for the given grid cell
     fn convert_coordinates(row_index: usize, column_index: usize, model: &Model) -> (f32, f32) {
-        let (lower_x, lower_y) = GUI::get_lower_window_coordinates(model);
+        let (spacing, board) = GUI::get_layout(model);
+        grid_to_screen(row_index, column_index, spacing, board, &model.view)
+    }
 
-        let (x_width, y_width) = model.engine.get_grid_spacing();
-        let coordinate_x = lower_x + (column_index as f32 * x_width + x_width / 2.0);
-        let coordinate_y = (-1.0 * lower_y) - (row_index as f32 * y_width + y_width / 2.0);
-        (coordinate_x, coordinate_y)
+    /// Size of a cell and of the whole board before the pan and zoom, the board is centered in the window.
+    /// With --square-cells the cells use the smaller spacing and the board leaves margins, otherwise it fills the window.
+    /// # Params
+    /// - model: &Model, model that contains the engine and window size
+    ///
+    /// # Returns
+    /// - ((f32, f32), (f32, f32)), ((X, Y) size of a cell, (width, height) of the board) in pixels
+    fn get_layout(model: &Model) -> ((f32, f32), (f32, f32)) {
+        if model.params.square_cells {
+            let (spacing, (x_offset, y_offset)) = model.engine.get_square_spacing_and_offset();
+            (
+                (spacing, spacing),
+                (
+                    model.window_width - 2.0 * x_offset,
+                    model.window_height - 2.0 * y_offset,
+                ),
+            )
+        } else {
+            (
+                model.engine.get_grid_spacing(),
+                (model.window_width, model.window_height),
+            )
+        }
+    }
+
+    /// Size of a cell on the screen with the current zoom.
+    /// # Params
+    /// - model: &Model, model that contains the engine and view transform
+    ///
+    /// # Returns
+    /// - (f32, f32), (X, Y) size of a cell in pixels
+    fn get_view_spacing(model: &Model) -> (f32, f32) {
+        let ((row_width, column_width), _board) = GUI::get_layout(model);
+        (
+            row_width * model.view.scale,
+            column_width * model.view.scale,
+        )
     }
 
-    /// Get the lower X, Y coorindates of the window.
+    /// Get the lower X, Y coorindates of the board, the lower left corner of the window unless the cells are kept square.
     /// # Params
     /// - model: &Model, reference to the model, has the window width and height.
     ///
     /// # Returns
-    /// (f32, f32), tuple of (X, Y) coordiates of the lower left corner of the window.
-    fn get_lower_window_coordinates(model: &Model) -> (f32, f32) {
-        let lower_x = (model.window_width / 2.0) * -1.0;
-        let lower_y = (model.window_height / 2.0) * -1.0;
+    /// (f32, f32), tuple of (X, Y) coordiates of the lower left corner of the board.
+    fn get_lower_board_coordinates(model: &Model) -> (f32, f32) {
+        let (_spacing, (board_width, board_height)) = GUI::get_layout(model);
+        let lower_x = -(board_width / 2.0);
+        let lower_y = -(board_height / 2.0);
 
         (lower_x, lower_y)
     }
@@ -318,17 +1458,962 @@ impl GUI {
     /// # Returns
     /// Result<Icon, &'static str>, Either the Icon is returned or an error string
     fn load_icon(icon: String) -> Result<Icon, &'static str> {
-
         let image = match nannou::image::open(icon) {
             Ok(image) => image.into_rgba8(),
-            Err(_err) => return Err("Failed to read icon file")
+            Err(_err) => return Err("Failed to read icon file"),
         };
 
         let (icon_width, icon_height) = image.dimensions();
 
         match Icon::from_rgba(image.into_raw(), icon_width, icon_height) {
             Ok(icon) => Ok(icon),
-            Err(_err) => Err("Failed to create icon")
+            Err(_err) => Err("Failed to create icon"),
+        }
+    }
+}
+
+/// Render the whole board to a PNG the size of the window, without any pan or zoom.
+/// Colors match the GUI, including the heat map and the grid lines if they are enabled.
+/// # Params
+/// - engine: &conway_engine::ConwayEngine, engine holding the board to render
+/// - params: &ConfigParams, window size and colors
+/// - path: &str, location of the PNG to write, an existing file is overwritten
+/// # Returns
+/// ImageResult<()>, empty OK if successful, the image error otherwise
+fn render_to_png(
+    engine: &conway_engine::ConwayEngine,
+    params: &ConfigParams,
+    path: &str,
+) -> ImageResult<()> {
+    render_to_image(engine, params).save(path)
+}
+
+/// Build the window title, the engine state followed by the time per step and whether the simulation is paused.
+/// # Params
+/// - engine: &conway_engine::ConwayEngine, engine providing ```get_title_string``` and the update rate
+/// - speed: f32, playback speed the update rate is divided by
+/// - paused: bool, flag indicating the simulation was paused with space (or --start-paused)
+/// - unfocused: bool, flag indicating the simulation is paused because the window is unfocused
+/// # Returns
+/// String, title for the window
+fn format_title(
+    engine: &conway_engine::ConwayEngine,
+    speed: f32,
+    paused: bool,
+    unfocused: bool,
+) -> String {
+    let interval = clamp_rate(engine.get_update_rate_duration(), speed);
+    let mut title = format!(
+        "{} -- {:.3}s per step",
+        engine.get_title_string(),
+        interval.as_secs_f32()
+    );
+    if paused {
+        title += " -- paused (space: resume, s: step, r: reset)";
+    } else if unfocused {
+        title += " -- paused while unfocused";
+    }
+    title
+}
+
+/// Size of a window that shows every cell as a square of the given size.
+/// # Params
+/// - grid: (usize, usize), (row, column) size of the grid
+/// - cell_size: f32, pixels on each side of a cell
+/// # Returns
+/// (f32, f32), (width, height) of the window in pixels
+pub fn window_size(grid: (usize, usize), cell_size: f32) -> (f32, f32) {
+    (grid.1 as f32 * cell_size, grid.0 as f32 * cell_size)
+}
+
+/// Center of the HUD text box, HUD_MARGIN in from the corner of the window.
+/// The window's origin is its center, so the lower left of the window is at (-width / 2, -height / 2).
+/// # Params
+/// - corner: HudCorner, corner the HUD is drawn in
+/// - window: (f32, f32), width and height of the window in pixels
+/// # Returns
+/// (f32, f32), x and y of the center of the HUD text box
+fn hud_anchor(corner: HudCorner, window: (f32, f32)) -> (f32, f32) {
+    let (width, height) = window;
+    let x_offset = width / 2.0 - HUD_MARGIN - HUD_SIZE.0 / 2.0;
+    let y_offset = height / 2.0 - HUD_MARGIN - HUD_SIZE.1 / 2.0;
+    match corner {
+        HudCorner::TopLeft => (-x_offset, y_offset),
+        HudCorner::TopRight => (x_offset, y_offset),
+        HudCorner::BottomLeft => (-x_offset, -y_offset),
+        HudCorner::BottomRight => (x_offset, -y_offset),
+    }
+}
+
+/// Format the board for printing, the generation number followed by ```ConwayEngine::to_ascii```.
+/// Matches a generation printed by ```--headless```.
+/// # Params
+/// engine: &conway_engine::ConwayEngine, engine holding the board
+/// # Returns
+/// String, "Generation <n>" and one line per row, every line ends with a newline
+pub fn format_dump(engine: &conway_engine::ConwayEngine) -> String {
+    format!(
+        "Generation {}\n{}",
+        engine.get_generation(),
+        engine.to_ascii()
+    )
+}
+
+/// Step the engine and write every generation to an animated GIF that loops forever.
+/// The first frame is the current generation, stepping stops once the simulation ends or MAX_GIF_FRAMES are written.
+/// # Params
+/// - engine: &mut conway_engine::ConwayEngine, engine to run, its update rate is the delay between frames
+/// - params: &ConfigParams, frame size and colors, see ```render_to_image```
+/// - path: &str, location of the GIF to write, an existing file is overwritten
+/// # Returns
+/// Result<usize, String>, number of frames written or an error if the file can't be written
+fn render_to_gif(
+    engine: &mut conway_engine::ConwayEngine,
+    params: &ConfigParams,
+    path: &str,
+) -> Result<usize, String> {
+    let too_large = || "the GIF must be at most 65535 pixels wide and tall".to_string();
+    let width = u16::try_from(params.width as u32).map_err(|_err| too_large())?;
+    let height = u16::try_from(params.height as u32).map_err(|_err| too_large())?;
+    // GIF delays are in hundredths of a second
+    let delay = (engine.get_update_rate_duration().as_millis() / 10).min(u16::MAX as u128) as u16;
+
+    let file = File::create(path).map_err(|err| err.to_string())?;
+    let mut encoder = gif::Encoder::new(file, width, height, &[]).map_err(|err| err.to_string())?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|err| err.to_string())?;
+    let mut frames = 0;
+    loop {
+        let image = render_to_image(engine, params);
+        // NOTE: speed 10 is the default quality of the color quantizer, 1 is the slowest and best
+        let mut frame = gif::Frame::from_rgb_speed(width, height, image.as_raw(), 10);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(|err| err.to_string())?;
+        frames += 1;
+        if engine.is_simulation_ended() || frames >= MAX_GIF_FRAMES {
+            return Ok(frames);
+        }
+        engine.take_step();
+    }
+}
+
+/// Render the whole board to an image, see ```render_to_png```.
+/// # Params
+/// - engine: &conway_engine::ConwayEngine, engine holding the board to render
+/// - params: &ConfigParams, window size and colors
+/// # Returns
+/// RgbImage, the rendered board
+fn render_to_image(engine: &conway_engine::ConwayEngine, params: &ConfigParams) -> RgbImage {
+    let to_pixel = |color: Srgb<u8>| Rgb([color.red, color.green, color.blue]);
+    let (image_width, image_height) = (params.width as u32, params.height as u32);
+    let (x_width, y_width) = engine.get_grid_spacing();
+    let (row_count, column_count) = engine.get_grid_dimensions();
+
+    let mut image = RgbImage::from_pixel(image_width, image_height, to_pixel(params.dead_color));
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let row_index = ((y as f32 / y_width) as usize).min(row_count - 1);
+        let column_index = ((x as f32 / x_width) as usize).min(column_count - 1);
+        let value = engine.get_cell(row_index, column_index);
+        if value > 0 {
+            *pixel = to_pixel(cell_color(value, params));
+        }
+    }
+
+    if params.enable_grid {
+        let boundary = engine.get_boundary();
+        // at least one pixel, so thin lines still show up
+        let thickness = (params.grid_thickness.round() as u32).max(1);
+        for row_index in 0..=row_count {
+            let grid_pixel = to_pixel(grid_line_color(
+                row_index,
+                row_count,
+                boundary,
+                params.grid_color,
+            ));
+            let top = (row_index as f32 * y_width) as u32;
+            for y in (top..top + thickness).map(|y| y.min(image_height - 1)) {
+                for x in 0..image_width {
+                    image.put_pixel(x, y, grid_pixel);
+                }
+            }
+        }
+        for column_index in 0..=column_count {
+            let grid_pixel = to_pixel(grid_line_color(
+                column_index,
+                column_count,
+                boundary,
+                params.grid_color,
+            ));
+            let left = (column_index as f32 * x_width) as u32;
+            for x in (left..left + thickness).map(|x| x.min(image_width - 1)) {
+                for y in 0..image_height {
+                    image.put_pixel(x, y, grid_pixel);
+                }
+            }
+        }
+    }
+    image
+}
+
+/// Colors of species 2 and up, species 1 uses the alive color.  Species past the end of the list reuse the colors.
+const SPECIES_COLORS: [Srgb<u8>; 6] = [
+    DODGERBLUE,
+    CRIMSON,
+    FORESTGREEN,
+    DARKORANGE,
+    MEDIUMPURPLE,
+    GOLD,
+];
+
+/// Pick the color the window is cleared with.
+/// # Params
+/// params: &ConfigParams, colors from the CLI
+/// # Returns
+/// Srgb<u8>, the background color if one was set, the dead color otherwise
+fn background_color(params: &ConfigParams) -> Srgb<u8> {
+    params.background.unwrap_or(params.dead_color)
+}
+
+/// Color of the cell outlines, the grid color blended halfway into the background so it stays faint.
+/// # Params
+/// params: &ConfigParams, grid color and the background options
+/// # Returns
+/// Srgb<u8>, color to stroke the cell borders with
+fn outline_color(params: &ConfigParams) -> Srgb<u8> {
+    let grid_color = params.grid_color.into_format::<f32>().into_linear();
+    let background = background_color(params).into_format::<f32>().into_linear();
+    Srgb::from_linear(grid_color.mix(&background, 0.5)).into_format()
+}
+
+/// Rectangle a cell is drawn in, inset by a pixel so the cells don't run together.
+/// # Params
+/// - position: (f32, f32), center of the cell on screen, see ```convert_coordinates```
+/// - size: (f32, f32), width and height of the cell
+/// # Returns
+/// (f32, f32, f32, f32), x and y of the rectangle's center, then its width and height
+fn cell_rect(position: (f32, f32), size: (f32, f32)) -> (f32, f32, f32, f32) {
+    (
+        position.0 + 0.5,
+        position.1 + 0.5,
+        size.0 - 1.0,
+        size.1 - 1.0,
+    )
+}
+
+/// Pick the color of a living cell.
+/// With species on the value is a species id, see ```species_color```.  Otherwise it is the age,
+/// colored by ```age_color``` with the heat map enabled (max_age > 0) and the alive color without it.
+/// # Params
+/// - value: usize, value of the living cell, greater than 0
+/// - params: &ConfigParams, colors and the species and heat map options
+/// # Returns
+/// Srgb<u8>, color to draw the cell with
+fn cell_color(value: usize, params: &ConfigParams) -> Srgb<u8> {
+    if params.species {
+        species_color(value, params.alive_color)
+    } else if params.max_age > 0 {
+        age_color(value, params.max_age, params.alive_color, params.old_color)
+    } else {
+        params.alive_color
+    }
+}
+
+/// Pick the color of a species.
+/// # Params
+/// - species: usize, species id, 1 and up
+/// - alive_color: Srgb<u8>, color of species 1
+/// # Returns
+/// Srgb<u8>, alive_color for species 1, a color from ```SPECIES_COLORS``` for the rest
+fn species_color(species: usize, alive_color: Srgb<u8>) -> Srgb<u8> {
+    if species <= 1 {
+        alive_color
+    } else {
+        SPECIES_COLORS[(species - 2) % SPECIES_COLORS.len()]
+    }
+}
+
+/// Pick the color of a living cell based on its age.
+/// Newborn cells (age 1) are young_color, cells at max_age or older are old_color, the ages in between are blended.
+/// # Params
+/// - age: usize, number of generations the cell has been alive
+/// - max_age: usize, age where the color is fully old_color
+/// - young_color: Srgb<u8>, color of newborn cells
+/// - old_color: Srgb<u8>, color of the oldest cells
+/// # Returns
+/// Srgb<u8>, color to draw the cell with
+fn age_color(age: usize, max_age: usize, young_color: Srgb<u8>, old_color: Srgb<u8>) -> Srgb<u8> {
+    let factor = if max_age <= 1 {
+        1.0
+    } else {
+        age.clamp(1, max_age).saturating_sub(1) as f32 / (max_age - 1) as f32
+    };
+
+    // blend in linear space so the middle ages don't look muddy
+    let young_color = young_color.into_format::<f32>().into_linear();
+    let old_color = old_color.into_format::<f32>().into_linear();
+    Srgb::from_linear(young_color.mix(&old_color, factor)).into_format()
+}
+
+/// Pick the color of a grid line, the border is ```ORANGE``` when the edges wrap so it stands out.
+/// # Params
+/// - line_index: usize, index of the line, 0 is the first border and cell_count the last
+/// - cell_count: usize, number of rows or columns between the lines
+/// - boundary: conway_engine::BoundaryCondition, how the engine treats the edges
+/// - grid_color: Srgb<u8>, color of every other line
+/// # Returns
+/// Srgb<u8>, ```ORANGE``` for a wrapped border, grid_color otherwise
+fn grid_line_color(
+    line_index: usize,
+    cell_count: usize,
+    boundary: conway_engine::BoundaryCondition,
+    grid_color: Srgb<u8>,
+) -> Srgb<u8> {
+    let is_border = line_index == 0 || line_index == cell_count;
+    if is_border && boundary == conway_engine::BoundaryCondition::Toroidal {
+        ORANGE
+    } else {
+        grid_color
+    }
+}
+
+/// Take the whole step intervals out of the accumulated time, at most max_steps of them.
+/// A backlog beyond max_steps is dropped instead of carried over, otherwise a slow frame makes the next one
+/// take more steps and be slower still.
+/// # Params
+/// - time: Duration, time accumulated since the last step
+/// - interval: Duration, time between steps
+/// - max_steps: usize, most steps to take at once
+/// # Returns
+/// (usize, Duration), number of steps to take and the time left over for the next frame
+fn drain_steps(mut time: Duration, interval: Duration, max_steps: usize) -> (usize, Duration) {
+    let mut steps = 0;
+    while time >= interval && steps < max_steps {
+        time -= interval;
+        steps += 1;
+    }
+    if time >= interval {
+        time = Duration::new(0, 0);
+    }
+    (steps, time)
+}
+
+/// Apply a speed multiplier to the time between steps.
+/// Only the multiplier is bounded, the result is clamped between MIN_STEP_INTERVAL and MAX_STEP_INTERVAL
+/// unless current is already outside of them, then current is the bound instead.
+/// So an update rate of 30s or 1ms is kept at normal speed, and speeding up can't make 30s any slower.
+/// # Params
+/// - current: Duration, time between steps at normal speed
+/// - factor: f32, speed multiplier, 2.0 halves the time between steps
+/// # Returns
+/// Duration, current divided by factor and clamped
+fn clamp_rate(current: Duration, factor: f32) -> Duration {
+    if factor == 1.0 {
+        return current;
+    }
+    let seconds = current.as_secs_f64() / factor as f64;
+    let min = MIN_STEP_INTERVAL.min(current).as_secs_f64();
+    let max = MAX_STEP_INTERVAL.max(current).as_secs_f64();
+    // NOTE: max and min also catch a zero factor, infinity clamps to the max and NaN to the min
+    Duration::from_secs_f64(seconds.max(min).min(max))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_build_engine_reset() {
+        let params = ConfigParams {
+            file_names: &["test-files/test.txt"],
+            number_of_steps: 20,
+            ..Default::default()
+        };
+
+        let mut engine = GUI::build_engine(&params, 0);
+        engine.take_step();
+        engine.take_step();
+
+        // reset gives back the initial pattern and step count from the file header
+        let engine = GUI::build_engine(&params, 0);
+        assert_eq!(engine.get_number_of_steps(), 20);
+        for index in 0..5 {
+            assert_eq!(engine.get_cell(index, index), 1);
+        }
+    }
+
+    #[test]
+    fn test_age_color() {
+        let young_color = Srgb::new(0u8, 0, 0);
+        let old_color = Srgb::new(255u8, 255, 255);
+        assert_eq!(age_color(1, 10, young_color, old_color), young_color);
+        assert_eq!(age_color(10, 10, young_color, old_color), old_color);
+        // older than max age stays saturated
+        assert_eq!(age_color(50, 10, young_color, old_color), old_color);
+        // max age of 1 jumps straight to the old color
+        assert_eq!(age_color(1, 1, young_color, old_color), old_color);
+
+        // ages in between are blended, getting closer to the old color as the cell ages
+        let middle = age_color(5, 10, young_color, old_color);
+        let later = age_color(8, 10, young_color, old_color);
+        assert!(middle.red > young_color.red && middle.red < old_color.red);
+        assert!(later.red > middle.red);
+        assert_eq!(middle.red, middle.green);
+        assert_eq!(middle.red, middle.blue);
+    }
+
+    #[test]
+    fn test_square_cells_layout() {
+        // 5x5 grid in a 1024x768 window, square cells leave a 128 pixel margin on the left and right
+        let engine = conway_engine::ConwayEngine::new(
+            &"test-files/test2.txt".to_string(),
+            768.0,
+            1024.0,
+            0.0,
+            0,
+        );
+        let (spacing, (x_offset, y_offset)) = engine.get_square_spacing_and_offset();
+        let board = (1024.0 - 2.0 * x_offset, 768.0 - 2.0 * y_offset);
+        assert_eq!(board, (768.0, 768.0));
+        let spacing = (spacing, spacing);
+        let view = ViewTransform::identity();
+
+        // the top left cell starts after the margin
+        assert_eq!(
+            grid_to_screen(0, 0, spacing, board, &view),
+            (-384.0 + 76.8, 384.0 - 76.8)
+        );
+        for row_index in 0..5 {
+            for column_index in 0..5 {
+                let (x, y) = grid_to_screen(row_index, column_index, spacing, board, &view);
+                assert_eq!(
+                    screen_to_grid(x, y, spacing, board, (5, 5), &view),
+                    Some((row_index, column_index))
+                );
+            }
+        }
+
+        // clicks in the margins miss the grid
+        assert_eq!(
+            screen_to_grid(-400.0, 0.0, spacing, board, (5, 5), &view),
+            None
+        );
+        assert_eq!(
+            screen_to_grid(400.0, 0.0, spacing, board, (5, 5), &view),
+            None
+        );
+        assert_eq!(
+            screen_to_grid(-383.0, 0.0, spacing, board, (5, 5), &view),
+            Some((2, 0))
+        );
+    }
+
+    #[test]
+    fn test_next_in_playlist() {
+        let params = ConfigParams {
+            file_names: &["test-files/test.txt", "test-files/glider_test.txt"],
+            number_of_steps: 3,
+            ..Default::default()
+        };
+
+        // the first file runs out of steps, then the second one starts from its own header
+        let mut engine = GUI::build_engine(&params, 0);
+        assert_eq!(engine.get_name(), "test-files/test.txt");
+        engine.step_many(3);
+        assert!(engine.is_simulation_ended());
+        let (index, engine) = GUI::next_in_playlist(&params, 0).unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(engine.get_name(), "test-files/glider_test.txt");
+        assert_eq!(engine.get_generation(), 0);
+        assert_eq!(engine.population(), 5);
+        assert!(!engine.is_simulation_ended());
+
+        // the last file has nothing after it
+        assert!(GUI::next_in_playlist(&params, 1).is_none());
+
+        // a bundled pattern isn't part of the playlist
+        let params = ConfigParams {
+            pattern: "glider",
+            ..params
+        };
+        assert!(GUI::next_in_playlist(&params, 0).is_none());
+    }
+
+    #[test]
+    fn test_build_engine_random() {
+        let params = ConfigParams {
+            random_density: Some(0.5),
+            seed: Some(7),
+            random_size: (20, 30),
+            ..Default::default()
+        };
+
+        // seeded boards come back the same after a reset
+        let engine = GUI::build_engine(&params, 0);
+        let reset_engine = GUI::build_engine(&params, 0);
+        assert_eq!(engine.get_grid_dimensions(), (20, 30));
+        for row_index in 0..20 {
+            for column_index in 0..30 {
+                assert_eq!(
+                    engine.get_cell(row_index, column_index),
+                    reset_engine.get_cell(row_index, column_index)
+                );
+            }
         }
     }
+
+    #[test]
+    fn test_build_engine_pattern() {
+        let params = ConfigParams {
+            pattern: "pulsar",
+            ..Default::default()
+        };
+        let engine = GUI::build_engine(&params, 0);
+        assert_eq!(engine.get_name(), "pulsar");
+        assert_eq!(engine.population(), 48);
+
+        // a random board takes priority over the pattern
+        let params = ConfigParams {
+            random_density: Some(0.0),
+            ..params
+        };
+        assert_eq!(GUI::build_engine(&params, 0).population(), 0);
+    }
+
+    #[test]
+    fn test_render_to_png() {
+        // default blinker, 5 x 5 cells of 10 x 10 pixels
+        let mut params = ConfigParams {
+            height: 50.0,
+            width: 50.0,
+            ..Default::default()
+        };
+        let engine = GUI::build_engine(&params, 0);
+
+        let count_pixels = |image: &RgbImage, color: Srgb<u8>| {
+            image
+                .pixels()
+                .filter(|pixel| **pixel == Rgb([color.red, color.green, color.blue]))
+                .count()
+        };
+        let image = render_to_image(&engine, &params);
+        assert_eq!(image.dimensions(), (50, 50));
+        assert_eq!(count_pixels(&image, params.alive_color), 3 * 100);
+        assert_eq!(count_pixels(&image, params.dead_color), 22 * 100);
+
+        // grid lines cover the first row and column of every cell, plus the last row and column of the image
+        params.enable_grid = true;
+        let image = render_to_image(&engine, &params);
+        assert_eq!(count_pixels(&image, SLATEGREY), 50 * 50 - 44 * 44);
+        // each living cell keeps 9 x 9 pixels inside the lines
+        assert_eq!(count_pixels(&image, params.alive_color), 3 * 9 * 9);
+
+        // the PNG on disk holds the same image
+        let path = std::env::temp_dir().join("conway_rust_test_render.png");
+        let path = path.to_str().unwrap();
+        assert!(render_to_png(&engine, &params, path).is_ok());
+        let saved = nannou::image::open(path).unwrap().into_rgb8();
+        assert_eq!(saved, image);
+        std::fs::remove_file(path).unwrap();
+
+        assert!(render_to_png(&engine, &params, "test-files/no-directory/snapshot.png").is_err());
+
+        // 2 pixel blue lines, the last line is clamped to the last row and column of the image
+        params.grid_color = BLUE;
+        params.grid_thickness = 2.0;
+        let image = render_to_image(&engine, &params);
+        assert_eq!(count_pixels(&image, SLATEGREY), 0);
+        assert_eq!(count_pixels(&image, BLUE), 50 * 50 - 39 * 39);
+        assert_eq!(count_pixels(&image, params.alive_color), 3 * 8 * 8);
+    }
+
+    #[test]
+    fn test_cell_rect() {
+        assert_eq!(
+            cell_rect((10.0, -20.0), (16.0, 8.0)),
+            (10.5, -19.5, 15.0, 7.0)
+        );
+        // neighboring cells keep a pixel between them
+        let (left_x, _, left_w, _) = cell_rect((0.0, 0.0), (16.0, 16.0));
+        let (right_x, _, right_w, _) = cell_rect((16.0, 0.0), (16.0, 16.0));
+        assert_eq!((right_x - right_w / 2.0) - (left_x + left_w / 2.0), 1.0);
+    }
+
+    #[test]
+    fn test_outline_color() {
+        let params = ConfigParams {
+            grid_color: BLACK,
+            dead_color: WHITE,
+            ..ConfigParams::default()
+        };
+        let color = outline_color(&params);
+        // between the grid and background colors, a gray
+        assert!(color.red > 0 && color.red < 255);
+        assert_eq!((color.red, color.green), (color.green, color.blue));
+        // blends into the background instead of the dead color when one is set
+        let params = ConfigParams {
+            background: Some(BLACK),
+            ..params
+        };
+        assert_eq!(outline_color(&params), BLACK);
+    }
+
+    #[test]
+    fn test_window_size() {
+        // 48 rows by 64 columns of 16 pixel cells is the default window
+        assert_eq!(window_size((48, 64), 16.0), (1024.0, 768.0));
+        // width follows the columns and height the rows
+        assert_eq!(window_size((10, 20), 5.0), (100.0, 50.0));
+        assert_eq!(window_size((3, 3), 2.5), (7.5, 7.5));
+        // the cells stay square in the window
+        let (width, height) = window_size((30, 40), 12.0);
+        assert_eq!(width / 40.0, height / 30.0);
+    }
+
+    #[test]
+    fn test_hud_anchor() {
+        // 1024x768 window, the box is 220x50 and sits 10 pixels in from the edges
+        let window = (1024.0, 768.0);
+        assert_eq!(hud_anchor(HudCorner::TopLeft, window), (-392.0, 349.0));
+        assert_eq!(hud_anchor(HudCorner::TopRight, window), (392.0, 349.0));
+        assert_eq!(hud_anchor(HudCorner::BottomLeft, window), (-392.0, -349.0));
+        assert_eq!(hud_anchor(HudCorner::BottomRight, window), (392.0, -349.0));
+    }
+
+    #[test]
+    fn test_parse_hud_corner() {
+        assert_eq!("top-left".parse::<HudCorner>(), Ok(HudCorner::TopLeft));
+        assert_eq!(
+            " Bottom-Right ".parse::<HudCorner>(),
+            Ok(HudCorner::BottomRight)
+        );
+        assert_eq!(
+            "middle".parse::<HudCorner>(),
+            Err("HUD corner must be top-left, top-right, bottom-left or bottom-right")
+        );
+    }
+
+    #[test]
+    fn test_format_title() {
+        let engine =
+            conway_engine::ConwayEngine::from_pattern("blinker", 80.0, 80.0, 0.25, 0).unwrap();
+        let title = format_title(&engine, 1.0, false, false);
+        assert_eq!(
+            title,
+            format!("{} -- 0.250s per step", engine.get_title_string())
+        );
+
+        // --start-paused opens with the paused suffix, a manual pause wins over the unfocused one
+        assert_eq!(
+            format_title(&engine, 1.0, true, true),
+            format!("{} -- paused (space: resume, s: step, r: reset)", title)
+        );
+        assert_eq!(
+            format_title(&engine, 2.0, false, true),
+            format!(
+                "{} -- 0.125s per step -- paused while unfocused",
+                engine.get_title_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_dump() {
+        let params = ConfigParams {
+            file_names: &["test-files/test.txt"],
+            ..Default::default()
+        };
+        let mut engine = GUI::build_engine(&params, 0);
+        assert_eq!(
+            format_dump(&engine),
+            "Generation 0\n#....\n.#...\n..#..\n...#.\n....#\n"
+        );
+        // the diagonal ends lose a cell each step
+        engine.take_step();
+        assert_eq!(
+            format_dump(&engine),
+            "Generation 1\n.....\n.#...\n..#..\n...#.\n.....\n"
+        );
+    }
+
+    #[test]
+    fn test_render_to_gif() {
+        // default blinker for 3 steps, 4 frames of 50 x 50 pixels
+        let params = ConfigParams {
+            height: 50.0,
+            width: 50.0,
+            number_of_steps: 3,
+            update_rate: 0.25,
+            ..Default::default()
+        };
+        let mut engine = GUI::build_engine(&params, 0);
+        let path = std::env::temp_dir().join("conway_rust_test_render.gif");
+        let path = path.to_str().unwrap();
+        assert_eq!(render_to_gif(&mut engine, &params, path), Ok(4));
+        assert!(std::fs::metadata(path).unwrap().len() > 0);
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = decoder.read_info(File::open(path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (50, 50));
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 25);
+            // the blinker has 3 cells of 10 x 10 pixels in every generation
+            let alive = frame
+                .buffer
+                .chunks(4)
+                .filter(|pixel| pixel[..3] == [0, 0, 0])
+                .count();
+            assert_eq!(alive, 3 * 100);
+            frames += 1;
+        }
+        assert_eq!(frames, 4);
+        std::fs::remove_file(path).unwrap();
+
+        // non-stop runs are capped
+        let params = ConfigParams {
+            height: 10.0,
+            width: 10.0,
+            number_of_steps: 0,
+            ..params
+        };
+        let mut engine = GUI::build_engine(&params, 0);
+        assert_eq!(
+            render_to_gif(&mut engine, &params, path),
+            Ok(MAX_GIF_FRAMES)
+        );
+        assert!(!engine.is_simulation_ended());
+        std::fs::remove_file(path).unwrap();
+
+        assert!(render_to_gif(&mut engine, &params, "test-files/no-directory/run.gif").is_err());
+    }
+
+    #[test]
+    fn test_export_gif_cell_size() {
+        // default blinker built for a 50 x 50 window, exported with 4 pixel cells
+        let params = ConfigParams {
+            height: 50.0,
+            width: 50.0,
+            number_of_steps: 2,
+            ..Default::default()
+        };
+        let engine = GUI::build_engine(&params, 0);
+        let size = window_size(engine.get_grid_dimensions(), 4.0);
+        let path = std::env::temp_dir().join("conway_rust_test_cell_size.gif");
+        let path = path.to_str().unwrap();
+        assert_eq!(
+            GUI::export_gif(
+                engine,
+                path,
+                size,
+                params.alive_color,
+                params.dead_color,
+                None,
+                (0, params.old_color),
+                false
+            ),
+            0
+        );
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = decoder.read_info(File::open(path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (20, 20));
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            // the blinker has 3 cells of 4 x 4 pixels in every generation
+            let alive = frame
+                .buffer
+                .chunks(4)
+                .filter(|pixel| pixel[..3] == [0, 0, 0])
+                .count();
+            assert_eq!(alive, 3 * 16);
+            frames += 1;
+        }
+        assert_eq!(frames, 3);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_grid_line_color() {
+        let toroidal = conway_engine::BoundaryCondition::Toroidal;
+        assert_eq!(grid_line_color(0, 5, toroidal, SLATEGREY), ORANGE);
+        assert_eq!(grid_line_color(5, 5, toroidal, SLATEGREY), ORANGE);
+        for line_index in 1..5 {
+            assert_eq!(
+                grid_line_color(line_index, 5, toroidal, SLATEGREY),
+                SLATEGREY
+            );
+            assert_eq!(grid_line_color(line_index, 5, toroidal, BLUE), BLUE);
+        }
+
+        // the border looks like every other line when the edges don't wrap
+        for boundary in [
+            conway_engine::BoundaryCondition::Dead,
+            conway_engine::BoundaryCondition::Alive,
+        ]
+        .iter()
+        {
+            for line_index in 0..=5 {
+                assert_eq!(
+                    grid_line_color(line_index, 5, *boundary, SLATEGREY),
+                    SLATEGREY
+                );
+                assert_eq!(grid_line_color(line_index, 5, *boundary, BLUE), BLUE);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_cell_shape() {
+        assert_eq!("square".parse::<CellShape>(), Ok(CellShape::Square));
+        assert_eq!(" Circle ".parse::<CellShape>(), Ok(CellShape::Circle));
+        assert_eq!(CellShape::default(), CellShape::Square);
+        assert!("hexagon".parse::<CellShape>().is_err());
+        assert!("".parse::<CellShape>().is_err());
+    }
+
+    #[test]
+    fn test_background_color() {
+        let params = ConfigParams::default();
+        assert_eq!(background_color(&params), params.dead_color);
+        let params = ConfigParams {
+            background: Some(DARKSLATEGRAY),
+            ..params
+        };
+        assert_eq!(background_color(&params), DARKSLATEGRAY);
+    }
+
+    #[test]
+    fn test_species_color() {
+        assert_eq!(species_color(1, BLACK), BLACK);
+        assert_eq!(species_color(2, BLACK), DODGERBLUE);
+        assert_eq!(species_color(7, BLACK), GOLD);
+        // the colors repeat once they run out
+        assert_eq!(species_color(8, BLACK), DODGERBLUE);
+        // species wins over the heat map
+        let params = ConfigParams {
+            species: true,
+            max_age: 5,
+            ..ConfigParams::default()
+        };
+        assert_eq!(cell_color(3, &params), CRIMSON);
+        let params = ConfigParams {
+            max_age: 5,
+            ..ConfigParams::default()
+        };
+        assert_eq!(cell_color(5, &params), RED);
+        assert_eq!(cell_color(3, &ConfigParams::default()), BLACK);
+    }
+
+    #[test]
+    fn test_clamp_rate() {
+        let second = Duration::from_secs(1);
+        assert_eq!(clamp_rate(second, 1.0), second);
+        assert_eq!(clamp_rate(second, 2.0), Duration::from_millis(500));
+        assert_eq!(clamp_rate(second, 0.5), Duration::from_secs(2));
+
+        // boundary clamps
+        assert_eq!(clamp_rate(second, 1000.0), MIN_STEP_INTERVAL);
+        assert_eq!(
+            clamp_rate(Duration::from_millis(16), 1.0),
+            MIN_STEP_INTERVAL
+        );
+        assert_eq!(clamp_rate(second, 0.01), MAX_STEP_INTERVAL);
+        assert_eq!(clamp_rate(second, 0.0), MAX_STEP_INTERVAL);
+
+        // a base rate outside the bounds passes through unchanged at normal speed
+        let fast = Duration::from_millis(1);
+        let slow = Duration::from_secs(30);
+        assert_eq!(clamp_rate(fast, 1.0), fast);
+        assert_eq!(clamp_rate(slow, 1.0), slow);
+        // the multiplier can bring it back inside the bounds, but not take it further out
+        assert_eq!(clamp_rate(fast, 0.5), Duration::from_millis(2));
+        assert_eq!(clamp_rate(fast, 2.0), fast);
+        assert_eq!(clamp_rate(slow, 2.0), Duration::from_secs(15));
+        assert_eq!(clamp_rate(slow, 0.5), slow);
+        assert_eq!(clamp_rate(slow, 0.0), slow);
+    }
+
+    #[test]
+    fn test_drain_steps() {
+        let interval = Duration::from_millis(100);
+        // not a whole interval yet, the time carries over
+        assert_eq!(
+            drain_steps(Duration::from_millis(50), interval, 8),
+            (0, Duration::from_millis(50))
+        );
+        assert_eq!(drain_steps(interval, interval, 8), (1, Duration::new(0, 0)));
+        // a hitch takes every owed step and keeps the rest of an interval
+        assert_eq!(
+            drain_steps(Duration::from_millis(350), interval, 8),
+            (3, Duration::from_millis(50))
+        );
+        assert_eq!(
+            drain_steps(Duration::from_millis(800), interval, 8),
+            (8, Duration::new(0, 0))
+        );
+        // a long stall is capped and the backlog dropped, so the next frame starts fresh
+        assert_eq!(
+            drain_steps(Duration::from_secs(5), interval, 8),
+            (8, Duration::new(0, 0))
+        );
+        assert_eq!(
+            drain_steps(Duration::from_secs(5), interval, 1),
+            (1, Duration::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn test_redraw_flag() {
+        // the first frame is drawn
+        let redraw = RedrawFlag::new();
+        assert!(redraw.take(false));
+        // paused or ended with no input, the frames after it aren't
+        assert!(!redraw.take(false));
+        assert!(!redraw.take(false));
+
+        // a step is drawn once
+        redraw.set();
+        assert!(redraw.take(false));
+        assert!(!redraw.take(false));
+
+        // several inputs before a frame still draw it once
+        redraw.set();
+        redraw.set();
+        assert!(redraw.take(false));
+        assert!(!redraw.take(false));
+
+        // the frame rate overlay draws every frame, and still clears the flag
+        assert!(redraw.take(true));
+        redraw.set();
+        assert!(redraw.take(true));
+        assert!(!redraw.take(false));
+    }
+
+    #[test]
+    fn test_fps_counter() {
+        let mut counter = FpsCounter::new(4);
+        assert_eq!(counter.value(), 0.0);
+
+        counter.tick(Duration::from_millis(100));
+        counter.tick(Duration::from_millis(100));
+        assert!((counter.value() - 10.0).abs() < 1e-3);
+
+        // rolling window, the slow samples fall out
+        counter.tick(Duration::from_millis(500));
+        counter.tick(Duration::from_millis(300));
+        assert!((counter.value() - 4.0).abs() < 1e-3);
+        for _ in 0..4 {
+            counter.tick(Duration::from_millis(20));
+        }
+        assert_eq!(counter.samples.len(), 4);
+        assert!((counter.value() - 50.0).abs() < 1e-3);
+
+        // zero length frames don't divide by zero
+        let mut counter = FpsCounter::new(2);
+        counter.tick(Duration::from_secs(0));
+        assert_eq!(counter.value(), 0.0);
+    }
 }
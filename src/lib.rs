@@ -0,0 +1,39 @@
+//! Conway's Game of Life engine, the grid and the file parsers, without the GUI.
+//!
+//! Run a bundled pattern without a window:
+//! ```
+//! use conway_rust::ConwayEngine;
+//!
+//! // 0 steps runs non-stop, the window size only picks the grid size
+//! let mut engine = ConwayEngine::from_pattern("blinker", 80.0, 80.0, 0.0, 0).unwrap();
+//! assert_eq!(engine.population(), 3);
+//! engine.step_many(2);
+//! assert_eq!(engine.get_generation(), 2);
+//! print!("{}", engine.to_ascii());
+//! ```
+//!
+//! Or build the board yourself:
+//! ```
+//! use conway_rust::{ConwayEngine, Grid, Outcome};
+//!
+//! let mut grid = Grid::new(4, 4, 0);
+//! for (row, column) in [(1, 1), (1, 2), (2, 1), (2, 2)].iter() {
+//!     grid.set(*row, *column, 1).unwrap();
+//! }
+//! let mut engine = ConwayEngine::from_grid(grid, "block", 768.0, 1024.0, 0.0, 10);
+//! engine.step_many(10);
+//! assert_eq!(engine.classify_outcome(), Outcome::Stable);
+//! ```
+pub mod conway_engine;
+pub mod grid;
+pub mod patterns;
+pub mod ruleset;
+mod sparse_grid;
+
+pub use conway_engine::{
+    check_update_rate, generate_random_grid, parse_ascii, parse_plaintext, parse_rle, Backend,
+    BoundaryCondition, ConwayEngine, Flip, LoadOptions, Neighborhood, Outcome, Rotation,
+    SaveFormat, StepCallback, MAX_CELLS, MAX_UPDATE_RATE,
+};
+pub use grid::{Grid, GridBackend};
+pub use ruleset::{Rule, Ruleset};
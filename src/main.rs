@@ -1,6 +1,34 @@
 #[macro_use]
 extern crate clap;
+mod config;
+mod control;
+mod coords;
 mod gui;
+mod population_log;
+
+use config::Config;
+use control::{Command, ControlServer};
+use conway_rust::conway_engine;
+use population_log::PopulationLog;
+
+use nannou::color::named;
+use nannou::color::rgb::Srgb;
+use std::time::Duration;
+
+/// Seconds between steps when neither --rate nor the file sets it
+const DEFAULT_UPDATE_RATE: f64 = 1.0;
+/// Steps taken when neither --steps nor the file sets them, 0 would run forever
+const DEFAULT_STEPS: usize = 20;
+
+/// Exit codes for how the simulation ended, see ```outcome_exit_code```, 1 is left for errors
+const EXIT_STEP_LIMIT: i32 = 0;
+const EXIT_STABLE: i32 = 2;
+const EXIT_EXTINCT: i32 = 3;
+const EXIT_OSCILLATING: i32 = 4;
+const EXIT_RUNNING: i32 = 5;
+
+/// Longest --headless waits before checking the control port for commands again
+const CONTROL_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 fn main() {
     // handle CLI args
@@ -13,61 +41,1001 @@ fn main() {
         .about(crate_description!())
         .get_matches();
 
-    // read in height and width, deafult is 1024 x 768
-    let height = matches
-        .value_of("height")
-        .unwrap_or("768.0")
-        .parse::<f32>()
-        .expect("Failed to parse height argument");
-
-    let width = matches
-        .value_of("width")
-        .unwrap_or("1024.0")
-        .parse::<f32>()
-        .expect("Failed to parse width argument");
-
-    // read in the update rate
-    let update_rate = matches
-        .value_of("rate")
-        .unwrap_or("1")
-        .parse::<usize>()
-        .expect("Failed to parse rate argument");
-
-    // read in the number of steps
-    let number_of_steps = matches
-        .value_of("steps")
-        .unwrap_or("20")
-        .parse::<usize>()
-        .expect("Failed to parse number of steps argument");
-
-    // read in the alive color
-    // NOTE: All colors must be in lowercase to be parsed by the palette crate
-    let alive_color = matches
-        .value_of("alive")
-        .unwrap_or("BLACK")
-        .to_ascii_lowercase();
+    // resolve every option up front, the grid size limit and strict columns apply to the subcommands as well
+    let config = Config::from_matches(&matches);
+    let load_options = config.load_options();
 
-    // read in the dead color
-    // NOTE: All colors must be in lowercase to be parsed by the palette crate
-    let dead_color = matches
-        .value_of("dead")
-        .unwrap_or("WHITE")
-        .to_ascii_lowercase();
+    // lint a file and exit without starting the simulation
+    if let Some(validate_matches) = matches.subcommand_matches("validate") {
+        let file = validate_matches
+            .value_of("FILE")
+            .expect("FILE is a required argument");
+        std::process::exit(validate_file(file, load_options));
+    }
+
+    // run many random boards and report how they ended
+    if let Some(experiment_matches) = matches.subcommand_matches("experiment") {
+        let runs: usize = parse_arg(experiment_matches, "runs", "100");
+        let density: f64 = parse_arg(experiment_matches, "density", "0.3");
+        let steps: usize = parse_arg(experiment_matches, "steps", "500");
+        let seed: Option<u64> = parse_optional_arg(experiment_matches, "seed");
+        let rows: usize = parse_arg(experiment_matches, "rows", "48");
+        let columns: usize = parse_arg(experiment_matches, "columns", "64");
+        match run_experiment(
+            runs,
+            density,
+            steps,
+            seed,
+            (rows, columns),
+            load_options.max_cells,
+        ) {
+            Ok(outcomes) => {
+                println!(
+                    "Ran {} random {}x{} boards, density {}, up to {} steps",
+                    runs, rows, columns, density, steps
+                );
+                print!("{}", format_histogram(&outcomes));
+                std::process::exit(0);
+            }
+            Err(err) => {
+                eprintln!("Invalid experiment: {}", err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // search random soups and report the most interesting ones
+    if let Some(soup_matches) = matches.subcommand_matches("soup") {
+        let count: usize = parse_arg(soup_matches, "count", "100");
+        let size = soup_matches.value_of("size").unwrap_or("16x16");
+        let (rows, columns) = match parse_size(size) {
+            Ok(size) => size,
+            Err(err) => {
+                eprintln!("Invalid value '{}' for --size: {}", size, err);
+                std::process::exit(1);
+            }
+        };
+        let steps: usize = parse_arg(soup_matches, "steps", "1000");
+        let seed: u64 = parse_arg(soup_matches, "seed", "0");
+        let density: f64 = parse_arg(soup_matches, "density", "0.5");
+        let top: usize = parse_arg(soup_matches, "top", "10");
+        match run_soups(
+            count,
+            density,
+            steps,
+            seed,
+            (rows, columns),
+            load_options.max_cells,
+        ) {
+            Ok(soups) => {
+                println!(
+                    "Ran {} random {}x{} soups, density {}, up to {} steps, seeds {}..={}",
+                    count,
+                    columns,
+                    rows,
+                    density,
+                    steps,
+                    seed,
+                    seed.wrapping_add(count as u64 - 1)
+                );
+                print!("{}", format_soup_report(&soups, top));
+                std::process::exit(0);
+            }
+            Err(err) => {
+                eprintln!("Invalid soup search: {}", err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if matches.is_present("print-config") {
+        print!("{}", config.format());
+        std::process::exit(0);
+    }
+    for notice in &config.notices {
+        eprintln!("{}", notice);
+    }
 
-    // read in the game file, default is empty (which will generate a default oscillator)
-    let file_location = matches.value_of("file").unwrap_or("");
+    // the GUI plays the files in order, the headless and GIF runs only use the first one
+    let file_location = config.file_locations.first().cloned().unwrap_or_default();
 
-    let enable_grid = matches.is_present("grid");
+    // open the population log before anything runs, so a bad path fails right away
+    let population_log = if config.log_population.is_empty() {
+        None
+    } else {
+        match PopulationLog::create(&config.log_population) {
+            Ok(log) => Some(log),
+            Err(err) => {
+                eprintln!(
+                    "Failed to open the population log '{}': {}",
+                    config.log_population, err
+                );
+                std::process::exit(1);
+            }
+        }
+    };
+
+    // listen before anything runs as well, a GIF is written without stopping so it doesn't listen
+    let control = match config.control_port {
+        Some(port) if config.gif_path.is_empty() => match ControlServer::start(port) {
+            Ok(server) => Some(server),
+            Err(err) => {
+                eprintln!("Failed to listen on the control port {}: {}", port, err);
+                std::process::exit(1);
+            }
+        },
+        _ => None,
+    };
+
+    // without a display the engine runs in the terminal or is written to a GIF, nannou is never started
+    if config.headless || !config.gif_path.is_empty() {
+        if config.infinite && config.boundary != conway_engine::BoundaryCondition::Dead {
+            eprintln!("The --infinite option only works with the dead boundary");
+            std::process::exit(1);
+        }
+        let ruleset = match config
+            .rule
+            .as_deref()
+            .map(str::parse::<conway_rust::Ruleset>)
+            .transpose()
+        {
+            Ok(ruleset) => ruleset,
+            Err(err) => {
+                eprintln!(
+                    "Invalid rule '{}': {}",
+                    config.rule.as_deref().unwrap_or_default(),
+                    err
+                );
+                std::process::exit(1);
+            }
+        };
+        // builds the starting engine, again for every reset sent to the control port
+        let build_engine = || {
+            let mut engine = match config.random_density {
+                Some(density) => match conway_engine::ConwayEngine::new_random(
+                    config.random_rows,
+                    config.random_columns,
+                    density,
+                    config.seed,
+                    config.height,
+                    config.width,
+                    config.update_rate,
+                    config.number_of_steps,
+                    load_options.max_cells,
+                ) {
+                    Ok(engine) => engine,
+                    Err(err) => {
+                        eprintln!("Invalid random board: {}", err);
+                        std::process::exit(1);
+                    }
+                },
+                None if !config.pattern.is_empty() => conway_engine::ConwayEngine::from_pattern(
+                    &config.pattern,
+                    config.height,
+                    config.width,
+                    config.update_rate,
+                    config.number_of_steps,
+                )
+                .expect("Pattern was validated during parsing"),
+                None => match gui::GUI::load_file(
+                    &file_location,
+                    config.ascii_cells,
+                    load_options,
+                    config.height,
+                    config.width,
+                    config.update_rate,
+                    config.number_of_steps,
+                ) {
+                    Ok(engine) => engine,
+                    Err(err) => {
+                        eprintln!("Invalid file '{}': {}", file_location, err);
+                        std::process::exit(1);
+                    }
+                },
+            };
+            engine.transform(config.rotation, config.flip);
+            engine.set_boundary(config.boundary);
+            engine.set_infinite(config.infinite);
+            engine.set_max_cells(load_options.max_cells);
+            engine.set_species(config.species);
+            engine.set_neighborhood(config.neighborhood);
+            if let Some(radius) = config.radius {
+                engine.set_radius(radius);
+            }
+            engine.set_backend(config.backend);
+            // precedence is --rule, then the rule in the file, then B3/S23
+            if let Some(ruleset) = &ruleset {
+                engine.set_ruleset(ruleset.clone());
+            }
+            engine.set_cycle_detection(config.detect_cycle);
+            if let Some(log) = &population_log {
+                log.attach(&mut engine);
+            }
+            engine
+        };
+        let engine = build_engine();
+        let exit_code = if config.gif_path.is_empty() {
+            run_headless(engine, control.as_ref(), &build_engine)
+        } else {
+            let grid_style = if config.enable_grid {
+                Some((config.grid_color, config.grid_thickness))
+            } else {
+                None
+            };
+            // with a cell size the frames fit the grid like the window does
+            let size = match config.cell_size {
+                Some(cell_size) => gui::window_size(engine.get_grid_dimensions(), cell_size),
+                None => (config.width, config.height),
+            };
+            gui::GUI::export_gif(
+                engine,
+                &config.gif_path,
+                size,
+                config.alive_color,
+                config.dead_color,
+                grid_style,
+                (config.max_age, config.old_color),
+                config.species,
+            )
+        };
+        flush_population_log(population_log.as_ref());
+        std::process::exit(exit_code);
+    }
 
     // Call the GUI class (empty struct with functions) to start the application
-    gui::GUI::start(
-        file_location.to_string(),
-        number_of_steps,
-        update_rate,
-        height,
-        width,
-        alive_color.to_string(),
-        dead_color.to_string(),
-        enable_grid,
+    gui::GUI::start(&config, population_log, control);
+}
+
+/// Parse a pattern file and print a summary of the grid or the parse error.
+/// # Params
+/// - file: &str, location of the pattern file
+/// - options: conway_engine::LoadOptions, checks applied to the file, e.g. ```--strict```
+/// # Returns
+/// i32, exit code, 0 if the file is valid and 1 otherwise
+fn validate_file(file: &str, options: conway_engine::LoadOptions) -> i32 {
+    match conway_engine::ConwayEngine::try_load(&file.to_string(), 768.0, 1024.0, 1.0, 20, options)
+    {
+        Ok(engine) => {
+            let (row_size, column_size) = engine.get_grid_dimensions();
+            println!(
+                "OK: {}x{} grid, {} live cells",
+                row_size,
+                column_size,
+                engine.population()
+            );
+            0
+        }
+        Err(err) => {
+            eprintln!("Invalid file '{}': {}", file, err);
+            1
+        }
+    }
+}
+
+/// Run random boards to completion and classify how each one ended.
+/// Every board looks for cycles as long as the step limit, so oscillators end as soon as they repeat.
+/// # Params
+/// - runs: usize, number of boards to run
+/// - density: f64, probability (0.0 - 1.0) of each cell starting alive
+/// - steps: usize, most steps each board takes
+/// - seed: Option<u64>, seed of the first board, each run adds 1, None uses a new random seed for every board
+/// - size: (usize, usize), rows and columns of each board
+/// - max_cells: usize, largest rows * columns a board can have, see ```--max-cells```
+/// # Returns
+/// Result<Vec<conway_engine::Outcome>, &'static str>, outcome of each run or an error if the options are out of range
+fn run_experiment(
+    runs: usize,
+    density: f64,
+    steps: usize,
+    seed: Option<u64>,
+    size: (usize, usize),
+    max_cells: usize,
+) -> Result<Vec<conway_engine::Outcome>, &'static str> {
+    if runs == 0 {
+        return Err("runs must be greater than zero");
+    }
+    // 0 steps is non-stop, an experiment needs every run to finish
+    if steps == 0 {
+        return Err("steps must be greater than zero");
+    }
+    (0..runs)
+        .map(|run| {
+            let mut engine = conway_engine::ConwayEngine::new_random(
+                size.0,
+                size.1,
+                density,
+                seed.map(|seed| seed.wrapping_add(run as u64)),
+                768.0,
+                1024.0,
+                0.0,
+                steps,
+                max_cells,
+            )?;
+            engine.set_cycle_detection(steps);
+            engine.step_many(steps);
+            Ok(engine.classify_outcome())
+        })
+        .collect()
+}
+
+/// How one random soup ended, see ```run_soups```.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Soup {
+    /// Seed the soup was generated from, ```--random <density> --seed <seed>``` replays it
+    seed: u64,
+    outcome: conway_engine::Outcome,
+    /// Living cells once the soup ended
+    population: usize,
+    /// Generation the soup ended on, earlier than the step limit once it settled
+    generation: usize,
+}
+
+/// Parse a board size written as WIDTHxHEIGHT, e.g. "64x48".
+/// # Params
+/// size: &str, columns and rows separated by an 'x'
+/// # Returns
+/// Result<(usize, usize), &'static str>, (rows, columns) or an error if the size is malformed
+fn parse_size(size: &str) -> Result<(usize, usize), &'static str> {
+    let (columns, rows) = size
+        .split_once(['x', 'X'])
+        .ok_or("size must be WIDTHxHEIGHT, e.g. 16x16")?;
+    match (rows.trim().parse(), columns.trim().parse()) {
+        (Ok(rows), Ok(columns)) => Ok((rows, columns)),
+        _ => Err("width and height must be whole numbers, e.g. 16x16"),
+    }
+}
+
+/// Run random soups until they settle or reach the step limit, like ```run_experiment``` but keeping the details of each soup.
+/// Soup i is generated from seed + i, so a search (and any soup in it) can be repeated.
+/// # Params
+/// - count: usize, number of soups to run
+/// - density: f64, probability (0.0 - 1.0) of each cell starting alive
+/// - steps: usize, most steps each soup takes
+/// - seed: u64, seed of the first soup
+/// - size: (usize, usize), rows and columns of each soup
+/// - max_cells: usize, largest rows * columns a soup can have, see ```--max-cells```
+/// # Returns
+/// Result<Vec<Soup>, &'static str>, each soup in seed order or an error if the options are out of range
+fn run_soups(
+    count: usize,
+    density: f64,
+    steps: usize,
+    seed: u64,
+    size: (usize, usize),
+    max_cells: usize,
+) -> Result<Vec<Soup>, &'static str> {
+    if count == 0 {
+        return Err("count must be greater than zero");
+    }
+    // 0 steps is non-stop, every soup has to finish
+    if steps == 0 {
+        return Err("steps must be greater than zero");
+    }
+    (0..count)
+        .map(|index| {
+            let seed = seed.wrapping_add(index as u64);
+            let mut engine = conway_engine::ConwayEngine::new_random(
+                size.0,
+                size.1,
+                density,
+                Some(seed),
+                768.0,
+                1024.0,
+                0.0,
+                steps,
+                max_cells,
+            )?;
+            engine.set_cycle_detection(steps);
+            engine.step_many(steps);
+            Ok(Soup {
+                seed,
+                outcome: engine.classify_outcome(),
+                population: engine.population(),
+                generation: engine.get_generation(),
+            })
+        })
+        .collect()
+}
+
+/// Format the most interesting soups as a table: the longest period first, still lifes (period 1) next and
+/// soups still changing at the step limit last, a larger final population breaks ties and then the lower seed.
+/// # Params
+/// - soups: &[Soup], soups from ```run_soups```
+/// - top: usize, most soups listed
+/// # Returns
+/// String, a header line and one line per soup with its rank, seed, outcome, population and generation
+fn format_soup_report(soups: &[Soup], top: usize) -> String {
+    let period = |soup: &Soup| match soup.outcome {
+        conway_engine::Outcome::Oscillating(period) => period,
+        conway_engine::Outcome::Stable => 1,
+        conway_engine::Outcome::StepLimit => 0,
+    };
+    let mut ranked = soups.to_vec();
+    ranked.sort_by(|a, b| {
+        period(b)
+            .cmp(&period(a))
+            .then(b.population.cmp(&a.population))
+            .then(a.seed.cmp(&b.seed))
+    });
+
+    let mut report = format!(
+        "{:>4} {:>20} {:<12} {:>10} {:>10}\n",
+        "rank", "seed", "outcome", "population", "generation"
     );
+    for (rank, soup) in ranked.iter().take(top).enumerate() {
+        let outcome = match soup.outcome {
+            conway_engine::Outcome::Oscillating(period) => format!("period {}", period),
+            conway_engine::Outcome::Stable => "stable".to_string(),
+            conway_engine::Outcome::StepLimit => "step limit".to_string(),
+        };
+        report.push_str(&format!(
+            "{:>4} {:>20} {:<12} {:>10} {:>10}\n",
+            rank + 1,
+            soup.seed,
+            outcome,
+            soup.population,
+            soup.generation
+        ));
+    }
+    report
+}
+
+/// Format experiment outcomes as a text histogram, oscillators are broken down by period.
+/// # Params
+/// outcomes: &[conway_engine::Outcome], outcome of each run
+/// # Returns
+/// String, one line per outcome with its count and a bar of ```#``` scaled to the number of runs
+fn format_histogram(outcomes: &[conway_engine::Outcome]) -> String {
+    let mut stable = 0;
+    let mut step_limit = 0;
+    let mut periods = std::collections::BTreeMap::new();
+    for outcome in outcomes {
+        match outcome {
+            conway_engine::Outcome::Stable => stable += 1,
+            conway_engine::Outcome::Oscillating(period) => {
+                *periods.entry(*period).or_insert(0) += 1
+            }
+            conway_engine::Outcome::StepLimit => step_limit += 1,
+        }
+    }
+    let oscillating: usize = periods.values().sum();
+
+    // the longest bar is 40 characters
+    let bar = |count: usize| "#".repeat(count * 40 / outcomes.len().max(1));
+    let mut histogram = String::new();
+    for (label, count) in [
+        ("stable", stable),
+        ("oscillating", oscillating),
+        ("step limit", step_limit),
+    ]
+    .iter()
+    {
+        histogram.push_str(&format!("{:<12}{:>6} {}\n", label, count, bar(*count)));
+        if *label == "oscillating" {
+            for (period, count) in periods.iter() {
+                let label = format!("  period {}", period);
+                histogram.push_str(&format!("{:<12}{:>6}\n", label, count));
+            }
+        }
+    }
+    histogram
+}
+
+/// Write out the rest of the population log, printing a message if it fails.
+/// # Params
+/// log: Option<&PopulationLog>, the log, None if --log-population wasn't given
+fn flush_population_log(log: Option<&PopulationLog>) {
+    if let Some(Err(err)) = log.map(PopulationLog::flush) {
+        eprintln!("Failed to write the population log: {}", err);
+    }
+}
+
+/// Run the simulation in the terminal, printing every generation as ASCII (```#``` alive, ```.``` dead).
+/// Sleeps for the update rate between generations and stops once the simulation ends or stabilizes.
+/// With a control port the sleep is cut into CONTROL_POLL_INTERVAL slices so the commands are answered in between.
+/// # Params
+/// - engine: conway_engine::ConwayEngine, engine to run
+/// - control: Option<&ControlServer>, server the commands are taken from, None to only step on the update rate
+/// - build_engine: &dyn Fn() -> conway_engine::ConwayEngine, builds the starting engine again for a reset command
+/// # Returns
+/// i32, exit code for how the simulation ended, see ```engine_exit_code```
+fn run_headless(
+    mut engine: conway_engine::ConwayEngine,
+    control: Option<&ControlServer>,
+    build_engine: &dyn Fn() -> conway_engine::ConwayEngine,
+) -> i32 {
+    let mut printed = None;
+    let mut paused = false;
+    let mut waited = Duration::new(0, 0);
+    loop {
+        // a generation is printed once, a reset prints the starting board again
+        if printed != Some(engine.get_generation()) {
+            print!("{}", gui::format_dump(&engine));
+            printed = Some(engine.get_generation());
+        }
+        if engine.is_simulation_ended() {
+            break;
+        }
+        let control = match control {
+            Some(control) => control,
+            None => {
+                std::thread::sleep(engine.get_update_rate_duration());
+                engine.take_step();
+                continue;
+            }
+        };
+        // commands are applied one at a time, so a step sent over the port is printed like any other
+        if let Some(request) = control.next_request() {
+            let reply = match request.command {
+                Command::Step => {
+                    engine.take_step();
+                    String::new()
+                }
+                Command::Pause => {
+                    paused = true;
+                    String::new()
+                }
+                Command::Resume => {
+                    paused = false;
+                    waited = Duration::new(0, 0);
+                    String::new()
+                }
+                Command::Reset => {
+                    engine = build_engine();
+                    printed = None;
+                    waited = Duration::new(0, 0);
+                    String::new()
+                }
+                Command::Dump => gui::format_dump(&engine),
+            };
+            request.reply(reply);
+            continue;
+        }
+        let interval = engine.get_update_rate_duration();
+        if paused {
+            std::thread::sleep(CONTROL_POLL_INTERVAL);
+        } else if waited >= interval {
+            engine.take_step();
+            waited = Duration::new(0, 0);
+        } else {
+            let slice = CONTROL_POLL_INTERVAL.min(interval - waited);
+            std::thread::sleep(slice);
+            waited += slice;
+        }
+    }
+
+    if engine.is_extinct() {
+        println!("Died out after {} generations", engine.get_generation());
+    } else if engine.is_stable() {
+        println!("Stabilized after {} generations", engine.get_generation());
+    } else {
+        println!(
+            "Simulation ended after {} generations",
+            engine.get_generation()
+        );
+    }
+    engine_exit_code(&engine)
+}
+
+/// Exit code for how the engine's simulation ended, or ```EXIT_RUNNING``` if it hasn't.
+/// # Params
+/// engine: &conway_engine::ConwayEngine, engine of the finished (or closed) run
+/// # Returns
+/// i32, exit code, see ```outcome_exit_code```
+fn engine_exit_code(engine: &conway_engine::ConwayEngine) -> i32 {
+    let outcome = if engine.is_simulation_ended() {
+        Some(engine.classify_outcome())
+    } else {
+        None
+    };
+    outcome_exit_code(outcome, engine.is_extinct())
+}
+
+/// Map how a simulation ended to the process exit code, so scripts can tell the outcomes apart.
+/// # Params
+/// - outcome: Option<conway_engine::Outcome>, how the simulation ended, None if it was still running
+/// - extinct: bool, flag indicating every cell died, told apart from the other stable boards
+/// # Returns
+/// i32, 0 at the step limit, 2 stable, 3 extinct, 4 oscillating and 5 still running
+fn outcome_exit_code(outcome: Option<conway_engine::Outcome>, extinct: bool) -> i32 {
+    match outcome {
+        None => EXIT_RUNNING,
+        Some(_) if extinct => EXIT_EXTINCT,
+        Some(conway_engine::Outcome::StepLimit) => EXIT_STEP_LIMIT,
+        Some(conway_engine::Outcome::Stable) => EXIT_STABLE,
+        Some(conway_engine::Outcome::Oscillating(_)) => EXIT_OSCILLATING,
+    }
+}
+
+/// Parse a CLI argument, printing a message and exiting with a nonzero code if the value is invalid.
+/// # Params
+/// - matches: &clap::ArgMatches, the parsed CLI arguments
+/// - name: &str, name of the argument
+/// - default: &str, value used if the argument was not given
+/// # Returns
+/// T, the parsed value
+fn parse_arg<T>(matches: &clap::ArgMatches, name: &str, default: &str) -> T
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let value = matches.value_of(name).unwrap_or(default);
+    match value.parse::<T>() {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("Invalid value '{}' for --{}: {}", value, name, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Fill in the update rate and number of steps when they weren't given on the command line.
+/// The values are still only defaults, a file header that sets them wins (see ```ConwayEngine::try_new```).
+/// # Params
+/// - update_rate: Option<f64>, --rate if given
+/// - number_of_steps: Option<usize>, --steps if given
+/// # Returns
+/// (f64, usize, Vec<String>), update rate, number of steps and a one line notice for each default that was used
+fn resolve_defaults(
+    update_rate: Option<f64>,
+    number_of_steps: Option<usize>,
+) -> (f64, usize, Vec<String>) {
+    let mut notices = Vec::new();
+    let update_rate = update_rate.unwrap_or_else(|| {
+        notices.push(format!(
+            "No --rate given, stepping every {}s unless the file sets a rate",
+            DEFAULT_UPDATE_RATE
+        ));
+        DEFAULT_UPDATE_RATE
+    });
+    let number_of_steps = number_of_steps.unwrap_or_else(|| {
+        notices.push(format!(
+            "No --steps given, stopping after {} steps unless the file sets them (--steps 0 runs forever)",
+            DEFAULT_STEPS
+        ));
+        DEFAULT_STEPS
+    });
+    (update_rate, number_of_steps, notices)
+}
+
+/// Same as ```parse_arg``` for arguments without a default.
+/// # Params
+/// - matches: &clap::ArgMatches, the parsed CLI arguments
+/// - name: &str, name of the argument
+/// # Returns
+/// Option<T>, the parsed value, None if the argument was not given
+fn parse_optional_arg<T>(matches: &clap::ArgMatches, name: &str) -> Option<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    matches
+        .value_of(name)
+        .map(|value| parse_arg(matches, name, value))
+}
+
+/// Parse a color, either a hex code ("#1a2b3c" or the short "#fff") or a color name, ignoring case.
+/// # Params
+/// name: &str, hex code or name of the color, e.g. "#ff8800" or "SteelBlue"
+/// # Returns
+/// Result<Srgb<u8>, String>, the color or an error describing the accepted formats
+fn parse_color(name: &str) -> Result<Srgb<u8>, String> {
+    if let Some(hex) = name.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+
+    // NOTE: All colors must be in lowercase to be parsed by the palette crate
+    match named::from_str(&name.to_ascii_lowercase()) {
+        Some(color) => Ok(color),
+        None => Err(
+            "unknown color, try a hex code like #1a2b3c or a name like black, white, red, green, blue or steelblue"
+                .to_string(),
+        ),
+    }
+}
+
+/// Parse the digits of a hex color code, the leading '#' already removed.
+/// # Params
+/// hex: &str, 6 digits (RRGGBB) or 3 digits (RGB, each digit is doubled so "fff" is "ffffff")
+/// # Returns
+/// Result<Srgb<u8>, String>, the color or an error if the code isn't 3 or 6 hex digits
+fn parse_hex_color(hex: &str) -> Result<Srgb<u8>, String> {
+    if !hex.chars().all(|digit| digit.is_ascii_hexdigit()) {
+        return Err(format!("'#{}' has characters that aren't hex digits", hex));
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|err| err.to_string());
+    match hex.len() {
+        6 => Ok(Srgb::new(
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        3 => Ok(Srgb::new(
+            channel(&hex[0..1])? * 17,
+            channel(&hex[1..2])? * 17,
+            channel(&hex[2..3])? * 17,
+        )),
+        _ => Err(format!("hex color '#{}' must have 3 or 6 digits", hex)),
+    }
+}
+
+/// Parse a color argument, printing a message and exiting with a nonzero code if the color is unknown.
+/// # Params
+/// - matches: &clap::ArgMatches, the parsed CLI arguments
+/// - name: &str, name of the argument
+/// - default: &str, color used if the argument was not given
+/// # Returns
+/// Srgb<u8>, the parsed color
+fn parse_color_arg(matches: &clap::ArgMatches, name: &str, default: &str) -> Srgb<u8> {
+    let value = matches.value_of(name).unwrap_or(default);
+    match parse_color(value) {
+        Ok(color) => color,
+        Err(err) => {
+            eprintln!("Invalid value '{}' for --{}: {}", value, name, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Parse a length in pixels, like a line thickness or a cell size.
+/// # Params
+/// value: &str, length to parse, e.g. "2" or "0.5"
+/// # Returns
+/// Result<f32, String>, the length or an error if it isn't a positive number
+fn parse_pixels(value: &str) -> Result<f32, String> {
+    match value.trim().parse::<f32>() {
+        Ok(pixels) if pixels > 0.0 && pixels.is_finite() => Ok(pixels),
+        Ok(_) => Err("the value must be greater than 0".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_outcome_exit_code() {
+        use conway_engine::Outcome;
+        assert_eq!(outcome_exit_code(Some(Outcome::StepLimit), false), 0);
+        assert_eq!(outcome_exit_code(Some(Outcome::Stable), false), 2);
+        assert_eq!(outcome_exit_code(Some(Outcome::Stable), true), 3);
+        assert_eq!(outcome_exit_code(Some(Outcome::Oscillating(2)), false), 4);
+        assert_eq!(outcome_exit_code(Some(Outcome::Oscillating(15)), false), 4);
+        assert_eq!(outcome_exit_code(None, false), 5);
+        // 1 is kept for errors
+        for outcome in [
+            None,
+            Some(Outcome::StepLimit),
+            Some(Outcome::Stable),
+            Some(Outcome::Oscillating(3)),
+        ] {
+            assert_ne!(outcome_exit_code(outcome, false), 1);
+        }
+
+        // a lone cell dies out, a block stays, a blinker found by the cycle detection oscillates
+        let mut grid = conway_rust::Grid::new(4, 4, 0);
+        grid.set(1, 1, 1).unwrap();
+        let mut engine = conway_engine::ConwayEngine::from_grid(grid, "dot", 768.0, 1024.0, 0.0, 5);
+        assert_eq!(engine_exit_code(&engine), EXIT_RUNNING);
+        engine.step_many(5);
+        assert_eq!(engine_exit_code(&engine), EXIT_EXTINCT);
+        let mut engine =
+            conway_engine::ConwayEngine::from_pattern("blinker", 768.0, 1024.0, 0.0, 10).unwrap();
+        engine.set_cycle_detection(4);
+        engine.step_many(10);
+        assert_eq!(engine_exit_code(&engine), EXIT_OSCILLATING);
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("black"), Ok(Srgb::new(0, 0, 0)));
+        // names are case insensitive
+        assert_eq!(parse_color("WHITE"), Ok(Srgb::new(255, 255, 255)));
+        assert_eq!(parse_color("SteelBlue"), Ok(named::STEELBLUE));
+
+        let err = parse_color("blak").unwrap_err();
+        assert!(err.contains("black"));
+        assert!(parse_color("").is_err());
+    }
+
+    #[test]
+    fn test_grid_color() {
+        // default grid color
+        assert_eq!(parse_color("SLATEGREY"), Ok(named::SLATEGREY));
+        assert_eq!(parse_color("#708090"), Ok(named::SLATEGREY));
+        assert!(parse_color("slate").is_err());
+        assert!(parse_color("#70809").is_err());
+    }
+
+    #[test]
+    fn test_background_color() {
+        assert_eq!(parse_color("#202020"), Ok(Srgb::new(0x20, 0x20, 0x20)));
+        assert_eq!(parse_color("DarkSlateGray"), Ok(named::DARKSLATEGRAY));
+        assert!(parse_color("background").is_err());
+        assert!(parse_color("#2020").is_err());
+    }
+
+    #[test]
+    fn test_parse_pixels() {
+        assert_eq!(parse_pixels("1"), Ok(1.0));
+        assert_eq!(parse_pixels(" 2.5 "), Ok(2.5));
+        assert!(parse_pixels("0").unwrap_err().contains("greater than 0"));
+        assert!(parse_pixels("-1").is_err());
+        assert!(parse_pixels("inf").is_err());
+        assert!(parse_pixels("NaN").is_err());
+        assert!(parse_pixels("thick").is_err());
+        assert!(parse_pixels("").is_err());
+    }
+
+    #[test]
+    fn test_resolve_defaults() {
+        // both given, nothing to explain
+        assert_eq!(resolve_defaults(Some(0.25), Some(0)), (0.25, 0, Vec::new()));
+
+        // each missing option falls back to its default with a notice
+        let (update_rate, number_of_steps, notices) = resolve_defaults(None, Some(5));
+        assert_eq!((update_rate, number_of_steps), (DEFAULT_UPDATE_RATE, 5));
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].starts_with("No --rate given"));
+
+        let (update_rate, number_of_steps, notices) = resolve_defaults(None, None);
+        assert_eq!((update_rate, number_of_steps), (1.0, 20));
+        assert_eq!(notices.len(), 2);
+        assert_eq!(
+            notices[1],
+            "No --steps given, stopping after 20 steps unless the file sets them (--steps 0 runs forever)"
+        );
+    }
+
+    #[test]
+    fn test_format_histogram() {
+        use conway_engine::Outcome;
+        let outcomes = [
+            Outcome::Stable,
+            Outcome::Oscillating(2),
+            Outcome::Stable,
+            Outcome::Oscillating(3),
+            Outcome::Oscillating(2),
+            Outcome::StepLimit,
+            Outcome::Stable,
+            Outcome::Stable,
+        ];
+        assert_eq!(
+            format_histogram(&outcomes),
+            "stable           4 ####################\n\
+             oscillating      3 ###############\n  \
+               period 2       2\n  \
+               period 3       1\n\
+             step limit       1 #####\n"
+        );
+        assert_eq!(
+            format_histogram(&[]),
+            "stable           0 \noscillating      0 \nstep limit       0 \n"
+        );
+    }
+
+    #[test]
+    fn test_run_experiment() {
+        // a seeded experiment repeats
+        let outcomes =
+            run_experiment(5, 0.3, 50, Some(7), (12, 12), conway_engine::MAX_CELLS).unwrap();
+        assert_eq!(outcomes.len(), 5);
+        assert_eq!(
+            run_experiment(5, 0.3, 50, Some(7), (12, 12), conway_engine::MAX_CELLS).unwrap(),
+            outcomes
+        );
+        // empty boards all stabilize
+        assert!(
+            run_experiment(3, 0.0, 10, None, (8, 8), conway_engine::MAX_CELLS)
+                .unwrap()
+                .iter()
+                .all(|outcome| *outcome == conway_engine::Outcome::Stable)
+        );
+
+        assert!(run_experiment(0, 0.3, 50, None, (8, 8), conway_engine::MAX_CELLS).is_err());
+        assert!(run_experiment(5, 0.3, 0, None, (8, 8), conway_engine::MAX_CELLS).is_err());
+        assert!(run_experiment(5, 1.5, 50, None, (8, 8), conway_engine::MAX_CELLS).is_err());
+        // --max-cells applies to every board
+        assert!(run_experiment(5, 0.3, 50, None, (8, 8), 63).is_err());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("64x48"), Ok((48, 64)));
+        assert_eq!(parse_size("16X8"), Ok((8, 16)));
+        assert!(parse_size("64").is_err());
+        assert!(parse_size("64x").is_err());
+        assert!(parse_size("ax4").is_err());
+        assert!(parse_size("4x-4").is_err());
+    }
+
+    #[test]
+    fn test_run_soups() {
+        // a fixed base seed repeats the whole search and its report
+        let soups = run_soups(20, 0.5, 200, 42, (12, 12), conway_engine::MAX_CELLS).unwrap();
+        assert_eq!(soups.len(), 20);
+        assert_eq!(
+            run_soups(20, 0.5, 200, 42, (12, 12), conway_engine::MAX_CELLS).unwrap(),
+            soups
+        );
+        assert_eq!(
+            format_soup_report(&soups, 5),
+            format_soup_report(
+                &run_soups(20, 0.5, 200, 42, (12, 12), conway_engine::MAX_CELLS).unwrap(),
+                5
+            )
+        );
+        // soup i is the random board of seed 42 + i
+        for (index, soup) in soups.iter().enumerate() {
+            assert_eq!(soup.seed, 42 + index as u64);
+            assert!(soup.generation <= 200);
+        }
+        let replay =
+            conway_engine::generate_random_grid(12, 12, 0.5, Some(47), conway_engine::MAX_CELLS)
+                .unwrap();
+        let mut engine =
+            conway_engine::ConwayEngine::from_grid(replay, "soup", 768.0, 1024.0, 0.0, 200);
+        engine.set_cycle_detection(200);
+        engine.step_many(200);
+        assert_eq!(engine.population(), soups[5].population);
+        assert_eq!(engine.classify_outcome(), soups[5].outcome);
+
+        // a header and the top 5
+        assert_eq!(format_soup_report(&soups, 5).lines().count(), 1 + 5);
+        assert_eq!(format_soup_report(&soups, 50).lines().count(), 1 + 20);
+
+        assert!(run_soups(0, 0.5, 200, 42, (12, 12), conway_engine::MAX_CELLS).is_err());
+        assert!(run_soups(5, 0.5, 0, 42, (12, 12), conway_engine::MAX_CELLS).is_err());
+        assert!(run_soups(5, 0.5, 200, 42, (0, 12), conway_engine::MAX_CELLS).is_err());
+    }
+
+    #[test]
+    fn test_format_soup_report() {
+        use conway_engine::Outcome;
+        let soup = |seed, outcome, population| Soup {
+            seed,
+            outcome,
+            population,
+            generation: 100,
+        };
+        let soups = [
+            soup(0, Outcome::Stable, 0),
+            soup(1, Outcome::Oscillating(2), 30),
+            soup(2, Outcome::StepLimit, 80),
+            soup(3, Outcome::Oscillating(15), 22),
+            soup(4, Outcome::Stable, 12),
+            soup(5, Outcome::Oscillating(2), 41),
+        ];
+        assert_eq!(
+            format_soup_report(&soups, 4),
+            concat!(
+                "rank                 seed outcome      population generation\n",
+                "   1                    3 period 15            22        100\n",
+                "   2                    5 period 2             41        100\n",
+                "   3                    1 period 2             30        100\n",
+                "   4                    4 stable               12        100\n",
+            )
+        );
+        assert_eq!(format_soup_report(&soups, 0).lines().count(), 1);
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        // short hex doubles each digit
+        assert_eq!(parse_color("#fff"), Ok(Srgb::new(255, 255, 255)));
+        assert_eq!(parse_color("#1aF"), Ok(Srgb::new(0x11, 0xaa, 0xff)));
+        // long hex
+        assert_eq!(parse_color("#1a2b3c"), Ok(Srgb::new(0x1a, 0x2b, 0x3c)));
+        assert_eq!(parse_color("#1A2B3C"), Ok(Srgb::new(0x1a, 0x2b, 0x3c)));
+        // invalid length
+        assert!(parse_color("#1a2b").is_err());
+        assert!(parse_color("#").is_err());
+        assert!(parse_color("#1a2b3c4d").is_err());
+        // not hex
+        assert!(parse_color("#ggg").is_err());
+        assert!(parse_color("#+12").is_err());
+        // named colors still work
+        assert_eq!(parse_color("red"), Ok(named::RED));
+    }
 }
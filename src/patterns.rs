@@ -0,0 +1,77 @@
+/// Library of well known patterns bundled with the application, stored as RLE so they parse like ```.rle``` files.
+/// (name, RLE pattern) of each bundled pattern, names are lowercase
+pub const PATTERNS: &[(&str, &str)] = &[
+    ("glider", "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!"),
+    ("blinker", "x = 3, y = 1, rule = B3/S23\n3o!"),
+    ("toad", "x = 4, y = 2, rule = B3/S23\nb3o$3o!"),
+    ("beacon", "x = 4, y = 4, rule = B3/S23\n2o$2o$2b2o$2b2o!"),
+    (
+        "pulsar",
+        "x = 13, y = 13, rule = B3/S23\n\
+         2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$\
+         2b3o3b3o$o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    ),
+    (
+        "gosper-glider-gun",
+        "x = 36, y = 9, rule = B3/S23\n\
+         24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$\
+         2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!",
+    ),
+    ("lwss", "x = 5, y = 4, rule = B3/S23\nbo2bo$o$o3bo$4o!"),
+];
+
+/// Find a bundled pattern by name, ignoring case.
+/// # Params
+/// name: &str, name of the pattern, e.g. "glider" or "LWSS"
+/// # Returns
+/// Option<&'static str>, the RLE pattern, None if there is no pattern with that name
+pub fn find(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_ascii_lowercase();
+    PATTERNS
+        .iter()
+        .find(|(pattern_name, _)| *pattern_name == name)
+        .map(|(_, pattern)| *pattern)
+}
+
+/// Names of all the bundled patterns, for listing in error messages.
+/// # Returns
+/// String, the names separated by commas
+pub fn names() -> String {
+    PATTERNS
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::conway_engine::{parse_rle, MAX_CELLS};
+
+    #[test]
+    fn test_patterns_parse() {
+        let expected = [
+            ("glider", 5),
+            ("blinker", 3),
+            ("toad", 6),
+            ("beacon", 8),
+            ("pulsar", 48),
+            ("gosper-glider-gun", 36),
+            ("lwss", 9),
+        ];
+        assert_eq!(PATTERNS.len(), expected.len());
+        for (name, population) in expected.iter() {
+            let (grid, _ruleset) = parse_rle(find(name).unwrap(), MAX_CELLS).unwrap();
+            assert_eq!(grid.iter_live().count(), *population, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert!(find("glider").is_some());
+        assert_eq!(find("LWSS"), find("lwss"));
+        assert!(find("glider-gun").is_none());
+        assert!(names().starts_with("glider, blinker"));
+    }
+}
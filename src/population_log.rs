@@ -0,0 +1,93 @@
+/// Writes the population of every generation to a CSV file for plotting, see ```--log-population```.
+use conway_rust::conway_engine::ConwayEngine;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::{Arc, Mutex};
+
+/// CSV file with a ```generation,population``` header and one row per generation.
+/// Clones share the file, so the GUI can attach the same log to every engine it builds.
+#[derive(Clone)]
+pub struct PopulationLog {
+    writer: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl PopulationLog {
+    /// Create (or truncate) the CSV file and write its header.
+    /// # Params
+    /// path: &str, location of the CSV file
+    /// # Returns
+    /// io::Result<PopulationLog>, the log or the error from creating the file
+    pub fn create(path: &str) -> io::Result<PopulationLog> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "generation,population")?;
+        Ok(PopulationLog {
+            writer: Arc::new(Mutex::new(writer)),
+        })
+    }
+
+    /// Log the engine's current generation, then every generation it steps to through its step callback.
+    /// Attaching again after a reset starts over at the reset generation, the rows of the earlier run are kept.
+    /// # Params
+    /// engine: &mut ConwayEngine, engine to log, replaces its step callback
+    pub fn attach(&self, engine: &mut ConwayEngine) {
+        self.record(engine.get_generation(), engine.population());
+        let log = self.clone();
+        engine.set_step_callback(Box::new(move |generation, grid| {
+            log.record(generation, grid.count_live())
+        }));
+    }
+
+    /// Write the rows still in the buffer to the file, call it once the simulation ends or the window closes.
+    /// # Returns
+    /// io::Result<()>, the error from writing to the file, if any
+    pub fn flush(&self) -> io::Result<()> {
+        self.writer
+            .lock()
+            .expect("Log writer is never poisoned")
+            .flush()
+    }
+
+    /// Append a row, a write error is printed since the step callback can't return it.
+    /// # Params
+    /// - generation: usize, generation number
+    /// - population: usize, number of living cells
+    fn record(&self, generation: usize, population: usize) {
+        let mut writer = self.writer.lock().expect("Log writer is never poisoned");
+        if let Err(err) = writeln!(writer, "{},{}", generation, population) {
+            eprintln!("Failed to write the population log: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use conway_rust::{BoundaryCondition, Grid};
+
+    #[test]
+    fn test_population_log() {
+        let path = std::env::temp_dir().join("conway_rust_test_population_log.csv");
+        let log = PopulationLog::create(path.to_str().unwrap()).unwrap();
+
+        // a glider on a wrapped board keeps its population, the domino next to it dies out
+        let mut grid = Grid::new(10, 10, 0);
+        for (row, column) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2), (7, 7), (7, 8)] {
+            grid.set(row, column, 1).unwrap();
+        }
+        let mut engine = ConwayEngine::from_grid(grid, "log", 768.0, 1024.0, 0.0, 0);
+        engine.set_boundary(BoundaryCondition::Toroidal);
+        log.attach(&mut engine);
+        assert_eq!(engine.step_many(5), 5);
+        log.flush().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            "generation,population\n0,7\n1,5\n2,5\n3,5\n4,5\n5,5\n"
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        // a directory can't be opened as a file
+        assert!(PopulationLog::create(std::env::temp_dir().to_str().unwrap()).is_err());
+    }
+}
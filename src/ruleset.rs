@@ -0,0 +1,351 @@
+/// Handles Life-like rulesets written in B/S notation, e.g. "B3/S23" for Conway's Game of Life.
+/// Larger than Life rules, counting neighbors in a bigger radius (see ```ConwayEngine::set_radius```),
+/// write their counts as comma separated values and ranges instead, e.g. "B34..45/S33..57".
+use std::fmt;
+use std::str::FromStr;
+
+/// Largest neighbor count a rule can name, a radius 50 Moore neighborhood has 10200 cells
+pub const MAX_RULE_COUNT: usize = 10_000;
+
+/// Transition function for a single cell, for rules that B/S notation can't describe (see ```ConwayEngine::with_rule```).
+/// It has to be Send + Sync like the grid backends, the parallel step shares the engine between threads.
+pub trait Rule: Send + Sync {
+    /// Compute the next state of a cell.
+    /// # Params
+    /// - cell: usize, current value of the cell, its age if it is alive, 0 if it is dead
+    /// - neighbors: usize, number of living neighbors in the engine's neighborhood and boundary
+    /// # Returns
+    /// usize, value of the cell in the next generation, 0 for a dead cell
+    fn next_state(&self, cell: usize, neighbors: usize) -> usize;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ruleset {
+    /// Number of living neighbors that bring a dead cell to life
+    pub birth: Vec<usize>,
+    /// Number of living neighbors that keep a living cell alive
+    pub survival: Vec<usize>,
+}
+
+impl Ruleset {
+    /// Check if a dead cell with the given number of living neighbors is born.
+    /// # Params
+    /// - number_of_neighbors: usize, living neighbors around the dead cell
+    /// # Returns
+    /// bool, true if the cell comes to life
+    pub fn is_birth(&self, number_of_neighbors: usize) -> bool {
+        self.birth.contains(&number_of_neighbors)
+    }
+
+    /// Check if a living cell with the given number of living neighbors survives.
+    /// # Params
+    /// - number_of_neighbors: usize, living neighbors around the living cell
+    /// # Returns
+    /// bool, true if the cell stays alive
+    pub fn is_survival(&self, number_of_neighbors: usize) -> bool {
+        self.survival.contains(&number_of_neighbors)
+    }
+
+    /// Rulesets that run a B0 rule without the background flashing, the standard inversion trick.
+    /// With B0 every dead cell far from the pattern is born, so the real board alternates between a dead
+    /// and a living background.  Instead the grid is stored inverted on odd generations, keeping the
+    /// background dead: even generations step with the complement of the rule (writing the inverted board)
+    /// and odd generations step with the rule read from the inverted board (writing the real board).
+    /// # Params
+    /// max_neighbors: usize, size of the neighborhood, 8 for Moore and 4 for von Neumann
+    /// # Returns
+    /// Option<(Ruleset, Ruleset)>, (even, odd) generation rulesets, None without B0 or when a cell with every neighbor
+    /// alive survives (the background then stays alive for good, nothing flashes)
+    pub fn b0_rulesets(&self, max_neighbors: usize) -> Option<(Ruleset, Ruleset)> {
+        if !self.is_birth(0) || self.is_survival(max_neighbors) {
+            return None;
+        }
+        let counts = |keep: &dyn Fn(usize) -> bool| -> Vec<usize> {
+            (0..=max_neighbors).filter(|&count| keep(count)).collect()
+        };
+        let even = Ruleset {
+            birth: counts(&|count| !self.is_birth(count)),
+            survival: counts(&|count| !self.is_survival(count)),
+        };
+        let odd = Ruleset {
+            birth: counts(&|count| self.is_survival(max_neighbors - count)),
+            survival: counts(&|count| self.is_birth(max_neighbors - count)),
+        };
+        Some((even, odd))
+    }
+}
+
+impl Default for Ruleset {
+    /// Conway's Game of Life, B3/S23
+    fn default() -> Ruleset {
+        Ruleset {
+            birth: vec![3],
+            survival: vec![2, 3],
+        }
+    }
+}
+
+impl fmt::Display for Ruleset {
+    /// Write the ruleset in B/S notation, e.g. "B36/S23", the same form ```from_str``` parses.
+    /// Counts above 8 can't be single digits, those rulesets use the range form, e.g. "B34..45/S33..57".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let large = self
+            .birth
+            .iter()
+            .chain(&self.survival)
+            .any(|&count| count > 8);
+        let write_counts = if large { ranges } else { digits };
+        write!(
+            f,
+            "B{}/S{}",
+            write_counts(&self.birth),
+            write_counts(&self.survival)
+        )
+    }
+}
+
+impl FromStr for Ruleset {
+    type Err = &'static str;
+
+    /// Parse a rule string in B/S notation, e.g. "B36/S23".
+    /// The letters are case insensitive, and either list of digits can be empty ("B2/S").
+    /// A section with ".." or ',' lists counts and ranges up to ```MAX_RULE_COUNT``` instead, e.g. "B34..45/S8,33..57".
+    /// # Params
+    /// rule: &str, the rule string to parse
+    /// # Returns
+    /// Result<Ruleset, &'static str>, the parsed ruleset or an error describing the problem
+    fn from_str(rule: &str) -> Result<Ruleset, &'static str> {
+        let parts: Vec<&str> = rule.trim().split('/').collect();
+        if parts.len() != 2 {
+            return Err("Rule must be in the form B<digits>/S<digits>, e.g. B3/S23");
+        }
+
+        let birth = match parts[0].strip_prefix(['B', 'b']) {
+            Some(digits) => parse_neighbor_counts(digits)?,
+            None => return Err("Rule birth section must start with 'B', e.g. B3/S23"),
+        };
+        let survival = match parts[1].strip_prefix(['S', 's']) {
+            Some(digits) => parse_neighbor_counts(digits)?,
+            None => return Err("Rule survival section must start with 'S', e.g. B3/S23"),
+        };
+
+        Ok(Ruleset { birth, survival })
+    }
+}
+
+impl Rule for Ruleset {
+    /// Life-like transition, a surviving cell ages by 1 and a cell being born starts at 1.
+    fn next_state(&self, cell: usize, neighbors: usize) -> usize {
+        if cell > 0 {
+            if self.is_survival(neighbors) {
+                cell + 1
+            } else {
+                0
+            }
+        } else if self.is_birth(neighbors) {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Convert a string of digits, or of counts and ranges, into a sorted list of neighbor counts.
+/// # Params
+/// digits: &str, string of digits 0-8, or comma separated counts and ranges like "3,5..7"
+/// # Returns
+/// Result<Vec<usize>, &'static str>, the neighbor counts or an error if a character is not 0-8
+fn parse_neighbor_counts(digits: &str) -> Result<Vec<usize>, &'static str> {
+    if digits.contains("..") || digits.contains(',') {
+        return parse_count_ranges(digits);
+    }
+    let mut counts: Vec<usize> = Vec::new();
+    for digit in digits.chars() {
+        match digit.to_digit(10) {
+            Some(count) if count <= 8 => counts.push(count as usize),
+            _ => return Err("Rule neighbor counts must be digits from 0 to 8"),
+        }
+    }
+    counts.sort_unstable();
+    counts.dedup();
+    Ok(counts)
+}
+
+/// Convert comma separated counts and inclusive ranges, e.g. "34..45" or "2,5..7", into a sorted list of neighbor counts.
+/// # Params
+/// ranges: &str, counts and ranges from 0 to ```MAX_RULE_COUNT```
+/// # Returns
+/// Result<Vec<usize>, &'static str>, the neighbor counts or an error if an entry is malformed
+fn parse_count_ranges(ranges: &str) -> Result<Vec<usize>, &'static str> {
+    let parse_count = |count: &str| -> Result<usize, &'static str> {
+        match count.trim().parse::<usize>() {
+            Ok(count) if count <= MAX_RULE_COUNT => Ok(count),
+            Ok(_) => Err("Rule neighbor counts can't be more than 10000"),
+            Err(_) => Err("Rule neighbor ranges must be counts like 5 or ranges like 3..7"),
+        }
+    };
+    let mut counts: Vec<usize> = Vec::new();
+    for entry in ranges.split(',') {
+        let (low, high) = match entry.split_once("..") {
+            Some((low, high)) => (parse_count(low)?, parse_count(high)?),
+            None => {
+                let count = parse_count(entry)?;
+                (count, count)
+            }
+        };
+        if low > high {
+            return Err("Rule neighbor ranges must go from low to high, e.g. 3..7");
+        }
+        counts.extend(low..=high);
+    }
+    counts.sort_unstable();
+    counts.dedup();
+    Ok(counts)
+}
+
+/// Write neighbor counts as single digits, e.g. "23".
+/// # Params
+/// counts: &[usize], sorted counts from 0 to 8
+/// # Returns
+/// String, the digits
+fn digits(counts: &[usize]) -> String {
+    counts.iter().map(|count| count.to_string()).collect()
+}
+
+/// Write neighbor counts as comma separated counts and ranges, e.g. "8,33..57".
+/// # Params
+/// counts: &[usize], sorted counts without duplicates
+/// # Returns
+/// String, the counts with every run of consecutive counts written as a range
+fn ranges(counts: &[usize]) -> String {
+    let mut entries: Vec<String> = Vec::new();
+    let mut index = 0;
+    while index < counts.len() {
+        let low = counts[index];
+        while index + 1 < counts.len() && counts[index + 1] == counts[index] + 1 {
+            index += 1;
+        }
+        let high = counts[index];
+        entries.push(if low == high {
+            low.to_string()
+        } else {
+            format!("{}..{}", low, high)
+        });
+        index += 1;
+    }
+    entries.join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_next_state() {
+        let ruleset = Ruleset::default();
+        // survival ages the cell, birth starts at 1
+        assert_eq!(ruleset.next_state(4, 2), 5);
+        assert_eq!(ruleset.next_state(1, 3), 2);
+        assert_eq!(ruleset.next_state(0, 3), 1);
+        // death and staying dead
+        assert_eq!(ruleset.next_state(4, 1), 0);
+        assert_eq!(ruleset.next_state(0, 2), 0);
+    }
+
+    #[test]
+    fn test_b0_rulesets() {
+        // B0 flashes, the even ruleset is the complement and the odd one reads the inverted board
+        let ruleset: Ruleset = "B012/S".parse().unwrap();
+        let (even, odd) = ruleset.b0_rulesets(8).unwrap();
+        assert_eq!(even.to_string(), "B345678/S012345678");
+        assert_eq!(odd.to_string(), "B/S678");
+        // neither keeps the background flashing
+        assert!(!even.is_birth(0));
+        assert!(!odd.is_birth(0));
+
+        let (even, odd) = "B0/S1".parse::<Ruleset>().unwrap().b0_rulesets(4).unwrap();
+        assert_eq!(even.to_string(), "B1234/S0234");
+        assert_eq!(odd.to_string(), "B3/S4");
+
+        // no B0, or a background that stays alive
+        assert!(Ruleset::default().b0_rulesets(8).is_none());
+        assert!("B0/S8".parse::<Ruleset>().unwrap().b0_rulesets(8).is_none());
+        assert!("B0/S4".parse::<Ruleset>().unwrap().b0_rulesets(4).is_none());
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        let rule = "B3/S23".parse::<Ruleset>();
+        assert!(rule.is_ok());
+        assert_eq!(rule.unwrap(), Ruleset::default());
+
+        // HighLife
+        let rule = "B36/S23".parse::<Ruleset>().unwrap();
+        assert_eq!(rule.birth, vec![3, 6]);
+        assert_eq!(rule.survival, vec![2, 3]);
+
+        // Seeds, no survival
+        let rule = "b2/s".parse::<Ruleset>().unwrap();
+        assert_eq!(rule.birth, vec![2]);
+        assert!(rule.survival.is_empty());
+
+        // Day & Night
+        let rule = "B3678/S34678".parse::<Ruleset>().unwrap();
+        assert_eq!(rule.birth, vec![3, 6, 7, 8]);
+        assert_eq!(rule.survival, vec![3, 4, 6, 7, 8]);
+        assert!(rule.is_birth(6));
+        assert!(!rule.is_birth(4));
+        assert!(rule.is_survival(4));
+        assert!(!rule.is_survival(5));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Ruleset::default().to_string(), "B3/S23");
+        assert_eq!("b63/s32".parse::<Ruleset>().unwrap().to_string(), "B36/S23");
+        assert_eq!("B2/S".parse::<Ruleset>().unwrap().to_string(), "B2/S");
+        for rule in ["B3/S23", "B36/S23", "B3678/S34678", "B/S"].iter() {
+            assert_eq!(rule.parse::<Ruleset>().unwrap().to_string(), *rule);
+        }
+    }
+
+    #[test]
+    fn test_parse_range_rule() {
+        // Bosco's rule, radius 5
+        let rule = "B34..45/S33..57".parse::<Ruleset>().unwrap();
+        assert_eq!(rule.birth, (34..=45).collect::<Vec<usize>>());
+        assert_eq!(rule.survival, (33..=57).collect::<Vec<usize>>());
+        assert!(rule.is_birth(40));
+        assert!(!rule.is_birth(46));
+        assert_eq!(rule.to_string(), "B34..45/S33..57");
+
+        // single counts, overlapping ranges and the empty section
+        let rule = "b 9, 2..3 ,3..4/s".parse::<Ruleset>().unwrap();
+        assert_eq!(rule.birth, vec![2, 3, 4, 9]);
+        assert!(rule.survival.is_empty());
+        assert_eq!(rule.to_string(), "B2..4,9/S");
+
+        // small counts keep the digit form
+        assert_eq!(
+            "B3..3/S2..3".parse::<Ruleset>().unwrap(),
+            Ruleset::default()
+        );
+        assert_eq!("B1..1/S".parse::<Ruleset>().unwrap().to_string(), "B1/S");
+
+        assert!("B5..3/S".parse::<Ruleset>().is_err());
+        assert!("B3..x/S".parse::<Ruleset>().is_err());
+        assert!("B3,,4/S".parse::<Ruleset>().is_err());
+        assert!("B1..10001/S".parse::<Ruleset>().is_err());
+    }
+
+    #[test]
+    fn test_parse_bad_rule() {
+        assert!("".parse::<Ruleset>().is_err());
+        assert!("B3S23".parse::<Ruleset>().is_err());
+        assert!("3/23".parse::<Ruleset>().is_err());
+        assert!("S23/B3".parse::<Ruleset>().is_err());
+        assert!("B9/S23".parse::<Ruleset>().is_err());
+        assert!("B3/Sx".parse::<Ruleset>().is_err());
+        assert!("B3/S2/S3".parse::<Ruleset>().is_err());
+    }
+}
@@ -0,0 +1,250 @@
+/// Sparse grid that only stores living cells, meant for large boards that are mostly empty.
+use crate::grid::{neighbor_coordinates, BoundaryCondition, GridBackend, Neighborhood};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Handles a logical grid layout where only the living cells (greater than 0) are stored.
+/// Cells are kept in row-major order, so iterating matches the dense grid.
+pub struct SparseGrid {
+    row_size: usize,
+    column_size: usize,
+    /// (row, column) of each living cell and its value
+    cells: BTreeMap<(usize, usize), usize>,
+}
+
+impl SparseGrid {
+    /// Creates a new empty grid, every cell starts dead.
+    /// # Params
+    /// - row_size: usize, number of rows in the grid
+    /// - column_size: usize, number of columns in the grid
+    /// # Returns
+    /// SparseGrid, grid with no living cells
+    pub fn new(row_size: usize, column_size: usize) -> SparseGrid {
+        SparseGrid {
+            row_size,
+            column_size,
+            cells: BTreeMap::new(),
+        }
+    }
+
+    /// Copy the living cells of any grid into a new sparse grid.
+    /// # Params
+    /// grid: &dyn GridBackend, grid to copy
+    /// # Returns
+    /// SparseGrid, grid with the same size and living cells
+    pub fn from_backend(grid: &dyn GridBackend) -> SparseGrid {
+        let (row_size, column_size) = grid.size();
+        SparseGrid {
+            row_size,
+            column_size,
+            cells: grid
+                .iter_live()
+                .map(|(row, column, cell)| ((row, column), cell))
+                .collect(),
+        }
+    }
+}
+
+impl GridBackend for SparseGrid {
+    fn size(&self) -> (usize, usize) {
+        (self.row_size, self.column_size)
+    }
+
+    fn get(&self, row: usize, column: usize) -> Result<usize, &'static str> {
+        // check inputs
+        if row >= self.row_size {
+            return Err("Get row is out of bounds");
+        }
+        if column >= self.column_size {
+            return Err("Get column is out of bounds");
+        }
+        Ok(*self.cells.get(&(row, column)).unwrap_or(&0))
+    }
+
+    fn set(&mut self, row: usize, column: usize, data: usize) -> Result<(), &'static str> {
+        // check inputs
+        if row >= self.row_size {
+            return Err("Given row is out of grid bounds");
+        }
+        if column >= self.column_size {
+            return Err("Given column is out of grid bounds");
+        }
+        // dead cells aren't stored
+        if data > 0 {
+            self.cells.insert((row, column), data);
+        } else {
+            self.cells.remove(&(row, column));
+        }
+        Ok(())
+    }
+
+    fn iter_live(&self) -> Box<dyn Iterator<Item = (usize, usize, usize)> + '_> {
+        Box::new(
+            self.cells
+                .iter()
+                .map(|((row, column), cell)| (*row, *column, *cell)),
+        )
+    }
+
+    fn all_live(&self, check: &mut dyn FnMut(usize, usize, usize) -> bool) -> bool {
+        self.cells
+            .iter()
+            .all(|((row, column), cell)| check(*row, *column, *cell))
+    }
+
+    fn new_empty(&self, row_size: usize, column_size: usize) -> Box<dyn GridBackend> {
+        Box::new(SparseGrid::new(row_size, column_size))
+    }
+
+    fn clone_backend(&self) -> Box<dyn GridBackend> {
+        Box::new(SparseGrid {
+            row_size: self.row_size,
+            column_size: self.column_size,
+            cells: self.cells.clone(),
+        })
+    }
+
+    fn reset(&mut self, row_size: usize, column_size: usize) {
+        self.cells.clear();
+        self.row_size = row_size;
+        self.column_size = column_size;
+    }
+
+    /// Only living cells and their neighbors can be alive after the next step, every other cell is skipped.
+    /// With a living boundary the edge cells always have living neighbors, so they are candidates as well.
+    fn step_candidates(&self, boundary: BoundaryCondition, candidates: &mut Vec<(usize, usize)>) {
+        let wrap_edges = boundary == BoundaryCondition::Toroidal;
+        let mut unique = BTreeSet::new();
+        if boundary == BoundaryCondition::Alive {
+            for row in 0..self.row_size {
+                unique.insert((row, 0));
+                unique.insert((row, self.column_size.saturating_sub(1)));
+            }
+            for column in 0..self.column_size {
+                unique.insert((0, column));
+                unique.insert((self.row_size.saturating_sub(1), column));
+            }
+        }
+        for (row, column) in self.cells.keys() {
+            unique.insert((*row, *column));
+            // the Moore neighborhood covers the von Neumann neighborhood too
+            unique.extend(neighbor_coordinates(
+                *row,
+                *column,
+                self.size(),
+                Neighborhood::Moore,
+                wrap_edges,
+            ));
+        }
+        candidates.clear();
+        candidates.extend(unique);
+    }
+
+    fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::Grid;
+
+    /// Dense and sparse copies of a small board with a glider touching the top and left edges.
+    fn glider_grids() -> (Grid<usize>, SparseGrid) {
+        let mut dense = Grid::new(5, 6, 0);
+        for (row, column) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)].iter() {
+            dense.set(*row, *column, 1).unwrap();
+        }
+        let sparse = SparseGrid::from_backend(&dense);
+        (dense, sparse)
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = SparseGrid::new(3, 4);
+        assert_eq!(grid.size(), (3, 4));
+        assert_eq!(GridBackend::get(&grid, 2, 3), Ok(0));
+        grid.set(2, 3, 5).unwrap();
+        assert_eq!(GridBackend::get(&grid, 2, 3), Ok(5));
+        assert_eq!(grid.population(), 1);
+        // dead cells are removed
+        grid.set(2, 3, 0).unwrap();
+        assert_eq!(grid.population(), 0);
+        assert!(grid.cells.is_empty());
+
+        assert!(GridBackend::get(&grid, 3, 0).is_err());
+        assert!(GridBackend::get(&grid, 0, 4).is_err());
+        assert!(grid.set(3, 0, 1).is_err());
+        assert!(grid.set(0, 4, 1).is_err());
+    }
+
+    #[test]
+    fn test_matches_dense() {
+        let (dense, sparse) = glider_grids();
+        assert!(sparse.equals(&dense));
+        assert!(GridBackend::equals(&dense, &sparse));
+        assert_eq!(
+            GridBackend::iter_live(&sparse).collect::<Vec<_>>(),
+            GridBackend::iter_live(&dense).collect::<Vec<_>>()
+        );
+
+        let (row_size, column_size) = dense.size();
+        for kind in [Neighborhood::Moore, Neighborhood::VonNeumann].iter() {
+            for boundary in [
+                BoundaryCondition::Dead,
+                BoundaryCondition::Alive,
+                BoundaryCondition::Toroidal,
+            ]
+            .iter()
+            {
+                for row in 0..row_size {
+                    for column in 0..column_size {
+                        assert_eq!(
+                            sparse.count_neighbors(row, column, *kind, *boundary),
+                            dense.count_neighbors(row, column, *kind, *boundary)
+                        );
+                    }
+                }
+            }
+        }
+        assert!(sparse
+            .count_neighbors(5, 0, Neighborhood::Moore, BoundaryCondition::Dead)
+            .is_err());
+        assert_eq!(sparse.hash(), GridBackend::hash(&dense));
+    }
+
+    #[test]
+    fn test_step_candidates() {
+        let mut grid = SparseGrid::new(5, 5);
+        let mut candidates = Vec::new();
+        grid.step_candidates(BoundaryCondition::Dead, &mut candidates);
+        assert!(candidates.is_empty());
+        // a living boundary can bring the edges to life
+        grid.step_candidates(BoundaryCondition::Alive, &mut candidates);
+        assert_eq!(candidates.len(), 16);
+
+        // the buffer is cleared before it's filled
+        grid.set(0, 0, 1).unwrap();
+        grid.step_candidates(BoundaryCondition::Dead, &mut candidates);
+        assert_eq!(candidates, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+        // wrapped edges reach the opposite sides
+        grid.step_candidates(BoundaryCondition::Toroidal, &mut candidates);
+        assert_eq!(candidates.len(), 9);
+        assert!(candidates.contains(&(4, 4)));
+    }
+
+    #[test]
+    fn test_resize_centered() {
+        let (dense, sparse) = glider_grids();
+        assert!(sparse
+            .resize_centered(10, 12)
+            .equals(&Grid::resize_centered(&dense, 10, 12)));
+        assert!(sparse
+            .resize_centered(3, 3)
+            .equals(&Grid::resize_centered(&dense, 3, 3)));
+    }
+}
@@ -0,0 +1,78 @@
+//! Counts the allocations made while stepping, the engine should reuse its grids and buffers instead of making new ones.
+use conway_rust::{BoundaryCondition, ConwayEngine, Grid};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// System allocator that counts every allocation and reallocation since the last reset.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Steps measured for each boundary.
+const STEPS: usize = 100;
+
+/// Allocations allowed across the measured steps, rayon's job queue allocates a block every few dozen jobs.
+#[cfg(feature = "parallel")]
+const MAX_ALLOCATIONS: usize = 4;
+
+#[test]
+fn test_step_does_not_allocate() {
+    // a glider from the top left corner is still travelling when the measured steps are done
+    const SIZE: usize = 64;
+    for boundary in [
+        BoundaryCondition::Dead,
+        BoundaryCondition::Alive,
+        BoundaryCondition::Toroidal,
+    ] {
+        for history_capacity in [0, 8] {
+            let mut grid = Grid::new(SIZE, SIZE, 0);
+            for (row, column) in [(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)] {
+                grid.set(row, column, 1).unwrap();
+            }
+            let mut engine = ConwayEngine::from_grid(grid, "glider", 768.0, 1024.0, 0.0, 0);
+            engine.set_boundary(boundary);
+            engine.set_history_capacity(history_capacity);
+
+            // the buffers grow while the history fills up and the living edges settle down
+            for _ in 0..=history_capacity + STEPS {
+                engine.take_step();
+            }
+            let generation = engine.get_generation();
+            ALLOCATIONS.store(0, Ordering::SeqCst);
+            for _ in 0..STEPS {
+                engine.take_step();
+            }
+            let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+
+            // every step computed a new generation
+            assert_eq!(engine.get_generation(), generation + STEPS);
+            let message = format!(
+                "{:?} boundary, history {}: {} allocations in {} steps",
+                boundary, history_capacity, allocations, STEPS
+            );
+            #[cfg(feature = "parallel")]
+            assert!(allocations <= MAX_ALLOCATIONS, "{}", message);
+            #[cfg(not(feature = "parallel"))]
+            assert_eq!(allocations, 0, "{}", message);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Drives a ```--headless``` run through ```--control-port``` and checks the replies.
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+/// helper function, sends a command and reads the reply up to its closing "ok" (or error) line
+fn send(reader: &mut BufReader<TcpStream>, command: &str) -> Vec<String> {
+    writeln!(reader.get_mut(), "{}", command).expect("Failed to send the command");
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        assert!(
+            reader
+                .read_line(&mut line)
+                .expect("Failed to read the reply")
+                > 0
+        );
+        let line = line.trim_end().to_string();
+        let done = line == "ok" || line.starts_with("error: ");
+        lines.push(line);
+        if done {
+            return lines;
+        }
+    }
+}
+
+#[test]
+fn test_control_port() {
+    // ask the OS for a free port, then hand it to the application
+    let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.local_addr())
+        .expect("Failed to find a free port")
+        .port();
+    // an hour between steps, so only the commands move the board
+    let mut child = Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+        .args([
+            "--headless",
+            "-f",
+            "test-files/glider.rle",
+            "--rate",
+            "3600",
+            "--steps",
+            "0",
+            "--control-port",
+            &port.to_string(),
+        ])
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("Failed to run conway-rust");
+
+    // the listener is up once the application has parsed its arguments
+    let mut stream = None;
+    for _ in 0..100 {
+        if let Ok(connected) = TcpStream::connect((Ipv4Addr::LOCALHOST, port)) {
+            stream = Some(connected);
+            break;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    let mut reader = BufReader::new(stream.expect("Failed to connect to the control port"));
+
+    let dump = send(&mut reader, "dump");
+    assert_eq!(dump[0], "Generation 0");
+    // 10x10 board, then the "ok" line
+    assert_eq!(dump.len(), 12);
+    assert_eq!(
+        dump.iter()
+            .map(|line| line.matches('#').count())
+            .sum::<usize>(),
+        5
+    );
+
+    assert_eq!(send(&mut reader, "step"), vec!["ok"]);
+    assert_eq!(send(&mut reader, "dump")[0], "Generation 1");
+
+    // unknown commands are rejected without closing the connection
+    assert!(send(&mut reader, "jump")[0].starts_with("error: "));
+    assert_eq!(send(&mut reader, "pause"), vec!["ok"]);
+    assert_eq!(send(&mut reader, "step"), vec!["ok"]);
+    assert_eq!(send(&mut reader, "dump")[0], "Generation 2");
+    assert_eq!(send(&mut reader, "reset"), vec!["ok"]);
+    assert_eq!(send(&mut reader, "dump")[0], "Generation 0");
+
+    // a client that disconnects doesn't stop the simulation, the next one is served
+    drop(reader);
+    let stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).expect("Failed to reconnect");
+    let mut reader = BufReader::new(stream);
+    assert_eq!(send(&mut reader, "step"), vec!["ok"]);
+    assert_eq!(send(&mut reader, "dump")[0], "Generation 1");
+
+    child.kill().expect("Failed to stop conway-rust");
+    child.wait().expect("Failed to wait for conway-rust");
+}
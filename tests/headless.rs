@@ -0,0 +1,210 @@
+//! Runs the simulation with ```--headless``` and checks what it prints.
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[test]
+fn test_headless_output_is_clean() {
+    // the glider touches the top and left edges, so edge neighbors are counted every step
+    let output = Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+        .args([
+            "--headless",
+            "-f",
+            "test-files/glider.rle",
+            "--rate",
+            "0",
+            "--steps",
+            "2",
+        ])
+        .output()
+        .expect("Failed to run conway-rust");
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+
+    // only the generations, the 10x10 boards and the final line, nothing else
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3 * 11 + 1);
+    for (index, line) in lines[..lines.len() - 1].iter().enumerate() {
+        if index % 11 == 0 {
+            assert_eq!(*line, format!("Generation {}", index / 11));
+        } else {
+            assert_eq!(line.len(), 10, "{}", line);
+            assert!(
+                line.chars().all(|cell| cell == '#' || cell == '.'),
+                "{}",
+                line
+            );
+        }
+    }
+    assert_eq!(
+        lines[lines.len() - 1],
+        "Simulation ended after 2 generations"
+    );
+    // a glider has 5 cells in every generation
+    assert_eq!(stdout.matches('#').count(), 3 * 5);
+}
+
+#[test]
+fn test_headless_reads_stdin() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+        .args(["--headless", "-f", "-", "--rate", "0", "--steps", "1"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to run conway-rust");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(b"3, 3\n0,1,0\n0,1,0\n0,1,0\n")
+        .expect("Failed to write the pattern");
+    let output = child.wait_with_output().expect("Failed to run conway-rust");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Generation 0\n.#.\n.#.\n.#.\nGeneration 1\n...\n###\n...\nSimulation ended after 1 generations\n"
+    );
+}
+
+#[test]
+fn test_headless_rule_precedence() {
+    // the middle cell has 6 neighbors, it is only born with the HighLife rule from the file
+    let run = |extra_args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+            .args([
+                "--headless",
+                "-f",
+                "test-files/highlife.rle",
+                "--rate",
+                "0",
+                "--steps",
+                "1",
+            ])
+            .args(extra_args)
+            .output()
+            .expect("Failed to run conway-rust");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    assert_eq!(
+        run(&[]),
+        "Generation 0\n###\n#.#\n#..\nGeneration 1\n#.#\n###\n.#.\nSimulation ended after 1 generations\n"
+    );
+    // --rule wins over the file
+    assert_eq!(
+        run(&["--rule", "B3/S23"]),
+        "Generation 0\n###\n#.#\n#..\nGeneration 1\n#.#\n#.#\n.#.\nSimulation ended after 1 generations\n"
+    );
+}
+
+#[test]
+fn test_headless_exit_code() {
+    let run = |pattern: &[u8], steps: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+            .args(["--headless", "-f", "-", "--rate", "0", "--steps", steps])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .expect("Failed to run conway-rust");
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(pattern)
+            .expect("Failed to write the pattern");
+        child.wait().expect("Failed to run conway-rust").code()
+    };
+    // a lone cell dies out, a block never changes, a blinker repeats once the history sees it
+    assert_eq!(run(b"3, 3\n0,0,0\n0,1,0\n0,0,0\n", "5"), Some(3));
+    assert_eq!(
+        run(b"4, 4\n0,0,0,0\n0,1,1,0\n0,1,1,0\n0,0,0,0\n", "5"),
+        Some(2)
+    );
+    assert_eq!(run(b"3, 3\n0,1,0\n0,1,0\n0,1,0\n", "4"), Some(4));
+    assert_eq!(run(b"3, 3\n0,1,0\n0,1,0\n0,1,0\n", "1"), Some(0));
+}
+
+#[test]
+fn test_headless_max_cells() {
+    // the glider file is a 10x10 board
+    let run = |max_cells: &str| {
+        Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+            .args([
+                "--headless",
+                "-f",
+                "test-files/glider.rle",
+                "--rate",
+                "0",
+                "--steps",
+                "1",
+                "--max-cells",
+                max_cells,
+            ])
+            .output()
+            .expect("Failed to run conway-rust")
+    };
+    let output = run("99");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Grid has more cells than the maximum allowed"));
+    assert!(run("100").status.success());
+}
+
+#[test]
+fn test_headless_log_population() {
+    let path = std::env::temp_dir().join("conway_rust_test_headless_population.csv");
+    let output = Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+        .args([
+            "--headless",
+            "-f",
+            "test-files/glider.rle",
+            "--rate",
+            "0",
+            "--steps",
+            "5",
+            "--log-population",
+        ])
+        .arg(&path)
+        .output()
+        .expect("Failed to run conway-rust");
+    assert!(output.status.success());
+
+    // the header, then generation 0 and the 5 steps, a glider always has 5 cells
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 1 + 6);
+    assert_eq!(lines[0], "generation,population");
+    for (generation, line) in lines[1..].iter().enumerate() {
+        assert_eq!(*line, format!("{},5", generation));
+    }
+    std::fs::remove_file(&path).unwrap();
+
+    // a file that can't be created is reported before anything runs
+    let output = Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+        .args([
+            "--headless",
+            "--log-population",
+            "no-such-directory/log.csv",
+        ])
+        .output()
+        .expect("Failed to run conway-rust");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to open the population log"));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_headless_rejects_infinite_rate() {
+    // an infinite rate can't be turned into the time between steps, so it's an error instead of a panic
+    let output = Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+        .args(["--headless", "--pattern", "glider", "--rate", "inf"])
+        .output()
+        .expect("Failed to run conway-rust");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid value 'inf' for --rate"),
+        "{}",
+        stderr
+    );
+}
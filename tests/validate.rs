@@ -0,0 +1,49 @@
+//! Runs the ```validate``` subcommand against the files in ```test-files```.
+use std::process::{Command, Output};
+
+/// Run ```conway-rust validate <file>```.
+/// # Params
+/// file: &str, location of the file to validate
+/// # Returns
+/// Output, exit status and captured output of the command
+fn validate(file: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_conway-rust"))
+        .args(["validate", file])
+        .output()
+        .expect("Failed to run conway-rust")
+}
+
+#[test]
+fn test_validate_good_files() {
+    let output = validate("test-files/test.txt");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "OK: 5x5 grid, 5 live cells\n"
+    );
+
+    let output = validate("test-files/glider.rle");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with(", 5 live cells\n"));
+
+    let output = validate("test-files/glider.cells");
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_validate_bad_files() {
+    let output = validate("test-files/bad_test.txt");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("failed to parse cell data"));
+
+    let output = validate("test-files/zero_test.txt");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Header row and column size must be greater than zero"));
+
+    // a missing file is an error instead of the default pattern
+    let output = validate("test-files/no-file.txt");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to open file"));
+}